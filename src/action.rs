@@ -1,14 +1,27 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, path::PathBuf};
 
 use tokio::sync::mpsc;
 use wiki_api::{
+    backlinks::Backlinks,
+    category::CategoryMembers,
+    diff::Diff,
+    feed::Feed,
     languages::Language,
     page::{LanguageLink, Link, Page},
+    revisions::Revisions,
     search::{Search, SearchResult},
+    summary::Summary,
     Endpoint,
 };
 
-use crate::components::page::Renderer;
+use crate::{
+    app::{PaneId, SplitDirection},
+    components::page::Renderer,
+    config::WikiProfile,
+    export::ExportFormat,
+    pending_keys::MAX_COUNT,
+    request::RequestHandle,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
@@ -21,6 +34,7 @@ pub enum Action {
     // View Focus
     ToggleShowLogger,
     ShowPageLanguageSelection,
+    ShowProfileSelection,
     ShowHelp,
 
     /// PopupMessage(Title, Content)
@@ -35,6 +49,18 @@ pub enum Action {
     SwitchContextPage,
     SwitchPreviousContext,
 
+    // Split View
+    /// SplitView(Direction), shows a second pane or changes the direction of the split
+    SplitView(SplitDirection),
+    FocusOtherPane,
+    /// Close the focussed pane of the split view
+    ClosePane,
+    /// InOtherPane(Action), runs the action in the other pane, splitting the view when needed
+    InOtherPane(Box<Action>),
+    /// ToPane(Pane, Action), runs the action in the pane, whether it has the focus or not. Used
+    /// for the results of requests, which belong to the pane that started them
+    ToPane(PaneId, Box<Action>),
+
     // Scrolling
     ScrollUp(u16),
     ScrollDown(u16),
@@ -58,16 +84,55 @@ pub enum Action {
     SubmitSearchBar,
     ExitSearchBar,
 
+    // Command Line
+    EnterCommandLine,
+    ExitCommandLine,
+    /// RunCommand(Input), parses and runs the command typed into the command line
+    RunCommand(String),
+    /// SetOption(Option, Value), changes an option of the config until the program exits
+    SetOption(String, String),
+    /// SetVariant(Language, Variant), converts the pages and searches in the language to the
+    /// variant until the program exits
+    SetVariant(Language, String),
+    /// SetTheme(Name), replaces the theme with the one from the themes directory
+    SetTheme(String),
+
     // Page loading
     LoadSearchResult(SearchResult),
     LoadLink(Link),
     LoadLangaugeLink(LanguageLink),
-    /// Try to load a page, checking cache first
-    TryLoadPage(String, Language, Endpoint),
+    /// TryLoadPage(Title, Language, Endpoint, Focus), try to load a page, checking cache first.
+    /// When a focus is given, the page scrolls to it once it's displayed
+    TryLoadPage(String, Language, Endpoint, Option<PageFocus>),
+    /// LoadPage(Title, Language, Endpoint, Focus), fetch a page from the api, cancelling the page
+    /// that is still loading
+    LoadPage(String, Language, Endpoint, Option<PageFocus>),
+    LoadRandomPage,
+    LoadFeaturedPage,
+    LoadFeed,
+    /// ShowFeed(Feed)
+    ShowFeed(Feed),
+    ShowPageHistory,
+    ShowPageCategories,
+    ShowPageBacklinks,
+    /// ShowSummary(Title, Language, Endpoint)
+    ShowSummary(String, Language, Endpoint),
+    /// LoadRevision(Title, RevisionId, Language, Endpoint)
+    LoadRevision(String, usize, Language, Endpoint),
+    /// CompareRevisions(Title, FromRevision, ToRevision, Endpoint)
+    CompareRevisions(String, usize, usize, Endpoint),
+    /// LoadChanges(Page), fetches the current version of the page and shows what changed
+    LoadChanges(Box<Page>),
+    /// ShowDiff(Title, Diff)
+    ShowDiff(String, Diff),
 
     Search(SearchAction),
     Page(PageAction),
     PageViewer(PageViewerAction),
+    History(HistoryAction),
+    Category(CategoryAction),
+    Backlinks(BacklinksAction),
+    Summary(SummaryAction),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -75,10 +140,14 @@ pub enum SearchAction {
     StartSearch(String),
     FinshSearch(Search),
     ContinueSearch,
+    CancelSearch,
     ClearSearchResults,
     OpenSearchResult,
     ChangeMode(crate::components::search::Mode),
     ChangeLanguage(Language),
+    ChangeProfile(WikiProfile),
+    /// ShowFeatured(Feed), shows the featured article of the feed on the dashboard
+    ShowFeatured(Feed),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -96,14 +165,83 @@ pub enum PageAction {
     SelectNextLink,
 
     GoToHeader(String),
+    /// Scroll to the next or previous header in the page
+    NextSection,
+    PrevSection,
+    /// Scroll to the next or previous header at the level of the current section
+    NextSectionSameLevel,
+    PrevSectionSameLevel,
+    /// Fold or unfold the section at the top of the page
+    ToggleFold,
+    /// FoldToLevel(Level), fold the sections below this level. On level 0, every section is
+    /// folded, on level 1 only the subsections and so on
+    FoldToLevel(u8),
+    UnfoldAll,
+    /// Label the links in the viewport, typing a label follows the link
+    ShowLinkHints,
+    ToggleZenMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryAction {
+    /// LoadHistory(Title, Language, Endpoint, ContinueFrom)
+    LoadHistory(String, Language, Endpoint, Option<String>),
+    FinishHistory(Revisions),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CategoryAction {
+    /// LoadMembers(Category, Language, Endpoint, ContinueFrom)
+    LoadMembers(String, Language, Endpoint, Option<String>),
+    FinishMembers(CategoryMembers),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BacklinksAction {
+    /// LoadBacklinks(Title, Language, Endpoint, ContinueFrom)
+    LoadBacklinks(String, Language, Endpoint, Option<String>),
+    ContinueBacklinks,
+    FinishBacklinks(Backlinks),
+}
+
+/// Where a page scrolls to once it's displayed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageFocus {
+    /// Scroll to the header with this anchor
+    Header(String),
+    /// Select the first link to this title
+    LinkTo(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SummaryAction {
+    /// LoadSummary(Title, Language, Endpoint)
+    LoadSummary(String, Language, Endpoint),
+    FinishSummary(Summary),
+    /// FailSummary(Title)
+    FailSummary(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PageViewerAction {
-    DisplayPage(Page),
+    /// DisplayPage(Page, Focus)
+    DisplayPage(Box<Page>, Option<PageFocus>),
     PopPage,
-    ExitLoading,
+    /// StartLoading(Request), shows the progress of the request until the page is displayed
+    StartLoading(RequestHandle),
+    /// Cancel the page that is currently loading
+    CancelLoading,
+    /// ExitLoading(Request), hides the processing screen when it still shows the request
+    ExitLoading(RequestHandle),
     SaveCache,
+    ShowChanges,
+    /// CachePage(Page, RequestedTitle), stores a prefetched page without displaying it
+    CachePage(Box<Page>, String),
+    /// Export(Format, Path), saves the current page to the file. Without a path, the page is
+    /// saved as `<title>.<extension>` in the working directory
+    Export(ExportFormat, Option<PathBuf>),
+    /// Bookmark the current page, or remove its bookmark
+    ToggleBookmark,
 }
 
 pub enum ActionResult {
@@ -154,6 +292,41 @@ impl ActionPacket {
         self.actions.push(action);
     }
 
+    /// Runs all actions in the pane
+    pub fn in_pane(self, pane: PaneId) -> Self {
+        Self {
+            actions: self
+                .actions
+                .into_iter()
+                .map(|action| Action::ToPane(pane, Box::new(action)))
+                .collect(),
+        }
+    }
+
+    /// Applies a count typed before the binding, e.g. the `5` in `5 j`. Scrolling goes `count`
+    /// times as far, moving the link selection or jumping between sections is repeated and other
+    /// actions are kept as they are. Counts above [`MAX_COUNT`] are cut off
+    pub fn with_count(self, count: u16) -> Self {
+        let count = count.min(MAX_COUNT);
+        let mut actions = Vec::with_capacity(self.actions.len());
+        for action in self.actions {
+            match action {
+                Action::ScrollUp(n) => actions.push(Action::ScrollUp(n.saturating_mul(count))),
+                Action::ScrollDown(n) => actions.push(Action::ScrollDown(n.saturating_mul(count))),
+                Action::Page(
+                    PageAction::SelectPrevLink
+                    | PageAction::SelectNextLink
+                    | PageAction::NextSection
+                    | PageAction::PrevSection
+                    | PageAction::NextSectionSameLevel
+                    | PageAction::PrevSectionSameLevel,
+                ) => actions.extend(std::iter::repeat(action).take(count as usize)),
+                action => actions.push(action),
+            }
+        }
+        Self { actions }
+    }
+
     pub fn send(self, action_tx: &mpsc::UnboundedSender<Action>) {
         for action in self.actions {
             action_tx.send(action).unwrap();
@@ -178,3 +351,23 @@ impl Debug for ActionPacket {
         f.debug_list().entries(self.actions.iter()).finish()
     }
 }
+
+/// Sends actions to one pane, so the results of a request reach the pane that started it even
+/// when the focus moved to the other one
+#[derive(Clone)]
+pub struct PaneSender {
+    action_tx: mpsc::UnboundedSender<Action>,
+    pane: PaneId,
+}
+
+impl PaneSender {
+    pub fn new(action_tx: mpsc::UnboundedSender<Action>, pane: PaneId) -> Self {
+        Self { action_tx, pane }
+    }
+
+    pub fn send(&self, action: Action) {
+        self.action_tx
+            .send(Action::ToPane(self.pane, Box::new(action)))
+            .unwrap();
+    }
+}
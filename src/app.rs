@@ -1,47 +1,111 @@
-use std::sync::Arc;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::{
-    prelude::{Constraint, Direction, Layout, Rect},
-    style::Style,
+    prelude::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
     widgets::Block,
 };
-use tracing::warn;
+use tracing::{debug, warn};
 
 use tokio::sync::mpsc;
 
 use crate::{
-    action::{Action, ActionPacket, ActionResult},
+    action::{
+        Action, ActionPacket, ActionResult, BacklinksAction, CategoryAction, HistoryAction,
+        PageAction, PageFocus, PageViewerAction, SearchAction, SummaryAction,
+    },
+    commands::Command,
     components::{
+        backlinks_popup::BacklinksPopupComponent,
+        category_popup::CategoryPopupComponent,
+        command_line::CommandLineComponent,
+        diff_view::DiffViewComponent,
+        feed_popup::FeedPopupComponent,
         help_popup::HelpPopupComponent,
+        history_popup::HistoryPopupComponent,
         logger::LoggerComponent,
         message_popup::MessagePopupComponent,
         page_viewer::PageViewer,
+        profile_popup::ProfileSelectionComponent,
         search::SearchComponent,
         search_bar::{SearchBarComponent, SEARCH_BAR_HEIGTH},
         search_language_popup::SearchLanguageSelectionComponent,
+        summary_popup::SummaryPopupComponent,
         Component,
     },
-    config::{Config, Theme, ZenModeComponents},
+    config::{
+        load_named_theme, Binding, BindingContext, Config, DashboardSection, Theme,
+        ZenModeComponents,
+    },
+    event::Event,
     has_modifier,
+    page_cache::{PageCache, SharedPageCache},
     page_loader::PageLoader,
+    pending_keys::PendingKeys,
+    request::RequestHandle,
     terminal::Frame,
 };
-use wiki_api::page::Link;
+use wiki_api::{languages::Language, page::Link, Endpoint};
 
 const CONTEXT_SEARCH: u8 = 0;
 const CONTEXT_PAGE: u8 = 1;
 
+/// Identifies a pane of the split view, the results of its requests are sent back to it
+pub type PaneId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// The panes are side by side
+    Vertical,
+    /// The panes are above each other
+    Horizontal,
+}
+
+/// The pane of the split view without focus. The focussed pane is the page viewer and context of
+/// the app, switching the focus swaps them with the ones of this pane
+struct SplitView {
+    direction: SplitDirection,
+    page: PageViewer,
+    context: u8,
+    prev_context: u8,
+
+    /// Whether the focussed pane is the second one, on the right or at the bottom
+    focus_second: bool,
+    /// Where this pane was rendered, clicking into it moves the focus there
+    area: Rect,
+}
+
+impl SplitView {
+    /// Exchanges the page viewer and context of this pane with the focussed ones
+    fn swap(&mut self, page: &mut PageViewer, context: &mut u8, prev_context: &mut u8) {
+        std::mem::swap(page, &mut self.page);
+        std::mem::swap(context, &mut self.context);
+        std::mem::swap(prev_context, &mut self.prev_context);
+    }
+}
+
 #[derive(Default)]
 pub struct AppComponent {
     search: SearchComponent,
     page: PageViewer,
+    split: Option<SplitView>,
+    /// Shared by the page viewers of both panes
+    page_cache: SharedPageCache,
+    /// Id of the pane that was created last
+    last_pane: PaneId,
     logger: LoggerComponent,
     search_bar: SearchBarComponent,
+    command_line: CommandLineComponent,
     page_loader: Option<PageLoader>,
 
     is_logger: bool,
+    /// Count and keys typed for a binding that is not complete yet
+    pending_keys: PendingKeys,
 
     popups: Vec<Box<dyn Component + Send>>,
     config: Arc<Config>,
@@ -59,76 +123,224 @@ impl AppComponent {
         std::mem::swap(&mut self.prev_context, &mut self.context);
     }
 
+    /// Shows the request on the processing screen of the focussed pane. This happens before the
+    /// request can finish, so its result can't arrive before it's shown
+    fn start_loading(&mut self, request: RequestHandle) -> ActionResult {
+        self.switch_context(CONTEXT_PAGE);
+        self.page
+            .update(Action::PageViewer(PageViewerAction::StartLoading(request)))
+    }
+
+    /// Shows a second pane with the search, or changes the direction when the view is split
+    /// already. The new pane gets its own page viewer, starting with the cached pages
+    fn split_view(&mut self, direction: SplitDirection) -> ActionResult {
+        if let Some(ref mut split) = self.split {
+            split.direction = direction;
+            return ActionResult::consumed();
+        }
+
+        // the new pane finds the open pages in the cache
+        self.page.sync_cache();
+        self.last_pane += 1;
+        let mut page = PageViewer::new(self.last_pane, self.page_cache.clone());
+        if let Err(error) = page.init(
+            self.action_tx.clone().unwrap(),
+            self.config.clone(),
+            self.theme.clone(),
+        ) {
+            return Action::PopupError(format!("Unable to split the view: {:#}", error)).into();
+        }
+
+        self.split = Some(SplitView {
+            direction,
+            page,
+            context: CONTEXT_SEARCH,
+            prev_context: CONTEXT_SEARCH,
+            focus_second: false,
+            area: Rect::default(),
+        });
+        ActionResult::consumed()
+    }
+
+    fn focus_other_pane(&mut self) {
+        let split = match self.split {
+            Some(ref mut split) => split,
+            None => return,
+        };
+
+        split.swap(&mut self.page, &mut self.context, &mut self.prev_context);
+        split.focus_second = !split.focus_second;
+
+        if self.context == CONTEXT_SEARCH {
+            self.search
+                .refresh_dashboard(self.page.read_pages(), self.page.bookmarks());
+        }
+    }
+
+    /// Closes the focussed pane, the other one gets the focus. The pages of the closed pane are
+    /// kept in the cache
+    fn close_pane(&mut self) -> ActionResult {
+        if self.split.is_none() {
+            return ActionResult::Ignored;
+        }
+
+        self.focus_other_pane();
+        if let Some(mut split) = self.split.take() {
+            split.page.sync_cache();
+        }
+        ActionResult::consumed()
+    }
+
+    /// Runs the action in the pane with this id, the resulting actions run there as well. The
+    /// pane without focus is swapped in while the action runs. Actions of a closed pane are
+    /// dropped
+    fn run_in_pane(&mut self, pane: PaneId, action: Action) -> ActionResult {
+        let result = if self.page.pane() == pane {
+            self.update(action)
+        } else {
+            let mut split = match self.split.take() {
+                Some(split) if split.page.pane() == pane => split,
+                split => {
+                    debug!("dropping {:?}, the pane {} was closed", action, pane);
+                    self.split = split;
+                    return ActionResult::consumed();
+                }
+            };
+
+            split.swap(&mut self.page, &mut self.context, &mut self.prev_context);
+            let result = self.update(action);
+            split.swap(&mut self.page, &mut self.context, &mut self.prev_context);
+            self.split = Some(split);
+            result
+        };
+
+        match result {
+            ActionResult::Consumed(actions) => actions.in_pane(pane).into(),
+            result => result,
+        }
+    }
+
+    /// Runs the action in the other pane, which also gets the focus
+    fn in_other_pane(&mut self, action: Action) -> ActionResult {
+        if self.split.is_none() {
+            let result = self.split_view(SplitDirection::Vertical);
+            if self.split.is_none() {
+                return result;
+            }
+        }
+
+        self.focus_other_pane();
+        action.into()
+    }
+
     fn show_page_language(&mut self) {
         let selection_widget = self.page.get_page_language_selection_popup();
         self.popups.push(Box::new(selection_widget));
     }
 
-    fn render_search_bar(&mut self, f: &mut Frame<'_>, area: Rect) -> Rect {
-        let (search_bar_area, area) = {
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Min(SEARCH_BAR_HEIGTH),
-                    Constraint::Percentage(100),
-                ])
-                .split(area);
-            (chunks[0], chunks[1])
+    /// Returns the endpoint and language currently used for searching
+    fn search_site(&self) -> (Endpoint, Language) {
+        (
+            self.search
+                .endpoint
+                .clone()
+                .unwrap_or(self.config.api.endpoint.clone()),
+            self.search.language.unwrap_or(self.config.api.language),
+        )
+    }
+
+    fn show_page_history(&mut self) -> ActionResult {
+        let page = match self.page.current_page() {
+            Some(page) => &page.page,
+            None => return ActionResult::consumed(),
         };
 
-        self.search_bar.render(f, search_bar_area);
-        area
+        self.popups.push(Box::new(HistoryPopupComponent::new(
+            page.title.clone(),
+            page.revision_id,
+            page.language,
+            page.endpoint.clone(),
+            self.config.clone(),
+            self.theme.clone(),
+        )));
+
+        Action::History(HistoryAction::LoadHistory(
+            page.title.clone(),
+            page.language,
+            page.endpoint.clone(),
+            None,
+        ))
+        .into()
     }
-}
 
-impl Component for AppComponent {
-    fn init(
-        &mut self,
-        action_tx: mpsc::UnboundedSender<Action>,
-        config: Arc<Config>,
-        theme: Arc<Theme>,
-    ) -> Result<()> {
-        self.search
-            .init(action_tx.clone(), config.clone(), theme.clone())?;
-        self.page
-            .init(action_tx.clone(), config.clone(), theme.clone())?;
-        self.search_bar
-            .init(action_tx.clone(), config.clone(), theme.clone())?;
+    fn show_page_categories(&mut self) {
+        let page = match self.page.current_page() {
+            Some(page) => &page.page,
+            None => return,
+        };
 
-        self.page_loader = Some(PageLoader::new(config.clone(), action_tx.clone()));
+        let categories = page
+            .categories
+            .iter()
+            .flatten()
+            .filter(|category| !category.hidden)
+            .map(|category| category.title())
+            .collect();
 
-        action_tx.send(Action::EnterSearchBar).unwrap();
-        self.action_tx = Some(action_tx);
+        self.popups.push(Box::new(CategoryPopupComponent::new(
+            page.title.clone(),
+            categories,
+            page.language,
+            page.endpoint.clone(),
+            self.config.clone(),
+            self.theme.clone(),
+        )));
+    }
 
-        self.config = config;
-        self.theme = theme;
+    fn show_page_backlinks(&mut self) -> ActionResult {
+        let page = match self.page.current_page() {
+            Some(page) => &page.page,
+            None => return ActionResult::consumed(),
+        };
 
-        Ok(())
+        self.popups.push(Box::new(BacklinksPopupComponent::new(
+            page.title.clone(),
+            page.language,
+            page.endpoint.clone(),
+            self.config.clone(),
+            self.theme.clone(),
+        )));
+
+        Action::Backlinks(BacklinksAction::LoadBacklinks(
+            page.title.clone(),
+            page.language,
+            page.endpoint.clone(),
+            None,
+        ))
+        .into()
     }
-    fn handle_key_events(&mut self, key: KeyEvent) -> ActionResult {
-        // we need to always handle CTRL-C
-        if matches!(key.code, KeyCode::Char('c') if has_modifier!(key, Modifier::CONTROL)) {
-            return ActionPacket::single(Action::PageViewer(
-                crate::action::PageViewerAction::SaveCache,
-            ))
-            .action(Action::Quit)
-            .into();
+
+    /// Lets the popup, the search bar or the current context handle the key, then checks the
+    /// global bindings. The pending keys are the keys of a sequence typed before this one
+    fn dispatch_key(&mut self, key: KeyEvent, pending: &[Binding]) -> ActionResult {
+        if self.command_line.is_focussed {
+            return self.command_line.handle_key_events(key, pending);
         }
 
         if let Some(ref mut popup) = self.popups.last_mut() {
-            let result = popup.handle_key_events(key);
+            let result = popup.handle_key_events(key, pending);
             if result.is_consumed() {
                 return result;
             }
         }
 
         if self.search_bar.is_focussed {
-            return self.search_bar.handle_key_events(key);
+            return self.search_bar.handle_key_events(key, pending);
         }
 
         let result = match self.context {
-            CONTEXT_SEARCH => self.search.handle_key_events(key),
-            CONTEXT_PAGE => self.page.handle_key_events(key),
+            CONTEXT_SEARCH => self.search.handle_key_events(key, pending),
+            CONTEXT_PAGE => self.page.handle_key_events(key, pending),
             _ => {
                 warn!("unknown context");
                 ActionResult::Ignored
@@ -142,7 +354,7 @@ impl Component for AppComponent {
         let global_bindings = &self.config.bindings.global;
         macro_rules! match_bindings {
             ($($bind:ident => $action:expr),+) => {
-                $(if global_bindings.$bind.matches_event(key) {
+                $(if global_bindings.$bind.matches_event(key, pending) {
                     return $action.into();
                 })+
             };
@@ -158,6 +370,11 @@ impl Component for AppComponent {
             switch_context_search => Action::SwitchContextSearch,
             switch_context_page => Action::SwitchContextPage,
 
+            split_vertical => Action::SplitView(SplitDirection::Vertical),
+            split_horizontal => Action::SplitView(SplitDirection::Horizontal),
+            focus_other_pane => Action::FocusOtherPane,
+            close_pane => Action::ClosePane,
+
             scroll_down => Action::ScrollDown(1),
             scroll_up => Action::ScrollUp(1),
 
@@ -169,6 +386,7 @@ impl Component for AppComponent {
 
             unselect_scroll => Action::UnselectScroll,
             enter_search_bar => Action::EnterSearchBar,
+            command_line => Action::EnterCommandLine,
 
             toggle_search_language_selection => {
                 self.popups
@@ -178,6 +396,10 @@ impl Component for AppComponent {
                     )));
                 ActionResult::consumed()
             },
+            toggle_profile_selection => Action::ShowProfileSelection,
+
+            random_page => Action::LoadRandomPage,
+            show_feed => Action::LoadFeed,
 
             help => Action::ShowHelp
         );
@@ -185,6 +407,292 @@ impl Component for AppComponent {
         ActionResult::Ignored
     }
 
+    /// Handles the key together with the pending keys typed before it. When they don't form a
+    /// binding, the pending keys are dropped and the key is handled on its own. The count is
+    /// applied to the resulting actions
+    fn finish_keys(&mut self, key: KeyEvent) -> ActionResult {
+        let pending = self.pending_keys.keys().to_vec();
+        let mut result = self.dispatch_key(key, &pending);
+        if !result.is_consumed() && !pending.is_empty() {
+            self.pending_keys.set_keys(Vec::new());
+            result = self.dispatch_key(key, &[]);
+        }
+
+        match (result, self.pending_keys.finish()) {
+            (ActionResult::Consumed(actions), Some(count)) => actions.with_count(count).into(),
+            (result, _) => result,
+        }
+    }
+
+    /// Collects counts and key sequences before the key is dispatched
+    fn handle_key(&mut self, key: KeyEvent) -> ActionResult {
+        // we need to always handle CTRL-C
+        if matches!(key.code, KeyCode::Char('c') if has_modifier!(key, Modifier::CONTROL)) {
+            return ActionPacket::single(Action::PageViewer(
+                crate::action::PageViewerAction::SaveCache,
+            ))
+            .action(Action::Quit)
+            .into();
+        }
+
+        // counts and key sequences are not collected while typing text
+        if self.is_typing() {
+            self.pending_keys.finish();
+            return self.dispatch_key(key, &[]);
+        }
+
+        if let KeyCode::Char(c) = key.code {
+            if key.modifiers == KeyModifiers::NONE && self.pending_keys.push_digit(c) {
+                return ActionResult::consumed();
+            }
+        }
+
+        // keys that fall through the popups are handled by the current context
+        let context = match self.context {
+            CONTEXT_PAGE => BindingContext::Page,
+            _ => BindingContext::Search,
+        };
+        let mut keys = self.pending_keys.keys().to_vec();
+        keys.push(key.into());
+        if self.config.bindings.continues(&keys, context) {
+            self.pending_keys.set_keys(keys);
+            return ActionResult::consumed();
+        }
+
+        self.finish_keys(key)
+    }
+
+    /// Whether the command line, the focussed popup, the search bar or the current context is
+    /// reading text
+    fn is_typing(&self) -> bool {
+        if self.command_line.is_focussed || self.search_bar.is_focussed {
+            return true;
+        }
+
+        match self.popups.last() {
+            Some(popup) => popup.is_typing(),
+            None => self.context == CONTEXT_PAGE && self.page.is_typing(),
+        }
+    }
+
+    /// Parses the command and turns it into actions. Invalid commands are shown in an error popup
+    fn run_command(&mut self, input: &str) -> ActionResult {
+        let command = match input.parse::<Command>() {
+            Ok(command) => command,
+            Err(error) => return Action::PopupError(format!("{:#}", error)).into(),
+        };
+
+        let (endpoint, language) = self.search_site();
+        match command {
+            Command::Open(title) => Action::TryLoadPage(title, language, endpoint, None).into(),
+            Command::Search(query) => ActionPacket::single(Action::SwitchContextSearch)
+                .action(Action::Search(SearchAction::StartSearch(query)))
+                .into(),
+            Command::Language(language) => {
+                let mut packet =
+                    ActionPacket::single(Action::Search(SearchAction::ChangeLanguage(language)));
+                if self.config.ui.popup_search_language_changed {
+                    packet = packet.action(Action::PopupMessage(
+                        "Information".to_string(),
+                        format!("Changed the language for searches to '{}'", language.name()),
+                    ));
+                }
+                packet.into()
+            }
+            Command::Profile(name) => match self.config.api.profile(&name) {
+                Some(profile) => {
+                    Action::Search(SearchAction::ChangeProfile(profile.clone())).into()
+                }
+                None => Action::PopupError(format!("the profile '{}' doesn't exist", name)).into(),
+            },
+            Command::Export(format, path) => {
+                Action::PageViewer(PageViewerAction::Export(format, path)).into()
+            }
+            Command::Set(option, value) => Action::SetOption(option, value).into(),
+            Command::Zen => Action::Page(PageAction::ToggleZenMode).into(),
+            Command::Toc => Action::Page(PageAction::ToggleContents).into(),
+            Command::Fold(level) => Action::Page(PageAction::FoldToLevel(level)).into(),
+            Command::Unfold => Action::Page(PageAction::UnfoldAll).into(),
+            Command::Bookmark => Action::PageViewer(PageViewerAction::ToggleBookmark).into(),
+            Command::Split(direction) => Action::SplitView(direction).into(),
+            Command::Close => Action::ClosePane.into(),
+            Command::Theme(name) => Action::SetTheme(name).into(),
+            Command::Random => Action::LoadRandomPage.into(),
+            Command::Featured => Action::LoadFeaturedPage.into(),
+            Command::Feed => Action::LoadFeed.into(),
+            Command::History => Action::ShowPageHistory.into(),
+            Command::Categories => Action::ShowPageCategories.into(),
+            Command::Backlinks => Action::ShowPageBacklinks.into(),
+            Command::Back => Action::PageViewer(PageViewerAction::PopPage).into(),
+            Command::Logger => Action::ToggleShowLogger.into(),
+            Command::Help => Action::ShowHelp.into(),
+            Command::Quit => ActionPacket::single(Action::PageViewer(PageViewerAction::SaveCache))
+                .action(Action::Quit)
+                .into(),
+        }
+    }
+
+    /// Passes the changed config and theme on to the components. Open popups keep the old ones
+    fn reconfigure(&mut self, config: Config, theme: Theme) {
+        self.config = Arc::new(config);
+        self.theme = Arc::new(theme);
+
+        let (config, theme) = (self.config.clone(), self.theme.clone());
+        self.search.reconfigure(config.clone(), theme.clone());
+        if let Some(ref mut page_loader) = self.page_loader {
+            page_loader.reconfigure(config.clone());
+        }
+        self.page.reconfigure(config.clone(), theme.clone());
+        if let Some(ref mut split) = self.split {
+            split.page.reconfigure(config.clone(), theme.clone());
+        }
+        self.logger.reconfigure(config.clone(), theme.clone());
+        self.search_bar.reconfigure(config.clone(), theme.clone());
+        self.command_line.reconfigure(config, theme);
+    }
+
+    fn set_option(&mut self, option: &str, value: &str) -> ActionResult {
+        let mut config = Config::clone(&self.config);
+        if let Err(error) = config.set_option(option, value) {
+            return Action::PopupError(format!("{:#}", error)).into();
+        }
+        self.reconfigure(config, Theme::clone(&self.theme));
+        ActionResult::consumed()
+    }
+
+    fn set_variant(&mut self, language: Language, variant: String) -> ActionResult {
+        let mut config = Config::clone(&self.config);
+        // the language itself is the variant without conversion
+        if variant == language.code() {
+            config.api.variants.remove(language.code());
+        } else {
+            config
+                .api
+                .variants
+                .insert(language.code().to_string(), variant);
+        }
+        self.reconfigure(config, Theme::clone(&self.theme));
+        ActionResult::consumed()
+    }
+
+    fn set_theme(&mut self, name: &str) -> ActionResult {
+        let theme = match load_named_theme(name) {
+            Ok(theme) => theme,
+            Err(error) => return Action::PopupError(format!("{:#}", error)).into(),
+        };
+        self.reconfigure(Config::clone(&self.config), theme);
+        ActionResult::consumed()
+    }
+
+    fn render_search_bar(&mut self, f: &mut Frame<'_>, area: Rect) -> Rect {
+        let (search_bar_area, area) = {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(SEARCH_BAR_HEIGTH),
+                    Constraint::Percentage(100),
+                ])
+                .split(area);
+            (chunks[0], chunks[1])
+        };
+
+        self.search_bar.render(f, search_bar_area);
+        area
+    }
+}
+
+impl Component for AppComponent {
+    fn init(
+        &mut self,
+        action_tx: mpsc::UnboundedSender<Action>,
+        config: Arc<Config>,
+        theme: Arc<Theme>,
+    ) -> Result<()> {
+        self.search
+            .init(action_tx.clone(), config.clone(), theme.clone())?;
+        self.page_cache = Arc::new(Mutex::new(PageCache::load()));
+        self.page = PageViewer::new(self.last_pane, self.page_cache.clone());
+        self.page
+            .init(action_tx.clone(), config.clone(), theme.clone())?;
+        self.search
+            .refresh_dashboard(self.page.read_pages(), self.page.bookmarks());
+        self.search_bar
+            .init(action_tx.clone(), config.clone(), theme.clone())?;
+        self.command_line
+            .init(action_tx.clone(), config.clone(), theme.clone())?;
+
+        self.page_loader = Some(PageLoader::new(config.clone(), action_tx.clone()));
+        if config
+            .ui
+            .dashboard
+            .sections
+            .contains(&DashboardSection::Featured)
+        {
+            self.page_loader
+                .as_ref()
+                .unwrap()
+                .load_dashboard_featured(config.api.endpoint.clone(), config.api.language);
+        }
+
+        action_tx.send(Action::EnterSearchBar).unwrap();
+        self.action_tx = Some(action_tx);
+
+        self.config = config;
+        self.theme = theme;
+
+        Ok(())
+    }
+    fn handle_events(&mut self, event: Option<Event>) -> ActionResult {
+        match event {
+            Some(Event::Quit) => Action::Quit.into(),
+            Some(Event::RenderTick) => {
+                // like in vim, keys that could start a longer sequence are handled on their own
+                // once no other key was typed for the timeout
+                let timeout = Duration::from_millis(self.config.bindings.sequence_timeout);
+                if self.pending_keys.is_expired(timeout) {
+                    let mut keys = self.pending_keys.keys().to_vec();
+                    if let Some(key) = keys.pop() {
+                        self.pending_keys.set_keys(keys);
+                        if let ActionResult::Consumed(actions) = self.finish_keys(key.key_event()) {
+                            return actions.action(Action::RenderTick).into();
+                        }
+                    }
+                }
+                Action::RenderTick.into()
+            }
+            Some(Event::Key(key_event)) => self.handle_key(key_event),
+            Some(Event::Mouse(mouse_event)) => self.handle_mouse_events(mouse_event),
+            Some(Event::Resize(x, y)) => Action::Resize(x, y).into(),
+            None => ActionResult::Ignored,
+        }
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> ActionResult {
+        // popups are modal, so they get all mouse events
+        if let Some(ref mut popup) = self.popups.last_mut() {
+            return popup.handle_mouse_events(mouse);
+        }
+
+        let position = Position::new(mouse.column, mouse.row);
+        if matches!(mouse.kind, MouseEventKind::Down(_))
+            && self
+                .split
+                .as_ref()
+                .is_some_and(|split| split.area.contains(position))
+        {
+            self.focus_other_pane();
+        }
+
+        match self.context {
+            CONTEXT_SEARCH => self.search.handle_mouse_events(mouse),
+            CONTEXT_PAGE => self.page.handle_mouse_events(mouse),
+            _ => {
+                warn!("unknown context");
+                ActionResult::Ignored
+            }
+        }
+    }
+
     fn update(&mut self, action: Action) -> ActionResult {
         // global actions
         match action {
@@ -194,6 +702,15 @@ impl Component for AppComponent {
 
             Action::ToggleShowLogger => self.is_logger = !self.is_logger,
             Action::ShowPageLanguageSelection => self.show_page_language(),
+            Action::ShowProfileSelection => {
+                let (endpoint, language) = self.search_site();
+                self.popups.push(Box::new(ProfileSelectionComponent::new(
+                    endpoint,
+                    language,
+                    self.config.clone(),
+                    self.theme.clone(),
+                )));
+            }
             Action::ShowHelp => {
                 self.popups.push(Box::new(HelpPopupComponent::new(
                     self.config.clone(),
@@ -201,13 +718,38 @@ impl Component for AppComponent {
                 )));
             }
 
-            Action::SwitchContextSearch => self.switch_context(CONTEXT_SEARCH),
+            Action::SwitchContextSearch => {
+                // the pages could have been read or bookmarked since the dashboard was made
+                self.search
+                    .refresh_dashboard(self.page.read_pages(), self.page.bookmarks());
+                self.switch_context(CONTEXT_SEARCH);
+            }
             Action::SwitchContextPage => self.switch_context(CONTEXT_PAGE),
             Action::SwitchPreviousContext => self.switch_context(self.prev_context),
 
+            Action::SplitView(direction) => return self.split_view(direction),
+            Action::FocusOtherPane => self.focus_other_pane(),
+            Action::ClosePane => return self.close_pane(),
+            Action::InOtherPane(action) => return self.in_other_pane(*action),
+            Action::ToPane(pane, action) => return self.run_in_pane(pane, *action),
+            Action::PageViewer(PageViewerAction::SaveCache) => {
+                // the open pages of the other pane are saved as well
+                if let Some(ref mut split) = self.split {
+                    split.page.sync_cache();
+                }
+                return self.page.update(action);
+            }
+
             Action::EnterSearchBar => self.search_bar.is_focussed = true,
             Action::ExitSearchBar => self.search_bar.is_focussed = false,
             Action::ClearSearchBar => self.search_bar.clear(),
+
+            Action::EnterCommandLine => self.command_line.enter(),
+            Action::ExitCommandLine => self.command_line.exit(),
+            Action::RunCommand(input) => return self.run_command(&input),
+            Action::SetOption(option, value) => return self.set_option(&option, &value),
+            Action::SetVariant(language, variant) => return self.set_variant(language, variant),
+            Action::SetTheme(name) => return self.set_theme(&name),
             Action::SubmitSearchBar => {
                 return ActionPacket::default()
                     .action(Action::ExitSearchBar)
@@ -216,24 +758,158 @@ impl Component for AppComponent {
                     .into()
             }
 
-            Action::TryLoadPage(title, language, endpoint) => {
+            Action::TryLoadPage(title, language, endpoint, focus) => {
                 return self
                     .page
-                    .update(Action::TryLoadPage(title, language, endpoint));
+                    .update(Action::TryLoadPage(title, language, endpoint, focus));
+            }
+            Action::LoadPage(title, language, endpoint, focus) => {
+                let request = self.page_loader.as_ref().unwrap().load_page(
+                    title,
+                    language,
+                    endpoint,
+                    focus,
+                    self.page.pane(),
+                );
+                return self.start_loading(request);
             }
             Action::LoadSearchResult(result) => {
                 // Use TryLoadPage to check cache first
-                return Action::TryLoadPage(result.title, result.language, result.endpoint).into();
+                return Action::TryLoadPage(result.title, result.language, result.endpoint, None)
+                    .into();
             }
             Action::LoadLink(link) => match link {
                 Link::Internal(data) => {
-                    return Action::TryLoadPage(data.page, data.language, data.endpoint).into();
+                    let focus = data.anchor.map(|anchor| PageFocus::Header(anchor.anchor));
+                    return Action::TryLoadPage(data.page, data.language, data.endpoint, focus)
+                        .into();
+                }
+                _ => {
+                    let request = self
+                        .page_loader
+                        .as_ref()
+                        .unwrap()
+                        .load_link(link, self.page.pane());
+                    if let Some(request) = request {
+                        return self.start_loading(request);
+                    }
                 }
-                _ => self.page_loader.as_ref().unwrap().load_link(link),
             },
             Action::LoadLangaugeLink(link) => {
-                return Action::TryLoadPage(link.title, link.language, link.endpoint).into();
+                return Action::TryLoadPage(link.title, link.language, link.endpoint, None).into();
+            }
+            Action::LoadRandomPage => {
+                let (endpoint, language) = self.search_site();
+                let request = self.page_loader.as_ref().unwrap().load_random_page(
+                    endpoint,
+                    language,
+                    self.page.pane(),
+                );
+                return self.start_loading(request);
             }
+            Action::LoadFeaturedPage => {
+                let (endpoint, language) = self.search_site();
+                let request = self.page_loader.as_ref().unwrap().load_featured_page(
+                    endpoint,
+                    language,
+                    self.page.pane(),
+                );
+                return self.start_loading(request);
+            }
+            Action::LoadFeed => {
+                let (endpoint, language) = self.search_site();
+                self.page_loader
+                    .as_ref()
+                    .unwrap()
+                    .load_feed(endpoint, language);
+            }
+            Action::ShowFeed(feed) => self.popups.push(Box::new(FeedPopupComponent::new(
+                feed,
+                self.config.clone(),
+                self.theme.clone(),
+            ))),
+
+            Action::ShowPageHistory => return self.show_page_history(),
+            Action::History(HistoryAction::LoadHistory(
+                title,
+                language,
+                endpoint,
+                continue_from,
+            )) => self.page_loader.as_ref().unwrap().load_history(
+                title,
+                language,
+                endpoint,
+                continue_from,
+            ),
+            Action::ShowPageCategories => self.show_page_categories(),
+            Action::Category(CategoryAction::LoadMembers(
+                category,
+                language,
+                endpoint,
+                continue_from,
+            )) => self.page_loader.as_ref().unwrap().load_category_members(
+                category,
+                language,
+                endpoint,
+                continue_from,
+            ),
+            Action::ShowPageBacklinks => return self.show_page_backlinks(),
+            Action::Backlinks(BacklinksAction::LoadBacklinks(
+                title,
+                language,
+                endpoint,
+                continue_from,
+            )) => self.page_loader.as_ref().unwrap().load_backlinks(
+                title,
+                language,
+                endpoint,
+                continue_from,
+            ),
+            Action::ShowSummary(title, language, endpoint) => {
+                self.popups.push(Box::new(SummaryPopupComponent::new(
+                    title.clone(),
+                    language,
+                    endpoint.clone(),
+                    self.config.clone(),
+                    self.theme.clone(),
+                )));
+                return Action::Summary(SummaryAction::LoadSummary(title, language, endpoint))
+                    .into();
+            }
+            Action::Summary(SummaryAction::LoadSummary(title, language, endpoint)) => self
+                .page_loader
+                .as_ref()
+                .unwrap()
+                .load_summary(title, language, endpoint),
+            Action::LoadRevision(title, revision, language, endpoint) => {
+                let request = self.page_loader.as_ref().unwrap().load_revision(
+                    title,
+                    revision,
+                    language,
+                    endpoint,
+                    self.page.pane(),
+                );
+                return self.start_loading(request);
+            }
+            Action::LoadChanges(page) => {
+                let request = self
+                    .page_loader
+                    .as_ref()
+                    .unwrap()
+                    .load_changes(*page, self.page.pane());
+                return self.start_loading(request);
+            }
+            Action::CompareRevisions(title, from, to, endpoint) => self
+                .page_loader
+                .as_ref()
+                .unwrap()
+                .load_diff(title, from, to, endpoint),
+            Action::ShowDiff(title, diff) => self.popups.push(Box::new(DiffViewComponent::new(
+                title,
+                diff,
+                self.config.clone(),
+                self.theme.clone(),
+            ))),
 
             Action::PopupMessage(title, content) => self.popups.push(Box::new(
                 MessagePopupComponent::new_raw(title, content, self.theme.clone()),
@@ -284,6 +960,15 @@ impl Component for AppComponent {
     }
 
     fn render(&mut self, f: &mut Frame<'_>, mut area: Rect) {
+        let full_area = area;
+        // the pending keys are shown in the status bar of the focussed pane
+        let pending_keys = self.pending_keys.indicator();
+        self.search.set_pending_keys(pending_keys.clone());
+        self.page.set_pending_keys(pending_keys);
+        if let Some(ref mut split) = self.split {
+            split.page.set_pending_keys(None);
+        }
+
         f.render_widget(
             Block::default().style(Style::default().bg(self.theme.bg)),
             area,
@@ -317,6 +1002,33 @@ impl Component for AppComponent {
             area = chunks[0];
         };
 
+        if let Some(ref mut split) = self.split {
+            let direction = match split.direction {
+                SplitDirection::Vertical => Direction::Horizontal,
+                SplitDirection::Horizontal => Direction::Vertical,
+            };
+            let chunks = Layout::default()
+                .direction(direction)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(area);
+            let (focussed_area, other_area) = if split.focus_second {
+                (chunks[1], chunks[0])
+            } else {
+                (chunks[0], chunks[1])
+            };
+
+            match split.context {
+                CONTEXT_SEARCH => self.search.render(f, other_area),
+                CONTEXT_PAGE => split.page.render(f, other_area),
+                _ => warn!("unknown context"),
+            }
+            // the pane without focus is dimmed
+            f.buffer_mut()
+                .set_style(other_area, Style::default().add_modifier(Modifier::DIM));
+            split.area = other_area;
+            area = focussed_area;
+        }
+
         match self.context {
             CONTEXT_SEARCH => self.search.render(f, area),
             CONTEXT_PAGE => self.page.render(f, area),
@@ -326,5 +1038,15 @@ impl Component for AppComponent {
         if let Some(ref mut popup) = self.popups.last_mut() {
             popup.render(f, area);
         }
+
+        // the command line is drawn over the last line, like in vim
+        if self.command_line.is_focussed {
+            let command_line_area = Rect {
+                y: full_area.bottom().saturating_sub(1),
+                height: full_area.height.min(1),
+                ..full_area
+            };
+            self.command_line.render(f, command_line_area);
+        }
     }
 }
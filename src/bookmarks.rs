@@ -0,0 +1,101 @@
+//! Pages the user bookmarked, saved in the cache directory
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, warn};
+use wiki_api::{languages::Language, Endpoint};
+
+use crate::config::cache_dir;
+
+const BOOKMARKS_FILE_NAME: &str = "bookmarks.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub title: String,
+    pub language: Language,
+    pub endpoint: Endpoint,
+}
+
+/// The bookmarked pages, the newest bookmark first
+#[derive(Default)]
+pub struct Bookmarks {
+    bookmarks: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    fn path() -> Option<PathBuf> {
+        match cache_dir() {
+            Ok(dir) => Some(dir.join(BOOKMARKS_FILE_NAME)),
+            Err(error) => {
+                warn!("unable to find the bookmarks: {:?}", error);
+                None
+            }
+        }
+    }
+
+    pub fn load() -> Self {
+        let path = match Self::path() {
+            Some(path) if path.exists() => path,
+            _ => return Self::default(),
+        };
+
+        let bookmarks = std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|bookmarks| serde_json::from_str(&bookmarks).map_err(anyhow::Error::from));
+        match bookmarks {
+            Ok(bookmarks) => {
+                debug!("loaded the bookmarks from {:?}", path);
+                Bookmarks { bookmarks }
+            }
+            Err(error) => {
+                error!("failed loading the bookmarks from {:?}: {}", path, error);
+                Self::default()
+            }
+        }
+    }
+
+    fn save(&self) {
+        let path = match Self::path() {
+            Some(path) => path,
+            None => return,
+        };
+
+        let result = serde_json::to_string(&self.bookmarks)
+            .map_err(anyhow::Error::from)
+            .and_then(|bookmarks| {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                Ok(std::fs::write(&path, bookmarks)?)
+            });
+        if let Err(error) = result {
+            error!("failed saving the bookmarks to {:?}: {}", path, error);
+        }
+    }
+
+    pub fn get(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    pub fn contains(&self, title: &str, language: Language) -> bool {
+        self.bookmarks
+            .iter()
+            .any(|bookmark| bookmark.title == title && bookmark.language == language)
+    }
+
+    /// Removes the bookmark of the page, or adds one when the page isn't bookmarked yet. Returns
+    /// whether the page is bookmarked now
+    pub fn toggle(&mut self, bookmark: Bookmark) -> bool {
+        let bookmarked = if self.contains(&bookmark.title, bookmark.language) {
+            self.bookmarks
+                .retain(|x| x.title != bookmark.title || x.language != bookmark.language);
+            false
+        } else {
+            self.bookmarks.insert(0, bookmark);
+            true
+        };
+        self.save();
+        bookmarked
+    }
+}
@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 use crate::{
     action::{Action, ActionPacket, SearchAction},
@@ -16,9 +16,17 @@ struct Cli {
 
     /// Override the configured search language of wikipedia. The value can be either the language
     /// code, the name of the language in english or the native language name
-    #[arg(value_name = "LANGUAGE", short = 'l', long = "language")]
+    #[arg(value_name = "LANGUAGE", short = 'l', long = "language", global = true)]
     language: Option<Language>,
 
+    /// Open a random article
+    #[arg(long = "random", conflicts_with_all = ["search_query", "featured"])]
+    random: bool,
+
+    /// Open today's featured article
+    #[arg(long = "featured", conflicts_with = "search_query")]
+    featured: bool,
+
     /// Override the configured logging level
     #[arg(value_name = "LEVEL", long = "level")]
     level: Option<tracing::level_filters::LevelFilter>,
@@ -38,11 +46,26 @@ struct Cli {
     #[cfg(debug_assertions)]
     #[arg(value_name = "PATH", long = "page")]
     load_debug_page: Option<std::path::PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the definitions of a word from Wiktionary
+    Define {
+        #[arg(value_name = "WORD")]
+        word: String,
+    },
 }
 
 pub struct CliResults {
     pub actions: Option<ActionPacket>,
     pub log_level: Option<tracing::level_filters::LevelFilter>,
+    /// Word to print the definitions of instead of starting the interface, in the language
+    /// given with `--language`
+    pub define: Option<(String, Option<Language>)>,
 }
 
 pub fn match_cli() -> CliResults {
@@ -52,8 +75,13 @@ pub fn match_cli() -> CliResults {
     let mut results = CliResults {
         actions: None,
         log_level: None,
+        define: None,
     };
 
+    if let Some(Command::Define { word }) = cli.command {
+        results.define = Some((word, cli.language));
+    }
+
     let mut packet = ActionPacket::default();
 
     if let Some(language) = cli.language {
@@ -70,6 +98,16 @@ pub fn match_cli() -> CliResults {
         packet.add_action(Action::Search(SearchAction::StartSearch(search_query)));
     }
 
+    if cli.random {
+        packet.add_action(Action::ExitSearchBar);
+        packet.add_action(Action::LoadRandomPage);
+    }
+
+    if cli.featured {
+        packet.add_action(Action::ExitSearchBar);
+        packet.add_action(Action::LoadFeaturedPage);
+    }
+
     if cli.print_config_path {
         let config_path = config_dir().map(|x| x.join(CONFIG_FILE_NAME));
         println!(
@@ -108,7 +146,7 @@ pub fn match_cli() -> CliResults {
         if let Some(page) = wiki_api::page::Page::from_path(debug_page) {
             packet.add_action(Action::SwitchContextPage);
             packet.add_action(Action::PageViewer(
-                crate::action::PageViewerAction::DisplayPage(page),
+                crate::action::PageViewerAction::DisplayPage(Box::new(page), None),
             ));
         }
     }
@@ -0,0 +1,299 @@
+//! Commands of the command line, e.g. `:open Rust` or `:set toc.position right`
+
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+use wiki_api::{languages::Language, sitematrix::languages};
+
+use crate::{
+    app::SplitDirection,
+    config::{theme_names, Config, RUNTIME_OPTIONS},
+    export::ExportFormat,
+    fuzzy::fuzzy_filter,
+};
+
+pub struct CommandInfo {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub description: &'static str,
+}
+
+macro_rules! commands {
+    ($($name:literal $usage:literal => $description:literal),+ $(,)?) => {
+        /// All commands, in the order they are suggested when nothing has been typed yet
+        pub const COMMANDS: &[CommandInfo] = &[
+            $(CommandInfo {
+                name: $name,
+                usage: $usage,
+                description: $description,
+            }),+
+        ];
+    };
+}
+
+commands!(
+    "open" "open <title>" => "Open a page on the current site",
+    "search" "search <query>" => "Search the current site",
+    "lang" "lang <language>" => "Change the language for searches",
+    "profile" "profile <name>" => "Switch to another wiki profile",
+    "export" "export <md|txt> [path]" => "Save the current page to a file",
+    "set" "set <option> <value>" => "Change an option until the program exits",
+    "zen" "zen" => "Toggle the zen mode of the current page",
+    "toc" "toc" => "Toggle the table of contents of the current page",
+    "fold" "fold [level]" => "Fold the sections below the level, all sections without one",
+    "unfold" "unfold" => "Unfold all sections of the current page",
+    "bookmark" "bookmark" => "Bookmark the current page or remove its bookmark",
+    "split" "split" => "Split the view into two panes above each other",
+    "vsplit" "vsplit" => "Split the view into two panes side by side",
+    "close" "close" => "Close the focussed pane of the split view",
+    "theme" "theme <name>" => "Load a theme from the themes directory",
+    "random" "random" => "Open a random page",
+    "featured" "featured" => "Open the featured article of today",
+    "feed" "feed" => "Show the featured content of today",
+    "history" "history" => "Show the revision history of the current page",
+    "categories" "categories" => "Show the categories of the current page",
+    "backlinks" "backlinks" => "Show the pages linking to the current page",
+    "back" "back" => "Go back to the previous page",
+    "logger" "logger" => "Toggle the logger",
+    "help" "help" => "Show the keybindings",
+    "quit" "quit" => "Quit the program",
+);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Open(String),
+    Search(String),
+    Language(Language),
+    Profile(String),
+    /// Export(Format, Path), without a path the page is saved as `<title>.<extension>`
+    Export(ExportFormat, Option<PathBuf>),
+    /// Set(Option, Value)
+    Set(String, String),
+    Zen,
+    Toc,
+    /// Fold(Level)
+    Fold(u8),
+    Unfold,
+    Bookmark,
+    /// Split(Direction)
+    Split(SplitDirection),
+    Close,
+    Theme(String),
+    Random,
+    Featured,
+    Feed,
+    History,
+    Categories,
+    Backlinks,
+    Back,
+    Logger,
+    Help,
+    Quit,
+}
+
+/// Finds the command with this name. Names that don't exist are fuzzy matched, so `:ex` runs
+/// `:export`
+pub fn find_command(name: &str) -> Option<&'static CommandInfo> {
+    COMMANDS
+        .iter()
+        .find(|command| command.name == name)
+        .or_else(|| {
+            fuzzy_filter(name, COMMANDS, |command| command.name)
+                .first()
+                .map(|(command, _)| *command)
+        })
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    let home = directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    match (path.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let input = input.trim().trim_start_matches(':');
+        let (name, args) = match input.split_once(char::is_whitespace) {
+            Some((name, args)) => (name, args.trim()),
+            None => (input, ""),
+        };
+
+        if name.is_empty() {
+            bail!("no command given");
+        }
+        let info = find_command(name).with_context(|| format!("unknown command '{}'", name))?;
+
+        macro_rules! required {
+            ($what:literal) => {{
+                if args.is_empty() {
+                    bail!("missing {}, usage: {}", $what, info.usage);
+                }
+                args.to_string()
+            }};
+        }
+
+        let command = match info.name {
+            "open" => Command::Open(required!("title")),
+            "search" => Command::Search(required!("query")),
+            "lang" => Command::Language(
+                required!("language")
+                    .parse()
+                    .with_context(|| format!("unknown language '{}'", args))?,
+            ),
+            "profile" => Command::Profile(required!("profile name")),
+            "export" => {
+                let args = required!("format");
+                let (format, path) = match args.split_once(char::is_whitespace) {
+                    Some((format, path)) => (format, Some(expand_home(path.trim()))),
+                    None => (args.as_str(), None),
+                };
+                let format = format.parse().map_err(anyhow::Error::msg)?;
+                Command::Export(format, path)
+            }
+            "set" => {
+                let args = required!("option");
+                match args.split_once(char::is_whitespace) {
+                    Some((option, value)) => {
+                        Command::Set(option.to_string(), value.trim().to_string())
+                    }
+                    None => bail!("missing value, usage: {}", info.usage),
+                }
+            }
+            "zen" => Command::Zen,
+            "toc" => Command::Toc,
+            "fold" if args.is_empty() => Command::Fold(0),
+            "fold" => Command::Fold(
+                args.parse()
+                    .with_context(|| format!("invalid level '{}'", args))?,
+            ),
+            "unfold" => Command::Unfold,
+            "bookmark" => Command::Bookmark,
+            "split" => Command::Split(SplitDirection::Horizontal),
+            "vsplit" => Command::Split(SplitDirection::Vertical),
+            "close" => Command::Close,
+            "theme" => Command::Theme(required!("theme name")),
+            "random" => Command::Random,
+            "featured" => Command::Featured,
+            "feed" => Command::Feed,
+            "history" => Command::History,
+            "categories" => Command::Categories,
+            "backlinks" => Command::Backlinks,
+            "back" => Command::Back,
+            "logger" => Command::Logger,
+            "help" => Command::Help,
+            "quit" => Command::Quit,
+            _ => unreachable!("every command in COMMANDS is parsed"),
+        };
+        Ok(command)
+    }
+}
+
+/// A suggestion for the text that is being typed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The whole input after choosing the completion
+    pub replacement: String,
+    /// Text shown in the list of suggestions
+    pub label: String,
+    pub description: String,
+    /// Characters of the label that matched the typed text
+    pub indices: Vec<usize>,
+}
+
+/// Values of the option that can be suggested
+fn option_values(option: &str) -> &'static [&'static str] {
+    match option.trim_start_matches("page.") {
+        "toc.position" => &["left", "right"],
+        "toc.enabled"
+        | "toc.enable_scrolling"
+        | "ui.popup_search_language_changed"
+        | "ui.popup_page_language_changed" => &["true", "false"],
+        _ => &[],
+    }
+}
+
+/// Suggests completions for the input. Command names are completed until the first space,
+/// after that the arguments of the command are completed. The best matches come first
+pub fn complete(input: &str, config: &Config) -> Vec<Completion> {
+    // split off the word that's being typed
+    let (prefix, word) = match input.rfind(char::is_whitespace) {
+        Some(idx) => input.split_at(idx + 1),
+        None => ("", input),
+    };
+
+    if prefix.is_empty() {
+        return fuzzy_filter(word, COMMANDS, |command| command.name)
+            .into_iter()
+            .map(|(command, fuzzy_match)| Completion {
+                replacement: format!("{} ", command.name),
+                label: command.name.to_string(),
+                description: command.description.to_string(),
+                indices: fuzzy_match.indices,
+            })
+            .collect();
+    }
+
+    let mut args = prefix.split_whitespace();
+    let command = match args.next().and_then(find_command) {
+        Some(command) => command,
+        None => return Vec::new(),
+    };
+    let args: Vec<&str> = args.collect();
+
+    let candidates: Vec<(String, String)> = match (command.name, args.as_slice()) {
+        ("lang", []) => languages()
+            .into_iter()
+            .map(|language| {
+                let mut description = format!("{} ({})", language.name(), language.local_name());
+                if language.is_closed() {
+                    description.push_str(", closed");
+                }
+                (language.code().to_string(), description)
+            })
+            .collect(),
+        ("profile", []) => config
+            .api
+            .profiles
+            .iter()
+            .map(|profile| {
+                (
+                    profile.name.clone(),
+                    profile.endpoint.site_name().to_string(),
+                )
+            })
+            .collect(),
+        ("export", []) => ExportFormat::NAMES
+            .iter()
+            .map(|name| (name.to_string(), String::new()))
+            .collect(),
+        ("set", []) => RUNTIME_OPTIONS
+            .iter()
+            .map(|option| (option.to_string(), String::new()))
+            .collect(),
+        ("set", [option]) => option_values(option)
+            .iter()
+            .map(|value| (value.to_string(), String::new()))
+            .collect(),
+        ("theme", []) => theme_names()
+            .into_iter()
+            .map(|name| (name, String::new()))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    fuzzy_filter(word, candidates, |(label, _)| label)
+        .into_iter()
+        .map(|((label, description), fuzzy_match)| Completion {
+            replacement: format!("{}{}", prefix, label),
+            label,
+            description,
+            indices: fuzzy_match.indices,
+        })
+        .collect()
+}
@@ -0,0 +1,272 @@
+use std::sync::Arc;
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Clear, HighlightSpacing, List, ListItem},
+};
+use wiki_api::{
+    backlinks::{Backlink, Backlinks, BacklinksContinue},
+    languages::Language,
+    Endpoint,
+};
+
+use crate::{
+    action::{Action, ActionPacket, ActionResult, BacklinksAction, PageFocus},
+    config::{Binding, Config, Theme},
+    terminal::Frame,
+    ui::{centered_rect, StatefulList},
+};
+
+use super::Component;
+
+/// Lists the pages that link to a page. Opening a backlink scrolls to the place where it links
+/// back, more backlinks are loaded with the `continue_search` binding
+pub struct BacklinksPopupComponent {
+    title: String,
+    language: Language,
+    endpoint: Endpoint,
+
+    list: StatefulList<Backlink>,
+    continue_backlinks: Option<BacklinksContinue>,
+    is_loading: bool,
+
+    config: Arc<Config>,
+    theme: Arc<Theme>,
+}
+
+impl BacklinksPopupComponent {
+    pub fn new(
+        title: String,
+        language: Language,
+        endpoint: Endpoint,
+        config: Arc<Config>,
+        theme: Arc<Theme>,
+    ) -> Self {
+        Self {
+            title,
+            language,
+            endpoint,
+
+            list: StatefulList::with_items(Vec::new()),
+            continue_backlinks: None,
+            is_loading: true,
+
+            config,
+            theme,
+        }
+    }
+
+    fn finish_backlinks(&mut self, backlinks: Backlinks) -> ActionResult {
+        self.is_loading = false;
+        self.continue_backlinks = backlinks.continue_data();
+        self.list.get_items_mut().extend(backlinks.links);
+        ActionResult::consumed()
+    }
+
+    fn continue_backlinks(&mut self) -> ActionResult {
+        if self.is_loading {
+            return ActionResult::consumed();
+        }
+
+        let code = match self.continue_backlinks {
+            Some(ref code) => code,
+            None => {
+                return Action::PopupMessage(
+                    "Information".to_string(),
+                    "There are no more pages linking here".to_string(),
+                )
+                .into()
+            }
+        };
+
+        self.is_loading = true;
+        Action::Backlinks(BacklinksAction::LoadBacklinks(
+            code.title.clone(),
+            code.language,
+            code.endpoint.clone(),
+            Some(code.continue_from.clone()),
+        ))
+        .into()
+    }
+
+    fn open_selected(&self) -> ActionResult {
+        let backlink = match self.list.selected() {
+            Some(backlink) => backlink,
+            None => return ActionResult::Ignored,
+        };
+
+        // a redirect leads back to this page, so there is nothing to scroll to
+        let focus = (!backlink.is_redirect).then(|| {
+            PageFocus::LinkTo(
+                backlink
+                    .via_redirect
+                    .clone()
+                    .unwrap_or_else(|| self.title.clone()),
+            )
+        });
+        ActionPacket::single(Action::PopPopup)
+            .action(Action::TryLoadPage(
+                backlink.title.clone(),
+                self.language,
+                self.endpoint.clone(),
+                focus,
+            ))
+            .into()
+    }
+
+    fn render_backlink(&self, backlink: &Backlink) -> ListItem<'static> {
+        let mut line = Vec::new();
+        if backlink.via_redirect.is_some() {
+            line.push(Span::raw("  "));
+        }
+        line.push(Span::raw(backlink.title.clone()).fg(self.theme.fg));
+
+        if backlink.is_redirect {
+            line.push(Span::raw(" (redirect)").fg(self.theme.inactive_fg));
+        }
+        if let Some(ref redirect) = backlink.via_redirect {
+            line.push(Span::raw(format!(" (via '{}')", redirect)).fg(self.theme.inactive_fg));
+        }
+
+        ListItem::new(Line::from(line))
+    }
+}
+
+impl Component for BacklinksPopupComponent {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[Binding],
+    ) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .submit
+            .matches_event(key, pending)
+        {
+            return self.open_selected();
+        }
+
+        if self
+            .config
+            .bindings
+            .search
+            .continue_search
+            .matches_event(key, pending)
+        {
+            return Action::Backlinks(BacklinksAction::ContinueBacklinks).into();
+        }
+
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
+            || self
+                .config
+                .bindings
+                .page
+                .show_backlinks
+                .matches_event(key, pending)
+        {
+            return Action::PopPopup.into();
+        }
+
+        ActionResult::Ignored
+    }
+
+    fn update(&mut self, action: Action) -> ActionResult {
+        match action {
+            Action::Backlinks(BacklinksAction::FinishBacklinks(backlinks)) => {
+                self.finish_backlinks(backlinks)
+            }
+            Action::Backlinks(BacklinksAction::ContinueBacklinks) => self.continue_backlinks(),
+            Action::ScrollUp(n) => {
+                for _ in 0..n {
+                    self.list.previous()
+                }
+                ActionResult::consumed()
+            }
+            Action::ScrollDown(n) => {
+                for _ in 0..n {
+                    self.list.next()
+                }
+                ActionResult::consumed()
+            }
+            Action::UnselectScroll => {
+                self.list.unselect();
+                ActionResult::consumed()
+            }
+            _ => ActionResult::Ignored,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let area = centered_rect(area, 60, 70);
+        f.render_widget(Clear, area);
+
+        let block = self
+            .theme
+            .default_block()
+            .title(format!("Pages linking to '{}'", self.title))
+            .style(Style::default().bg(self.theme.bg));
+
+        if self.list.get_items().is_empty() {
+            let message = if self.is_loading {
+                "Loading the backlinks..."
+            } else {
+                "No pages link to this page"
+            };
+            f.render_widget(
+                self.theme
+                    .default_paragraph(message)
+                    .alignment(Alignment::Center)
+                    .block(block),
+                area,
+            );
+            return;
+        }
+
+        let mut items: Vec<ListItem> = self
+            .list
+            .get_items()
+            .iter()
+            .map(|backlink| self.render_backlink(backlink))
+            .collect();
+
+        if self.is_loading {
+            items.push(ListItem::new(
+                Line::from("Loading more backlinks...").fg(self.theme.inactive_fg),
+            ));
+        } else if self.continue_backlinks.is_some() {
+            let hint = match self
+                .config
+                .bindings
+                .search
+                .continue_search
+                .bindings()
+                .first()
+            {
+                Some(binding) => format!("Press '{}' to load more backlinks", binding),
+                None => "More backlinks are available".to_string(),
+            };
+            items.push(ListItem::new(Line::from(hint).fg(self.theme.inactive_fg)));
+        }
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_symbol("| ")
+            .highlight_spacing(HighlightSpacing::Always)
+            .highlight_style(
+                Style::default()
+                    .fg(self.theme.selected_fg)
+                    .bg(self.theme.selected_bg)
+                    .add_modifier(Modifier::ITALIC),
+            );
+        f.render_stateful_widget(list, area, self.list.get_state_mut());
+    }
+}
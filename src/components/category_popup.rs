@@ -0,0 +1,382 @@
+use std::sync::Arc;
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Clear, HighlightSpacing, List, ListItem},
+};
+use wiki_api::{category::CategoryMembers, languages::Language, Endpoint};
+
+use crate::{
+    action::{Action, ActionPacket, ActionResult, CategoryAction},
+    config::{Binding, Config, Theme},
+    terminal::Frame,
+    ui::{centered_rect, StatefulList},
+};
+
+use super::Component;
+
+enum EntryKind {
+    Category {
+        is_expanded: bool,
+        is_loading: bool,
+    },
+    Page,
+    /// More members of the parent category are available
+    More(String),
+}
+
+struct Entry {
+    kind: EntryKind,
+    title: String,
+    depth: usize,
+}
+
+impl Entry {
+    fn category(title: String, depth: usize) -> Self {
+        Entry {
+            kind: EntryKind::Category {
+                is_expanded: false,
+                is_loading: false,
+            },
+            title,
+            depth,
+        }
+    }
+}
+
+/// Lists the categories of a page as a tree. Categories can be expanded to show their pages and
+/// subcategories, pages can be opened
+pub struct CategoryPopupComponent {
+    page_title: String,
+    language: Language,
+    endpoint: Endpoint,
+
+    list: StatefulList<Entry>,
+
+    config: Arc<Config>,
+    theme: Arc<Theme>,
+}
+
+impl CategoryPopupComponent {
+    pub fn new(
+        page_title: String,
+        categories: Vec<String>,
+        language: Language,
+        endpoint: Endpoint,
+        config: Arc<Config>,
+        theme: Arc<Theme>,
+    ) -> Self {
+        Self {
+            page_title,
+            language,
+            endpoint,
+
+            list: StatefulList::with_items(
+                categories
+                    .into_iter()
+                    .map(|title| Entry::category(title, 0))
+                    .collect(),
+            ),
+
+            config,
+            theme,
+        }
+    }
+
+    fn load_members(&self, category: String, continue_from: Option<String>) -> ActionResult {
+        Action::Category(CategoryAction::LoadMembers(
+            category,
+            self.language,
+            self.endpoint.clone(),
+            continue_from,
+        ))
+        .into()
+    }
+
+    /// Returns the index after the last entry below the entry at `index`
+    fn subtree_end(&self, index: usize) -> usize {
+        let items = self.list.get_items();
+        let depth = items[index].depth;
+        items
+            .iter()
+            .skip(index + 1)
+            .position(|entry| entry.depth <= depth)
+            .map(|i| i + index + 1)
+            .unwrap_or(items.len())
+    }
+
+    fn submit(&mut self) -> ActionResult {
+        let index = match self.list.get_state().selected() {
+            Some(index) if index < self.list.get_items().len() => index,
+            _ => return ActionResult::Ignored,
+        };
+        let entry = &self.list.get_items()[index];
+        let (title, depth) = (entry.title.clone(), entry.depth);
+
+        match entry.kind {
+            EntryKind::Page => ActionPacket::single(Action::PopPopup)
+                .action(Action::TryLoadPage(
+                    title,
+                    self.language,
+                    self.endpoint.clone(),
+                    None,
+                ))
+                .into(),
+            EntryKind::Category {
+                is_loading: true, ..
+            } => ActionResult::consumed(),
+            EntryKind::Category {
+                is_expanded: true, ..
+            } => {
+                let end = self.subtree_end(index);
+                self.list.get_items_mut().drain(index + 1..end);
+                self.list.get_items_mut()[index].kind = EntryKind::Category {
+                    is_expanded: false,
+                    is_loading: false,
+                };
+                ActionResult::consumed()
+            }
+            EntryKind::Category { .. } => {
+                self.list.get_items_mut()[index].kind = EntryKind::Category {
+                    is_expanded: true,
+                    is_loading: true,
+                };
+                self.load_members(title, None)
+            }
+            EntryKind::More(ref continue_from) => {
+                let continue_from = continue_from.clone();
+                let parent = self.list.get_items()[..index]
+                    .iter()
+                    .rev()
+                    .find(|parent| parent.depth + 1 == depth)
+                    .map(|parent| parent.title.clone());
+
+                match parent {
+                    Some(parent) => {
+                        self.set_loading(&parent, true);
+                        self.load_members(parent, Some(continue_from))
+                    }
+                    None => ActionResult::consumed(),
+                }
+            }
+        }
+    }
+
+    fn set_loading(&mut self, category: &str, loading: bool) {
+        for entry in self.list.get_items_mut().iter_mut() {
+            if let EntryKind::Category {
+                ref mut is_loading, ..
+            } = entry.kind
+            {
+                if entry.title == category {
+                    *is_loading = loading;
+                }
+            }
+        }
+    }
+
+    fn finish_members(&mut self, members: CategoryMembers) {
+        self.set_loading(&members.category, false);
+
+        // the same category can appear multiple times in the tree, only fill the expanded ones
+        // that don't have any members yet or that are waiting for more members
+        let mut index = 0;
+        while index < self.list.get_items().len() {
+            let entry = &self.list.get_items()[index];
+            let is_target = entry.title == members.category
+                && matches!(
+                    entry.kind,
+                    EntryKind::Category {
+                        is_expanded: true,
+                        ..
+                    }
+                );
+            if !is_target {
+                index += 1;
+                continue;
+            }
+
+            let depth = entry.depth + 1;
+            let mut end = self.subtree_end(index);
+            let has_members = end > index + 1;
+            let is_waiting_for_more = has_members
+                && self.list.get_items()[end - 1].depth == depth
+                && matches!(self.list.get_items()[end - 1].kind, EntryKind::More(_));
+
+            if has_members && !is_waiting_for_more {
+                index = end;
+                continue;
+            }
+
+            // replace the 'more' entry with the new members
+            if is_waiting_for_more {
+                self.list.get_items_mut().remove(end - 1);
+                end -= 1;
+            }
+
+            let mut new_entries: Vec<Entry> = members
+                .members
+                .iter()
+                .map(|member| {
+                    if member.is_subcategory() {
+                        Entry::category(member.title.clone(), depth)
+                    } else {
+                        Entry {
+                            kind: EntryKind::Page,
+                            title: member.title.clone(),
+                            depth,
+                        }
+                    }
+                })
+                .collect();
+            if let Some(ref continue_from) = members.continue_from {
+                new_entries.push(Entry {
+                    kind: EntryKind::More(continue_from.clone()),
+                    title: "Load more...".to_string(),
+                    depth,
+                });
+            }
+
+            let added = new_entries.len();
+            self.list.get_items_mut().splice(end..end, new_entries);
+            index = end + added;
+        }
+    }
+
+    fn render_entry(&self, entry: &Entry) -> ListItem<'static> {
+        let indent = Span::raw("  ".repeat(entry.depth));
+        let line = match entry.kind {
+            EntryKind::Category {
+                is_expanded,
+                is_loading,
+            } => {
+                let marker = match (is_expanded, is_loading) {
+                    (_, true) => "~ ",
+                    (true, false) => "- ",
+                    (false, false) => "+ ",
+                };
+                Line::from(vec![
+                    indent,
+                    Span::raw(marker),
+                    Span::raw(entry.title.clone()).fg(self.theme.search_title_fg),
+                ])
+            }
+            EntryKind::Page => Line::from(vec![
+                indent,
+                Span::raw("  "),
+                Span::raw(entry.title.clone()).fg(self.theme.fg),
+            ]),
+            EntryKind::More(_) => Line::from(vec![
+                indent,
+                Span::raw("  "),
+                Span::raw(entry.title.clone()).fg(self.theme.inactive_fg),
+            ]),
+        };
+        ListItem::new(line)
+    }
+}
+
+impl Component for CategoryPopupComponent {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[Binding],
+    ) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .submit
+            .matches_event(key, pending)
+        {
+            return self.submit();
+        }
+
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
+            || self
+                .config
+                .bindings
+                .page
+                .show_categories
+                .matches_event(key, pending)
+        {
+            return Action::PopPopup.into();
+        }
+
+        ActionResult::Ignored
+    }
+
+    fn update(&mut self, action: Action) -> ActionResult {
+        match action {
+            Action::Category(CategoryAction::FinishMembers(members)) => {
+                self.finish_members(members);
+                ActionResult::consumed()
+            }
+            Action::ScrollUp(n) => {
+                for _ in 0..n {
+                    self.list.previous()
+                }
+                ActionResult::consumed()
+            }
+            Action::ScrollDown(n) => {
+                for _ in 0..n {
+                    self.list.next()
+                }
+                ActionResult::consumed()
+            }
+            Action::UnselectScroll => {
+                self.list.unselect();
+                ActionResult::consumed()
+            }
+            _ => ActionResult::Ignored,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let area = centered_rect(area, 60, 70);
+        f.render_widget(Clear, area);
+
+        let block = self
+            .theme
+            .default_block()
+            .title(format!("Categories of '{}'", self.page_title))
+            .style(Style::default().bg(self.theme.bg));
+
+        if self.list.get_items().is_empty() {
+            f.render_widget(
+                self.theme
+                    .default_paragraph("This page has no categories")
+                    .alignment(Alignment::Center)
+                    .block(block),
+                area,
+            );
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .list
+            .get_items()
+            .iter()
+            .map(|entry| self.render_entry(entry))
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_symbol("| ")
+            .highlight_spacing(HighlightSpacing::Always)
+            .highlight_style(
+                Style::default()
+                    .fg(self.theme.selected_fg)
+                    .bg(self.theme.selected_bg)
+                    .add_modifier(Modifier::ITALIC),
+            );
+        f.render_stateful_widget(list, area, self.list.get_state_mut());
+    }
+}
@@ -0,0 +1,315 @@
+use std::{path::PathBuf, sync::Arc};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Clear, List, ListItem, ListState},
+};
+use tracing::{error, warn};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use crate::{
+    action::{Action, ActionPacket, ActionResult},
+    commands::{complete, Completion},
+    config::{cache_dir, Binding, Config, Theme},
+    terminal::Frame,
+};
+
+use super::Component;
+
+const HISTORY_FILE_NAME: &str = "command_history";
+const HISTORY_MAX_LEN: usize = 100;
+/// Suggestions shown above the command line at most
+const MAX_SUGGESTIONS: usize = 8;
+
+/// The `:` command line at the bottom of the screen. Completes command names and arguments with
+/// `Tab` and remembers the commands that were run
+#[derive(Default)]
+pub struct CommandLineComponent {
+    input: Input,
+    completions: Vec<Completion>,
+    /// Completion that was chosen with `Tab`, the input shows its replacement
+    selected: Option<usize>,
+
+    /// Commands that were run, the oldest first
+    history: Vec<String>,
+    /// Position in the history while browsing it with the arrow keys
+    history_idx: Option<usize>,
+
+    config: Arc<Config>,
+    theme: Arc<Theme>,
+    pub is_focussed: bool,
+}
+
+impl CommandLineComponent {
+    fn history_path() -> Option<PathBuf> {
+        match cache_dir() {
+            Ok(dir) => Some(dir.join(HISTORY_FILE_NAME)),
+            Err(error) => {
+                warn!("unable to find the command history: {:?}", error);
+                None
+            }
+        }
+    }
+
+    fn load_history(&mut self) {
+        let path = match Self::history_path() {
+            Some(path) if path.exists() => path,
+            _ => return,
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(history) => {
+                self.history = history
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| line.to_string())
+                    .collect()
+            }
+            Err(error) => error!(
+                "failed reading the command history at {:?}: {}",
+                path, error
+            ),
+        }
+    }
+
+    fn save_history(&self) {
+        let path = match Self::history_path() {
+            Some(path) => path,
+            None => return,
+        };
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, self.history.join("\n")));
+        if let Err(error) = result {
+            error!("failed saving the command history to {:?}: {}", path, error);
+        }
+    }
+
+    fn add_to_history(&mut self, command: String) {
+        self.history.retain(|x| x != &command);
+        self.history.push(command);
+        if self.history.len() > HISTORY_MAX_LEN {
+            self.history.remove(0);
+        }
+        self.save_history();
+    }
+
+    /// Opens the command line with an empty input
+    pub fn enter(&mut self) {
+        self.is_focussed = true;
+        self.history_idx = None;
+        self.set_input(String::new());
+    }
+
+    pub fn exit(&mut self) {
+        self.is_focussed = false;
+        self.completions.clear();
+    }
+
+    fn set_input(&mut self, value: String) {
+        self.input = Input::new(value);
+        self.update_completions();
+    }
+
+    fn update_completions(&mut self) {
+        self.completions = complete(self.input.value(), &self.config);
+        self.selected = None;
+    }
+
+    /// Replaces the input with the next (or previous) completion. The suggestions stay the same
+    /// until something else is typed
+    fn cycle_completion(&mut self, forward: bool) {
+        if self.completions.is_empty() {
+            return;
+        }
+
+        let len = self.completions.len();
+        let selected = match self.selected {
+            Some(idx) if forward => (idx + 1) % len,
+            Some(idx) => (idx + len - 1) % len,
+            None if forward => 0,
+            None => len - 1,
+        };
+        self.selected = Some(selected);
+        self.input = Input::new(self.completions[selected].replacement.clone());
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+
+        let idx = match (self.history_idx, older) {
+            (None, true) => Some(self.history.len() - 1),
+            (None, false) => return,
+            (Some(idx), true) => Some(idx.saturating_sub(1)),
+            (Some(idx), false) if idx + 1 < self.history.len() => Some(idx + 1),
+            (Some(_), false) => None,
+        };
+
+        self.history_idx = idx;
+        let value = idx.map(|idx| self.history[idx].clone()).unwrap_or_default();
+        self.set_input(value);
+    }
+
+    fn submit(&mut self) -> ActionResult {
+        let command = self.input.value().trim().to_string();
+        let packet = ActionPacket::single(Action::ExitCommandLine);
+        if command.is_empty() {
+            return packet.into();
+        }
+
+        self.add_to_history(command.clone());
+        packet.action(Action::RunCommand(command)).into()
+    }
+
+    fn render_suggestions(&self, f: &mut Frame<'_>, area: Rect) {
+        let label_width = self
+            .completions
+            .iter()
+            .map(|completion| completion.label.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let items: Vec<ListItem> = self
+            .completions
+            .iter()
+            .map(|completion| {
+                let mut spans: Vec<Span> = completion
+                    .label
+                    .chars()
+                    .enumerate()
+                    .map(|(idx, c)| {
+                        let span = Span::raw(c.to_string());
+                        if completion.indices.contains(&idx) {
+                            span.fg(self.theme.highlight_fg)
+                                .add_modifier(Modifier::BOLD)
+                        } else {
+                            span
+                        }
+                    })
+                    .collect();
+                spans.push(Span::raw(
+                    " ".repeat(label_width - completion.label.chars().count() + 2),
+                ));
+                spans.push(Span::raw(completion.description.clone()).fg(self.theme.inactive_fg));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let height = items.len().min(MAX_SUGGESTIONS) as u16;
+        let area = Rect {
+            y: area.y.saturating_sub(height),
+            height: height.min(area.y),
+            ..area
+        };
+
+        let mut state = ListState::default().with_selected(self.selected);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(
+            List::new(items)
+                .style(Style::default().fg(self.theme.fg).bg(self.theme.bg))
+                .highlight_style(
+                    Style::default()
+                        .fg(self.theme.selected_fg)
+                        .bg(self.theme.selected_bg),
+                ),
+            area,
+            &mut state,
+        );
+    }
+}
+
+impl Component for CommandLineComponent {
+    fn init(
+        &mut self,
+        _: tokio::sync::mpsc::UnboundedSender<Action>,
+        config: Arc<Config>,
+        theme: Arc<Theme>,
+    ) -> anyhow::Result<()> {
+        self.config = config;
+        self.theme = theme;
+        self.load_history();
+        Ok(())
+    }
+
+    fn reconfigure(&mut self, config: Arc<Config>, theme: Arc<Theme>) {
+        self.config = config;
+        self.theme = theme;
+    }
+
+    fn is_typing(&self) -> bool {
+        self.is_focussed
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent, pending: &[Binding]) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .submit
+            .matches_event(key, pending)
+        {
+            return self.submit();
+        }
+
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
+        {
+            return Action::ExitCommandLine.into();
+        }
+
+        match key.code {
+            KeyCode::Tab => self.cycle_completion(true),
+            KeyCode::BackTab => self.cycle_completion(false),
+            KeyCode::Up => self.browse_history(true),
+            KeyCode::Down => self.browse_history(false),
+            // deleting the `:` closes the command line, like in vim
+            KeyCode::Backspace if self.input.value().is_empty() => {
+                return Action::ExitCommandLine.into()
+            }
+            _ => {
+                if self
+                    .input
+                    .handle_event(&crossterm::event::Event::Key(key))
+                    .is_some_and(|changed| changed.value)
+                {
+                    self.update_completions();
+                }
+            }
+        }
+        ActionResult::consumed()
+    }
+
+    fn render(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let prompt_width = 1;
+        let input_width = area.width.saturating_sub(prompt_width);
+        let scroll = self.input.visual_scroll(input_width as usize);
+
+        f.render_widget(Clear, area);
+        f.render_widget(
+            self.theme.default_paragraph(Line::from(vec![
+                Span::raw(":"),
+                Span::raw(self.input.value().chars().skip(scroll).collect::<String>()),
+            ])),
+            area,
+        );
+        f.set_cursor_position((
+            area.x + prompt_width + (self.input.visual_cursor().max(scroll) - scroll) as u16,
+            area.y,
+        ));
+
+        if !self.completions.is_empty() {
+            self.render_suggestions(f, area);
+        }
+    }
+}
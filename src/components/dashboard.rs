@@ -0,0 +1,474 @@
+use std::{path::PathBuf, sync::Arc};
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{HighlightSpacing, List, ListItem},
+};
+use tokio::sync::mpsc;
+use tracing::{error, warn};
+use wiki_api::{feed::Feed, languages::Language, Endpoint};
+
+use crate::{
+    action::{Action, ActionResult, SearchAction},
+    bookmarks::Bookmark,
+    config::{cache_dir, Binding, Config, DashboardSection, Theme},
+    terminal::Frame,
+    ui::{centered_rect, ScrollBehaviour, StatefulList},
+};
+
+use super::{page_viewer::ReadPage, Component};
+
+const SEARCH_HISTORY_FILE_NAME: &str = "search_history";
+const SEARCH_HISTORY_MAX_LEN: usize = 50;
+/// Width of the bar showing how far a page was read
+const PROGRESS_BAR_WIDTH: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DashboardEntry {
+    Page {
+        title: String,
+        language: Language,
+        endpoint: Endpoint,
+        /// How far the page was read, in percent. Only shown in the continue reading section
+        progress: Option<u8>,
+        description: Option<String>,
+    },
+    Search(String),
+}
+
+impl DashboardEntry {
+    fn page(title: String, language: Language, endpoint: Endpoint) -> Self {
+        DashboardEntry::Page {
+            title,
+            language,
+            endpoint,
+            progress: None,
+            description: None,
+        }
+    }
+}
+
+struct Section {
+    kind: DashboardSection,
+    entries: StatefulList<DashboardEntry>,
+}
+
+fn section_title(section: DashboardSection) -> &'static str {
+    match section {
+        DashboardSection::ContinueReading => "Continue Reading",
+        DashboardSection::Recent => "Recently Viewed",
+        DashboardSection::Bookmarks => "Bookmarks",
+        DashboardSection::SearchHistory => "Search History",
+        DashboardSection::Featured => "Today's Featured Article",
+    }
+}
+
+/// The start screen, shown before anything was searched. Lists the configured sections of pages
+/// and searches, the selected entry is opened with `submit`
+#[derive(Default)]
+pub struct DashboardComponent {
+    read_pages: Vec<ReadPage>,
+    bookmarks: Vec<Bookmark>,
+    /// Searched queries, the newest first
+    search_history: Vec<String>,
+    featured: Option<DashboardEntry>,
+
+    /// The sections that have entries, in the configured order
+    sections: Vec<Section>,
+    /// Index of the section the selection is in
+    focus: usize,
+
+    config: Arc<Config>,
+    theme: Arc<Theme>,
+}
+
+impl DashboardComponent {
+    fn search_history_path() -> Option<PathBuf> {
+        match cache_dir() {
+            Ok(dir) => Some(dir.join(SEARCH_HISTORY_FILE_NAME)),
+            Err(error) => {
+                warn!("unable to find the search history: {:?}", error);
+                None
+            }
+        }
+    }
+
+    fn load_search_history(&mut self) {
+        let path = match Self::search_history_path() {
+            Some(path) if path.exists() => path,
+            _ => return,
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(history) => {
+                self.search_history = history
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| line.to_string())
+                    .collect()
+            }
+            Err(error) => error!("failed reading the search history at {:?}: {}", path, error),
+        }
+    }
+
+    fn save_search_history(&self) {
+        let path = match Self::search_history_path() {
+            Some(path) => path,
+            None => return,
+        };
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, self.search_history.join("\n")));
+        if let Err(error) = result {
+            error!("failed saving the search history to {:?}: {}", path, error);
+        }
+    }
+
+    /// Remembers the query for the search history section
+    pub fn add_search(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+
+        self.search_history.retain(|x| x != query);
+        self.search_history.insert(0, query.to_string());
+        self.search_history.truncate(SEARCH_HISTORY_MAX_LEN);
+        self.save_search_history();
+        self.build_sections();
+    }
+
+    /// Replaces the viewed and bookmarked pages with newer ones
+    pub fn refresh(&mut self, read_pages: Vec<ReadPage>, bookmarks: &[Bookmark]) {
+        self.read_pages = read_pages;
+        self.bookmarks = bookmarks.to_vec();
+        self.build_sections();
+    }
+
+    pub fn set_featured(&mut self, feed: Feed) {
+        let Feed {
+            featured,
+            language,
+            endpoint,
+            ..
+        } = feed;
+        self.featured = featured.map(|article| DashboardEntry::Page {
+            title: article.title,
+            language,
+            endpoint,
+            progress: None,
+            description: article.description,
+        });
+        self.build_sections();
+    }
+
+    fn entries(&self, section: DashboardSection) -> Vec<DashboardEntry> {
+        let max_entries = self.config.ui.dashboard.max_entries;
+        match section {
+            DashboardSection::ContinueReading => self
+                .read_pages
+                .iter()
+                .filter(|page| 0 < page.progress && page.progress < 100)
+                .take(max_entries)
+                .map(|page| DashboardEntry::Page {
+                    title: page.title.clone(),
+                    language: page.language,
+                    endpoint: page.endpoint.clone(),
+                    progress: Some(page.progress),
+                    description: None,
+                })
+                .collect(),
+            DashboardSection::Recent => self
+                .read_pages
+                .iter()
+                .take(max_entries)
+                .map(|page| {
+                    DashboardEntry::page(page.title.clone(), page.language, page.endpoint.clone())
+                })
+                .collect(),
+            DashboardSection::Bookmarks => self
+                .bookmarks
+                .iter()
+                .take(max_entries)
+                .map(|bookmark| {
+                    DashboardEntry::page(
+                        bookmark.title.clone(),
+                        bookmark.language,
+                        bookmark.endpoint.clone(),
+                    )
+                })
+                .collect(),
+            DashboardSection::SearchHistory => self
+                .search_history
+                .iter()
+                .take(max_entries)
+                .map(|query| DashboardEntry::Search(query.clone()))
+                .collect(),
+            DashboardSection::Featured => self.featured.iter().cloned().collect(),
+        }
+    }
+
+    /// Rebuilds the sections from the config, leaving out the empty ones. The focused section
+    /// stays focused when it still has entries
+    fn build_sections(&mut self) {
+        let focused = self.sections.get(self.focus).map(|section| section.kind);
+
+        self.sections = self
+            .config
+            .ui
+            .dashboard
+            .sections
+            .iter()
+            .map(|&kind| Section {
+                kind,
+                entries: StatefulList::with_items(self.entries(kind))
+                    .scroll_behavior(ScrollBehaviour::StickToEnds),
+            })
+            .filter(|section| !section.entries.get_items().is_empty())
+            .collect();
+
+        self.focus = focused
+            .and_then(|kind| self.sections.iter().position(|x| x.kind == kind))
+            .unwrap_or_default();
+        for (idx, section) in self.sections.iter_mut().enumerate() {
+            if idx != self.focus {
+                section.entries.unselect();
+            }
+        }
+    }
+
+    fn focus_section(&mut self, idx: usize) {
+        if let Some(section) = self.sections.get_mut(self.focus) {
+            section.entries.unselect();
+        }
+        self.focus = idx;
+        if let Some(section) = self.sections.get_mut(self.focus) {
+            section.entries.get_state_mut().select(Some(0));
+        }
+    }
+
+    fn cycle_section(&mut self, forward: bool) -> ActionResult {
+        let len = self.sections.len();
+        if len == 0 {
+            return ActionResult::Ignored;
+        }
+
+        let idx = if forward {
+            (self.focus + 1) % len
+        } else {
+            (self.focus + len - 1) % len
+        };
+        self.focus_section(idx);
+        ActionResult::consumed()
+    }
+
+    /// Scrolls through the entries of the focused section, continuing in the next (or previous)
+    /// section at its ends
+    fn scroll(&mut self, n: u16, down: bool) -> ActionResult {
+        let n_sections = self.sections.len();
+        for _ in 0..n {
+            let Some(section) = self.sections.get_mut(self.focus) else {
+                return ActionResult::Ignored;
+            };
+
+            let selected = section.entries.get_state().selected();
+            let last = section.entries.get_items().len().saturating_sub(1);
+            match selected {
+                Some(idx) if down && idx >= last && self.focus + 1 < n_sections => {
+                    self.focus_section(self.focus + 1)
+                }
+                Some(0) if !down && self.focus > 0 => {
+                    self.focus_section(self.focus - 1);
+                    let section = &mut self.sections[self.focus];
+                    let last = section.entries.get_items().len().saturating_sub(1);
+                    section.entries.get_state_mut().select(Some(last));
+                }
+                _ if down => section.entries.next(),
+                _ => section.entries.previous(),
+            }
+        }
+        ActionResult::consumed()
+    }
+
+    fn open_selected(&self) -> ActionResult {
+        let entry = match self
+            .sections
+            .get(self.focus)
+            .and_then(|section| section.entries.selected())
+        {
+            Some(entry) => entry,
+            None => return ActionResult::Ignored,
+        };
+
+        match entry {
+            DashboardEntry::Page {
+                title,
+                language,
+                endpoint,
+                ..
+            } => Action::TryLoadPage(title.clone(), *language, endpoint.clone(), None).into(),
+            DashboardEntry::Search(query) => {
+                Action::Search(SearchAction::StartSearch(query.clone())).into()
+            }
+        }
+    }
+
+    fn render_entry(&self, entry: &DashboardEntry) -> ListItem<'static> {
+        let mut spans = Vec::new();
+        match entry {
+            DashboardEntry::Page {
+                title,
+                language,
+                progress,
+                description,
+                ..
+            } => {
+                if let Some(progress) = progress {
+                    let filled = *progress as usize * PROGRESS_BAR_WIDTH / 100;
+                    spans.push(
+                        Span::raw(format!(
+                            "{}{} {:>3}% ",
+                            "█".repeat(filled),
+                            "░".repeat(PROGRESS_BAR_WIDTH - filled),
+                            progress
+                        ))
+                        .fg(self.theme.inactive_fg),
+                    );
+                }
+                spans.push(Span::raw(title.clone()).fg(self.theme.search_title_fg));
+                spans.push(Span::raw(format!(" | {}", language.name())).fg(self.theme.fg));
+                if let Some(description) = description {
+                    spans.push(Span::raw(format!(" | {}", description)).fg(self.theme.inactive_fg));
+                }
+            }
+            DashboardEntry::Search(query) => {
+                spans.push(Span::raw(query.clone()).fg(self.theme.search_title_fg))
+            }
+        }
+        ListItem::new(Line::from(spans))
+    }
+}
+
+impl Component for DashboardComponent {
+    fn init(
+        &mut self,
+        _: mpsc::UnboundedSender<Action>,
+        config: Arc<Config>,
+        theme: Arc<Theme>,
+    ) -> anyhow::Result<()> {
+        self.config = config;
+        self.theme = theme;
+        self.load_search_history();
+        self.build_sections();
+        Ok(())
+    }
+
+    fn reconfigure(&mut self, config: Arc<Config>, theme: Arc<Theme>) {
+        self.config = config;
+        self.theme = theme;
+        self.build_sections();
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent, pending: &[Binding]) -> ActionResult {
+        let bindings = &self.config.bindings;
+        if bindings.global.submit.matches_event(key, pending) {
+            return self.open_selected();
+        }
+        if bindings
+            .search
+            .next_dashboard_section
+            .matches_event(key, pending)
+        {
+            return self.cycle_section(true);
+        }
+        if bindings
+            .search
+            .prev_dashboard_section
+            .matches_event(key, pending)
+        {
+            return self.cycle_section(false);
+        }
+        ActionResult::Ignored
+    }
+
+    fn update(&mut self, action: Action) -> ActionResult {
+        match action {
+            Action::ScrollUp(n) => self.scroll(n, false),
+            Action::ScrollDown(n) => self.scroll(n, true),
+            Action::UnselectScroll => {
+                if let Some(section) = self.sections.get_mut(self.focus) {
+                    section.entries.unselect();
+                }
+                ActionResult::consumed()
+            }
+            _ => ActionResult::Ignored,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame<'_>, area: Rect) {
+        if self.sections.is_empty() {
+            f.render_widget(
+                self.theme
+                    .default_paragraph("Start a search!")
+                    .alignment(Alignment::Center),
+                centered_rect(area, 100, 50),
+            );
+            return;
+        }
+
+        let mut constraints: Vec<Constraint> = self
+            .sections
+            .iter()
+            .map(|section| Constraint::Length(section.entries.get_items().len() as u16 + 2))
+            .collect();
+        constraints.push(Constraint::Min(0));
+        let areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(centered_rect(area, 60, 90));
+
+        let items: Vec<Vec<ListItem>> = self
+            .sections
+            .iter()
+            .map(|section| {
+                section
+                    .entries
+                    .get_items()
+                    .iter()
+                    .map(|entry| self.render_entry(entry))
+                    .collect()
+            })
+            .collect();
+
+        for (idx, (section, items)) in self.sections.iter_mut().zip(items).enumerate() {
+            let mut block = self
+                .theme
+                .default_block()
+                .title(section_title(section.kind));
+            if idx == self.focus {
+                block = block.border_style(
+                    Style::default()
+                        .fg(self.theme.border_highlight_fg)
+                        .bg(self.theme.border_highlight_bg),
+                );
+            }
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_symbol("| ")
+                .highlight_spacing(HighlightSpacing::Always)
+                .highlight_style(
+                    Style::default()
+                        .fg(self.theme.selected_fg)
+                        .bg(self.theme.selected_bg)
+                        .add_modifier(Modifier::ITALIC),
+                );
+            f.render_stateful_widget(list, areas[idx], section.entries.get_state_mut());
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+};
+use wiki_api::diff::{ChangeKind, Diff, DiffParagraph};
+
+use crate::{
+    action::{Action, ActionResult},
+    config::{Binding, Config, Theme},
+    terminal::Frame,
+    ui::{centered_rect, padded_rect},
+};
+
+use super::Component;
+
+/// Displays a word-level diff with insertions and deletions highlighted. The view can jump
+/// between the changed paragraphs
+pub struct DiffViewComponent {
+    title: String,
+    diff: Diff,
+    changes: Vec<usize>,
+    current_change: Option<usize>,
+
+    /// The wrapped lines and the first line of every paragraph, for the width they were wrapped at
+    lines: Vec<Line<'static>>,
+    paragraph_starts: Vec<usize>,
+    wrapped_width: u16,
+
+    scroll: usize,
+    viewport_height: u16,
+
+    config: Arc<Config>,
+    theme: Arc<Theme>,
+}
+
+impl DiffViewComponent {
+    pub fn new(title: String, diff: Diff, config: Arc<Config>, theme: Arc<Theme>) -> Self {
+        Self {
+            title,
+            changes: diff.changes(),
+            diff,
+            current_change: None,
+
+            lines: Vec::new(),
+            paragraph_starts: Vec::new(),
+            wrapped_width: 0,
+
+            scroll: 0,
+            viewport_height: 0,
+
+            config,
+            theme,
+        }
+    }
+
+    fn style(&self, kind: ChangeKind) -> Style {
+        match kind {
+            ChangeKind::Unchanged => Style::default().fg(self.theme.fg),
+            ChangeKind::Inserted => Style::default()
+                .fg(self.theme.diff_inserted_fg)
+                .bg(self.theme.diff_inserted_bg)
+                .add_modifier(Modifier::BOLD),
+            ChangeKind::Deleted => Style::default()
+                .fg(self.theme.diff_deleted_fg)
+                .bg(self.theme.diff_deleted_bg)
+                .add_modifier(Modifier::CROSSED_OUT),
+        }
+    }
+
+    /// Wraps a paragraph word by word while keeping the style of every word
+    fn wrap_paragraph(&self, paragraph: &DiffParagraph, width: usize) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        let mut line: Vec<Span<'static>> = Vec::new();
+        let mut line_width = 0;
+
+        for span in paragraph.spans.iter() {
+            let style = self.style(span.kind);
+            let mut is_first_word = true;
+
+            for word in span.text.split(' ') {
+                // the leading space of a span separates it from the previous one
+                let needs_space = !is_first_word || span.text.starts_with(' ');
+                is_first_word = false;
+                if word.is_empty() {
+                    continue;
+                }
+
+                let word_width = textwrap::core::display_width(word);
+                let space_width = usize::from(needs_space && line_width > 0);
+                if line_width > 0 && line_width + space_width + word_width > width {
+                    lines.push(Line::from(std::mem::take(&mut line)));
+                    line_width = 0;
+                }
+
+                if line_width > 0 && needs_space {
+                    // spaces between two changed words are part of the change
+                    let space_style = match line.last() {
+                        Some(last) if last.style == style => style,
+                        _ => Style::default(),
+                    };
+                    line.push(Span::styled(" ", space_style));
+                    line_width += 1;
+                }
+                line.push(Span::styled(word.to_string(), style));
+                line_width += word_width;
+            }
+        }
+
+        if !line.is_empty() {
+            lines.push(Line::from(line));
+        }
+        lines
+    }
+
+    fn wrap(&mut self, width: u16) {
+        if self.wrapped_width == width && !self.lines.is_empty() {
+            return;
+        }
+
+        let mut lines = Vec::new();
+        let mut paragraph_starts = Vec::new();
+        for paragraph in self.diff.paragraphs.iter() {
+            paragraph_starts.push(lines.len());
+            lines.extend(self.wrap_paragraph(paragraph, width as usize));
+            lines.push(Line::default());
+        }
+
+        self.lines = lines;
+        self.paragraph_starts = paragraph_starts;
+        self.wrapped_width = width;
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines
+            .len()
+            .saturating_sub(self.viewport_height as usize)
+    }
+
+    fn scroll_to_change(&mut self) {
+        let start = self
+            .current_change
+            .and_then(|i| self.changes.get(i))
+            .and_then(|paragraph| self.paragraph_starts.get(*paragraph));
+        if let Some(start) = start {
+            self.scroll = (*start).min(self.max_scroll());
+        }
+    }
+
+    fn next_change(&mut self) {
+        if self.changes.is_empty() {
+            return;
+        }
+        self.current_change = Some(match self.current_change {
+            Some(i) => (i + 1) % self.changes.len(),
+            None => 0,
+        });
+        self.scroll_to_change();
+    }
+
+    fn prev_change(&mut self) {
+        if self.changes.is_empty() {
+            return;
+        }
+        self.current_change = Some(match self.current_change {
+            Some(0) | None => self.changes.len() - 1,
+            Some(i) => i - 1,
+        });
+        self.scroll_to_change();
+    }
+
+    fn render_status_bar(&self, f: &mut Frame<'_>, area: Rect) {
+        let status_msg = match self.current_change {
+            _ if self.changes.is_empty() => " No changes".to_string(),
+            Some(i) => format!(" Change {} of {}", i + 1, self.changes.len()),
+            None => format!(" {} changes", self.changes.len()),
+        };
+        f.render_widget(
+            self.theme.default_paragraph(status_msg).style(
+                Style::default()
+                    .fg(self.theme.status_bar_fg)
+                    .bg(self.theme.status_bar_bg),
+            ),
+            area,
+        );
+    }
+}
+
+impl Component for DiffViewComponent {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[Binding],
+    ) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .page
+            .next_change
+            .matches_event(key, pending)
+        {
+            self.next_change();
+            return ActionResult::consumed();
+        }
+
+        if self
+            .config
+            .bindings
+            .page
+            .prev_change
+            .matches_event(key, pending)
+        {
+            self.prev_change();
+            return ActionResult::consumed();
+        }
+
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
+        {
+            return Action::PopPopup.into();
+        }
+
+        ActionResult::Ignored
+    }
+
+    fn update(&mut self, action: Action) -> ActionResult {
+        let half_page = (self.viewport_height / 2) as usize;
+        match action {
+            Action::ScrollUp(n) => self.scroll = self.scroll.saturating_sub(n as usize),
+            Action::ScrollDown(n) => {
+                self.scroll = (self.scroll + n as usize).min(self.max_scroll())
+            }
+            Action::ScrollHalfUp => self.scroll = self.scroll.saturating_sub(half_page),
+            Action::ScrollHalfDown => {
+                self.scroll = (self.scroll + half_page).min(self.max_scroll())
+            }
+            Action::ScrollToTop => self.scroll = 0,
+            Action::ScrollToBottom => self.scroll = self.max_scroll(),
+            _ => return ActionResult::Ignored,
+        }
+        ActionResult::consumed()
+    }
+
+    fn render(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let area = centered_rect(area, 90, 90);
+        f.render_widget(Clear, area);
+        f.render_widget(
+            self.theme
+                .default_block()
+                .title(self.title.as_str())
+                .style(Style::default().bg(self.theme.bg)),
+            area,
+        );
+
+        let (content_area, status_area) = {
+            let splits = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(100), Constraint::Min(1)])
+                .split(padded_rect(area, 1, 1));
+            (splits[0], splits[1])
+        };
+        let content_area = padded_rect(content_area, 0, 1);
+
+        self.wrap(content_area.width);
+        self.viewport_height = content_area.height;
+        self.scroll = self.scroll.min(self.max_scroll());
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .take(content_area.height as usize)
+            .cloned()
+            .collect();
+        f.render_widget(
+            Paragraph::new(lines).style(Style::default().bg(self.theme.bg).fg(self.theme.fg)),
+            content_area,
+        );
+
+        self.render_status_bar(f, status_area);
+    }
+}
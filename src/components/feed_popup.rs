@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Clear, HighlightSpacing, List, ListItem},
+};
+use wiki_api::feed::{Feed, FeedArticle};
+
+use crate::{
+    action::{Action, ActionPacket, ActionResult},
+    config::{Binding, Config, Theme},
+    terminal::Frame,
+    ui::{centered_rect, StatefulList},
+};
+
+use super::Component;
+
+struct FeedItem {
+    kind: &'static str,
+    title: String,
+    description: String,
+}
+
+impl FeedItem {
+    fn from_article(kind: &'static str, article: &FeedArticle) -> Self {
+        Self {
+            kind,
+            title: article.title.clone(),
+            description: article
+                .description
+                .clone()
+                .or_else(|| article.extract.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Displays today's featured article, the most read articles and the events that happened on this
+/// day
+pub struct FeedPopupComponent {
+    feed: Feed,
+    list: StatefulList<FeedItem>,
+
+    config: Arc<Config>,
+    theme: Arc<Theme>,
+}
+
+impl FeedPopupComponent {
+    pub fn new(feed: Feed, config: Arc<Config>, theme: Arc<Theme>) -> Self {
+        let mut items = Vec::new();
+
+        if let Some(ref featured) = feed.featured {
+            items.push(FeedItem::from_article("Featured article", featured));
+        }
+
+        items.extend(
+            feed.most_read
+                .iter()
+                .map(|article| FeedItem::from_article("Most read", article)),
+        );
+
+        items.extend(feed.on_this_day.iter().filter_map(|event| {
+            let article = event.pages.first()?;
+            Some(FeedItem {
+                kind: "On this day",
+                title: article.title.clone(),
+                description: match event.year {
+                    Some(year) => format!("{}: {}", year, event.text),
+                    None => event.text.clone(),
+                },
+            })
+        }));
+
+        Self {
+            feed,
+            list: StatefulList::with_items(items),
+
+            config,
+            theme,
+        }
+    }
+}
+
+impl Component for FeedPopupComponent {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[Binding],
+    ) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .submit
+            .matches_event(key, pending)
+        {
+            if let Some(item) = self.list.selected() {
+                return ActionPacket::single(Action::PopPopup)
+                    .action(Action::TryLoadPage(
+                        item.title.clone(),
+                        self.feed.language,
+                        self.feed.endpoint.clone(),
+                        None,
+                    ))
+                    .into();
+            }
+            return ActionResult::Ignored;
+        }
+
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
+            || self
+                .config
+                .bindings
+                .global
+                .show_feed
+                .matches_event(key, pending)
+        {
+            return Action::PopPopup.into();
+        }
+
+        ActionResult::Ignored
+    }
+
+    fn update(&mut self, action: Action) -> ActionResult {
+        match action {
+            Action::ScrollUp(n) => {
+                for _ in 0..n {
+                    self.list.previous()
+                }
+                ActionResult::consumed()
+            }
+            Action::ScrollDown(n) => {
+                for _ in 0..n {
+                    self.list.next()
+                }
+                ActionResult::consumed()
+            }
+            Action::UnselectScroll => {
+                self.list.unselect();
+                ActionResult::consumed()
+            }
+            _ => ActionResult::Ignored,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let area = centered_rect(area, 60, 70);
+        f.render_widget(Clear, area);
+
+        // HACK: subtract 3 for border and highlight symbol
+        let list_width = area.width.saturating_sub(3) as usize;
+        let items: Vec<ListItem> = self
+            .list
+            .get_items()
+            .iter()
+            .map(|item| {
+                let mut text = Text::from(Line::from(vec![
+                    Span::raw(format!("{} | ", item.kind)).fg(self.theme.inactive_fg),
+                    Span::raw(item.title.clone()).fg(self.theme.search_title_fg),
+                ]));
+                text.lines.extend(
+                    textwrap::wrap(&item.description, list_width)
+                        .iter()
+                        .take(2)
+                        .map(|s| Line::from(s.to_string()).fg(self.theme.fg)),
+                );
+                ListItem::new(text)
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                self.theme
+                    .default_block()
+                    .title("Today on Wikipedia")
+                    .style(Style::default().bg(self.theme.bg)),
+            )
+            .highlight_symbol("| ")
+            .repeat_highlight_symbol(true)
+            .highlight_spacing(HighlightSpacing::Always)
+            .highlight_style(
+                Style::default()
+                    .fg(self.theme.selected_fg)
+                    .bg(self.theme.selected_bg)
+                    .add_modifier(Modifier::ITALIC),
+            );
+        f.render_stateful_widget(list, area, self.list.get_state_mut());
+    }
+}
@@ -94,26 +94,48 @@ impl HelpPopupComponent {
             convert_binding!(config.bindings.global.unselect_scroll, "unselect scroll"),
             convert_binding!(config.bindings.global.enter_search_bar, "enter search bar"),
             convert_binding!(config.bindings.global.exit_search_bar, "exit search bar"),
+            convert_binding!(config.bindings.global.command_line, "command line"),
             convert_binding!(
                 config.bindings.global.switch_context_search,
                 "switch to search"
             ),
             convert_binding!(config.bindings.global.switch_context_page, "switch to page"),
+            convert_binding!(config.bindings.global.split_vertical, "split side by side"),
+            convert_binding!(
+                config.bindings.global.split_horizontal,
+                "split above each other"
+            ),
+            convert_binding!(config.bindings.global.focus_other_pane, "focus other pane"),
+            convert_binding!(config.bindings.global.close_pane, "close pane"),
             convert_binding!(
                 config.bindings.global.toggle_search_language_selection,
                 "toggle search language"
             ),
+            convert_binding!(
+                config.bindings.global.toggle_profile_selection,
+                "switch the wiki"
+            ),
             convert_binding!(config.bindings.global.toggle_logger, "toggle logger"),
+            convert_binding!(config.bindings.global.random_page, "open a random page"),
+            convert_binding!(config.bindings.global.show_feed, "show today's feed"),
             convert_binding!(config.bindings.global.help, "show this help"),
             convert_binding!(config.bindings.global.pop_popup, "close popup"),
+            convert_binding!(config.bindings.global.cancel_request, "cancel loading"),
             convert_binding!(config.bindings.global.quit, "quit"),
         ]
         .into();
 
-        let search_bindings_list = vec![convert_binding!(
-            config.bindings.search.continue_search,
-            "continue search"
-        )]
+        let search_bindings_list = vec![
+            convert_binding!(config.bindings.search.continue_search, "continue search"),
+            convert_binding!(
+                config.bindings.search.next_dashboard_section,
+                "next dashboard section"
+            ),
+            convert_binding!(
+                config.bindings.search.prev_dashboard_section,
+                "previous dashboard section"
+            ),
+        ]
         .into();
 
         let page_bindings_list = vec![
@@ -126,13 +148,65 @@ impl HelpPopupComponent {
                 "select previous link"
             ),
             convert_binding!(config.bindings.page.select_next_link, "select next link"),
+            convert_binding!(
+                config.bindings.page.next_section,
+                "jump to the next section"
+            ),
+            convert_binding!(
+                config.bindings.page.prev_section,
+                "jump to the previous section"
+            ),
+            convert_binding!(
+                config.bindings.page.next_section_same_level,
+                "jump to the next section on the same level"
+            ),
+            convert_binding!(
+                config.bindings.page.prev_section_same_level,
+                "jump to the previous section on the same level"
+            ),
+            convert_binding!(config.bindings.page.toggle_fold, "fold or unfold section"),
+            convert_binding!(config.bindings.page.fold_all, "fold all sections"),
+            convert_binding!(config.bindings.page.unfold_all, "unfold all sections"),
+            convert_binding!(config.bindings.page.toggle_bookmark, "toggle bookmark"),
             convert_binding!(config.bindings.page.open_link, "open link"),
+            convert_binding!(
+                config.bindings.page.open_link_in_other_pane,
+                "open link in other pane"
+            ),
+            convert_binding!(
+                config.bindings.page.switch_variant,
+                "switch language variant"
+            ),
             convert_binding!(
                 config.bindings.page.toggle_page_language_selection,
                 "toggle page language"
             ),
             convert_binding!(config.bindings.page.toggle_zen_mode, "toggle zen mode"),
             convert_binding!(config.bindings.page.toggle_toc, "toggle table of contents"),
+            convert_binding!(config.bindings.page.show_history, "show page history"),
+            convert_binding!(
+                config.bindings.page.show_changes,
+                "show changes since last read"
+            ),
+            convert_binding!(
+                config.bindings.page.compare_revision,
+                "compare revision with previous (history)"
+            ),
+            convert_binding!(config.bindings.page.next_change, "next change (diff)"),
+            convert_binding!(config.bindings.page.prev_change, "previous change (diff)"),
+            convert_binding!(
+                config.bindings.page.show_categories,
+                "browse page categories"
+            ),
+            convert_binding!(
+                config.bindings.page.show_backlinks,
+                "list pages linking here"
+            ),
+            convert_binding!(
+                config.bindings.page.show_summary,
+                "preview the selected link"
+            ),
+            convert_binding!(config.bindings.page.link_hints, "follow a link by its hint"),
         ]
         .into();
 
@@ -155,23 +229,50 @@ impl HelpPopupComponent {
 }
 
 impl Component for HelpPopupComponent {
-    fn handle_key_events(&mut self, key: crossterm::event::KeyEvent) -> ActionResult {
-        if self.config.bindings.global.pop_popup.matches_event(key)
-            || self.config.bindings.global.quit.matches_event(key)
-            || self.config.bindings.global.help.matches_event(key)
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[crate::config::Binding],
+    ) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
+            || self.config.bindings.global.quit.matches_event(key, pending)
+            || self.config.bindings.global.help.matches_event(key, pending)
         {
             return Action::PopPopup.into();
         }
 
-        if self.config.bindings.global.scroll_up.matches_event(key) {
+        if self
+            .config
+            .bindings
+            .global
+            .scroll_up
+            .matches_event(key, pending)
+        {
             return Action::ScrollUp(1).into();
         }
 
-        if self.config.bindings.global.scroll_down.matches_event(key) {
+        if self
+            .config
+            .bindings
+            .global
+            .scroll_down
+            .matches_event(key, pending)
+        {
             return Action::ScrollDown(1).into();
         }
 
-        if self.config.bindings.global.scroll_to_top.matches_event(key) {
+        if self
+            .config
+            .bindings
+            .global
+            .scroll_to_top
+            .matches_event(key, pending)
+        {
             return Action::ScrollToTop.into();
         }
 
@@ -180,16 +281,28 @@ impl Component for HelpPopupComponent {
             .bindings
             .global
             .scroll_to_bottom
-            .matches_event(key)
+            .matches_event(key, pending)
         {
             return Action::ScrollToBottom.into();
         }
 
-        if self.config.bindings.global.half_up.matches_event(key) {
+        if self
+            .config
+            .bindings
+            .global
+            .half_up
+            .matches_event(key, pending)
+        {
             return Action::ScrollHalfUp.into();
         }
 
-        if self.config.bindings.global.half_down.matches_event(key) {
+        if self
+            .config
+            .bindings
+            .global
+            .half_down
+            .matches_event(key, pending)
+        {
             return Action::ScrollHalfDown.into();
         }
 
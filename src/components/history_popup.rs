@@ -0,0 +1,315 @@
+use std::sync::Arc;
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Clear, HighlightSpacing, List, ListItem},
+};
+use wiki_api::{languages::Language, revisions::Revision, Endpoint};
+
+use crate::{
+    action::{Action, ActionPacket, ActionResult, HistoryAction},
+    config::{Binding, Config, Theme},
+    terminal::Frame,
+    ui::{centered_rect, ScrollBehaviour, StatefulList},
+};
+
+use super::Component;
+
+/// Lists the revisions of a page, newest first. Selecting a revision opens it read-only. More
+/// revisions are loaded when scrolling past the end of the list
+pub struct HistoryPopupComponent {
+    title: String,
+    current_revision: Option<usize>,
+    language: Language,
+    endpoint: Endpoint,
+
+    list: StatefulList<Revision>,
+    continue_from: Option<String>,
+    is_loading: bool,
+
+    config: Arc<Config>,
+    theme: Arc<Theme>,
+}
+
+impl HistoryPopupComponent {
+    pub fn new(
+        title: String,
+        current_revision: Option<usize>,
+        language: Language,
+        endpoint: Endpoint,
+        config: Arc<Config>,
+        theme: Arc<Theme>,
+    ) -> Self {
+        Self {
+            title,
+            current_revision,
+            language,
+            endpoint,
+
+            list: StatefulList::with_items(Vec::new())
+                .scroll_behavior(ScrollBehaviour::StickToEnds),
+            continue_from: None,
+            is_loading: true,
+
+            config,
+            theme,
+        }
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.list
+            .get_state()
+            .selected()
+            .is_some_and(|i| i + 1 >= self.list.get_items().len())
+    }
+
+    fn load_more(&mut self) -> ActionResult {
+        let continue_from = match self.continue_from {
+            Some(ref continue_from) if !self.is_loading => continue_from.clone(),
+            _ => return ActionResult::consumed(),
+        };
+
+        self.is_loading = true;
+        Action::History(HistoryAction::LoadHistory(
+            self.title.clone(),
+            self.language,
+            self.endpoint.clone(),
+            Some(continue_from),
+        ))
+        .into()
+    }
+
+    fn open_selected(&self) -> ActionResult {
+        let revision = match self.list.selected() {
+            Some(revision) => revision,
+            None => return ActionResult::Ignored,
+        };
+
+        // the current revision is the regular page, no need to load it as an old revision
+        let action = if Some(revision.revid) == self.current_revision {
+            Action::TryLoadPage(
+                self.title.clone(),
+                self.language,
+                self.endpoint.clone(),
+                None,
+            )
+        } else {
+            Action::LoadRevision(
+                self.title.clone(),
+                revision.revid,
+                self.language,
+                self.endpoint.clone(),
+            )
+        };
+
+        ActionPacket::single(Action::PopPopup).action(action).into()
+    }
+
+    fn compare_selected(&self) -> ActionResult {
+        let revision = match self.list.selected() {
+            Some(revision) => revision,
+            None => return ActionResult::Ignored,
+        };
+
+        match revision.parentid {
+            Some(parentid) => Action::CompareRevisions(
+                self.title.clone(),
+                parentid,
+                revision.revid,
+                self.endpoint.clone(),
+            )
+            .into(),
+            None => Action::PopupMessage(
+                "Information".to_string(),
+                "This is the first revision of the page, there is nothing to compare it with"
+                    .to_string(),
+            )
+            .into(),
+        }
+    }
+
+    /// Returns the change in size compared to the previous revision, if the previous revision is
+    /// known
+    fn size_difference(&self, index: usize) -> Option<i64> {
+        let revision = self.list.get_items().get(index)?;
+        match self.list.get_items().get(index + 1) {
+            Some(previous) => Some(revision.size as i64 - previous.size as i64),
+            None if revision.parentid.is_none() => Some(revision.size as i64),
+            None => None,
+        }
+    }
+
+    fn format_revision(&self, index: usize, revision: &Revision, width: usize) -> Text<'static> {
+        let size_difference = match self.size_difference(index) {
+            Some(diff) => format!("{:+}", diff),
+            None => "?".to_string(),
+        };
+
+        let mut header = vec![
+            Span::raw(revision.timestamp.replace('T', " ").replace('Z', "")),
+            Span::raw(" | "),
+            match revision.user {
+                Some(ref user) => Span::raw(user.clone()).fg(self.theme.search_title_fg),
+                None => Span::raw("(username removed)").fg(self.theme.inactive_fg),
+            },
+            Span::raw(format!(" | {} bytes ({})", revision.size, size_difference)),
+        ];
+        if Some(revision.revid) == self.current_revision {
+            header.push(Span::raw(" | current").add_modifier(Modifier::BOLD));
+        }
+
+        let mut text = Text::from(Line::from(header));
+        match revision.comment {
+            Some(ref comment) if !comment.is_empty() => text.lines.extend(
+                textwrap::wrap(comment, width)
+                    .iter()
+                    .take(2)
+                    .map(|s| Line::from(s.to_string()).fg(self.theme.fg)),
+            ),
+            Some(_) => {}
+            None => text
+                .lines
+                .push(Line::from("(edit summary removed)").fg(self.theme.inactive_fg)),
+        }
+        text
+    }
+}
+
+impl Component for HistoryPopupComponent {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[Binding],
+    ) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .submit
+            .matches_event(key, pending)
+        {
+            return self.open_selected();
+        }
+
+        if self
+            .config
+            .bindings
+            .page
+            .compare_revision
+            .matches_event(key, pending)
+        {
+            return self.compare_selected();
+        }
+
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
+            || self
+                .config
+                .bindings
+                .page
+                .show_history
+                .matches_event(key, pending)
+        {
+            return Action::PopPopup.into();
+        }
+
+        ActionResult::Ignored
+    }
+
+    fn update(&mut self, action: Action) -> ActionResult {
+        match action {
+            Action::History(HistoryAction::FinishHistory(revisions)) => {
+                self.is_loading = false;
+                self.continue_from = revisions.continue_from;
+                self.list.get_items_mut().extend(revisions.revisions);
+                ActionResult::consumed()
+            }
+            Action::ScrollUp(n) => {
+                for _ in 0..n {
+                    self.list.previous()
+                }
+                ActionResult::consumed()
+            }
+            Action::ScrollDown(n) => {
+                if self.is_at_end() {
+                    return self.load_more();
+                }
+                for _ in 0..n {
+                    self.list.next()
+                }
+                ActionResult::consumed()
+            }
+            Action::UnselectScroll => {
+                self.list.unselect();
+                ActionResult::consumed()
+            }
+            _ => ActionResult::Ignored,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let area = centered_rect(area, 70, 70);
+        f.render_widget(Clear, area);
+
+        let block = self
+            .theme
+            .default_block()
+            .title(format!("History of '{}'", self.title))
+            .style(Style::default().bg(self.theme.bg));
+
+        if self.list.get_items().is_empty() {
+            let message = if self.is_loading {
+                "Loading the history..."
+            } else {
+                "No revisions found"
+            };
+            f.render_widget(
+                self.theme
+                    .default_paragraph(message)
+                    .alignment(Alignment::Center)
+                    .block(block),
+                area,
+            );
+            return;
+        }
+
+        // HACK: subtract 3 for border and highlight symbol
+        let list_width = area.width.saturating_sub(3) as usize;
+        let mut items: Vec<ListItem> = self
+            .list
+            .get_items()
+            .iter()
+            .enumerate()
+            .map(|(i, revision)| ListItem::new(self.format_revision(i, revision, list_width)))
+            .collect();
+
+        if self.is_loading {
+            items.push(ListItem::new(
+                Line::from("Loading more revisions...").fg(self.theme.inactive_fg),
+            ));
+        } else if self.continue_from.is_some() {
+            items.push(ListItem::new(
+                Line::from("Scroll down to load more revisions").fg(self.theme.inactive_fg),
+            ));
+        }
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_symbol("| ")
+            .repeat_highlight_symbol(true)
+            .highlight_spacing(HighlightSpacing::Always)
+            .highlight_style(
+                Style::default()
+                    .fg(self.theme.selected_fg)
+                    .bg(self.theme.selected_bg)
+                    .add_modifier(Modifier::ITALIC),
+            );
+        f.render_stateful_widget(list, area, self.list.get_state_mut());
+    }
+}
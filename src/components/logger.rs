@@ -36,6 +36,11 @@ impl Component for LoggerComponent {
         Ok(())
     }
 
+    fn reconfigure(&mut self, config: Arc<Config>, theme: Arc<Theme>) {
+        self.config = config;
+        self.theme = theme;
+    }
+
     fn render(&mut self, frame: &mut Frame<'_>, size: Rect) {
         let widget = TuiLoggerWidget::default()
             .block(self.theme.default_block().title("Log"))
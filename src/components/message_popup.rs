@@ -10,7 +10,7 @@ use ratatui::{
 
 use crate::{
     action::{Action, ActionPacket, ActionResult},
-    config::Theme,
+    config::{Binding, Theme},
     ui::centered_rect,
 };
 
@@ -73,7 +73,7 @@ impl MessagePopupComponent {
 }
 
 impl Component for MessagePopupComponent {
-    fn handle_key_events(&mut self, key: KeyEvent) -> ActionResult {
+    fn handle_key_events(&mut self, key: KeyEvent, _pending: &[Binding]) -> ActionResult {
         match key.code {
             KeyCode::Char('y') if self.confirmation.is_some() => self
                 .confirmation
@@ -1,26 +1,38 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent, MouseEventKind};
 use ratatui::prelude::Rect;
 use tokio::sync::mpsc;
 
 use crate::{
     action::{Action, ActionResult},
-    config::{Config, Theme},
+    config::{Binding, Config, Theme},
     event::Event,
     terminal::Frame,
 };
 
+pub mod backlinks_popup;
+pub mod category_popup;
+pub mod command_line;
+pub mod dashboard;
+pub mod diff_view;
+pub mod feed_popup;
 pub mod help_popup;
+pub mod history_popup;
 pub mod logger;
 pub mod message_popup;
 pub mod page;
 pub mod page_language_popup;
 pub mod page_viewer;
+pub mod profile_popup;
 pub mod search;
 pub mod search_bar;
 pub mod search_language_popup;
+pub mod summary_popup;
+
+/// Amount of lines scrolled by one step of the mouse wheel
+pub const MOUSE_SCROLL_LINES: u16 = 3;
 
 #[macro_export]
 macro_rules! key_event {
@@ -56,22 +68,44 @@ pub trait Component {
         Ok(())
     }
 
+    /// Replaces the config and theme after they were changed at runtime, e.g. with `:set`
+    #[allow(unused_variables)]
+    fn reconfigure(&mut self, config: Arc<Config>, theme: Arc<Theme>) {}
+
     #[allow(unused_variables)]
     fn handle_events(&mut self, event: Option<Event>) -> ActionResult {
         match event {
             Some(Event::Quit) => Action::Quit.into(),
             Some(Event::RenderTick) => Action::RenderTick.into(),
-            Some(Event::Key(key_event)) => self.handle_key_events(key_event),
+            Some(Event::Key(key_event)) => self.handle_key_events(key_event, &[]),
+            Some(Event::Mouse(mouse_event)) => self.handle_mouse_events(mouse_event),
             Some(Event::Resize(x, y)) => Action::Resize(x, y).into(),
             None => ActionResult::Ignored,
         }
     }
 
+    /// Whether the component is reading text. Counts and key sequences are not collected while
+    /// typing, so all keys reach the component
+    fn is_typing(&self) -> bool {
+        false
+    }
+
+    /// Handles a key event. The pending keys are the first keys of a key sequence that were typed
+    /// before this one, they are matched together with the key
     #[allow(unused_variables)]
-    fn handle_key_events(&mut self, key: KeyEvent) -> ActionResult {
+    fn handle_key_events(&mut self, key: KeyEvent, pending: &[Binding]) -> ActionResult {
         ActionResult::Ignored
     }
 
+    /// Handles a mouse event. By default, the mouse wheel scrolls the component
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> ActionResult {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => Action::ScrollUp(MOUSE_SCROLL_LINES).into(),
+            MouseEventKind::ScrollDown => Action::ScrollDown(MOUSE_SCROLL_LINES).into(),
+            _ => ActionResult::Ignored,
+        }
+    }
+
     #[allow(unused_variables)]
     fn update(&mut self, action: Action) -> ActionResult {
         ActionResult::Ignored
@@ -1,25 +1,37 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::SystemTime,
+};
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::{Constraint, Direction, Flex, Layout},
-    prelude::{Margin, Rect},
+    prelude::{Margin, Position, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
     widgets::{Block, List, ListState, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
 };
 use tracing::{debug, info, warn};
 use wiki_api::{
-    document::{Data, Node},
-    page::{Link, Page, Section},
+    dictionary::Dictionary,
+    document::{Data, HeaderKind, Node},
+    endpoint::Project,
+    page::{link_data::InternalData, Link, Page, Section},
+    search::Namespace,
 };
 
 use crate::{
-    action::{Action, ActionPacket, ActionResult, PageAction},
-    components::Component,
-    config::{Config, Theme, TocConfigPosition, TocConfigTitle, ZenModeComponents},
+    action::{Action, ActionPacket, ActionResult, PageAction, PageFocus},
+    components::{Component, MOUSE_SCROLL_LINES},
+    config::{
+        Binding, Config, PrefetchMode, Theme, TocConfigPosition, TocConfigTitle, ZenModeComponents,
+    },
     has_modifier,
-    renderer::{default_renderer::render_document, RenderedDocument},
+    renderer::{
+        default_renderer::{render_dictionary, render_document, section_header},
+        RenderedDocument,
+    },
     terminal::Frame,
     ui::padded_rect,
 };
@@ -32,6 +44,8 @@ use crate::renderer::test_renderer::{render_nodes_raw, render_tree_data, render_
 pub enum Renderer {
     #[default]
     Default,
+    /// Compact layout of the definitions of Wiktionary pages
+    Dictionary,
 
     #[cfg(debug_assertions)]
     TestRendererTreeData,
@@ -44,11 +58,13 @@ pub enum Renderer {
 impl Renderer {
     pub fn next(&self) -> Self {
         match self {
+            &Renderer::Default => Renderer::Dictionary,
+
             #[cfg(not(debug_assertions))]
-            &Renderer::Default => Renderer::Default,
+            &Renderer::Dictionary => Renderer::Default,
 
             #[cfg(debug_assertions)]
-            &Renderer::Default => Renderer::TestRendererTreeData,
+            &Renderer::Dictionary => Renderer::TestRendererTreeData,
             #[cfg(debug_assertions)]
             &Renderer::TestRendererTreeData => Renderer::TestRendererTreeRaw,
             #[cfg(debug_assertions)]
@@ -59,12 +75,66 @@ impl Renderer {
     }
 }
 
+/// Label of a link in the viewport in the hint mode
+#[derive(Clone)]
+struct LinkHint {
+    label: String,
+    /// First and last index of the link node, like the selection
+    first: usize,
+    last: usize,
+}
+
+#[derive(Default, Clone)]
+struct LinkHints {
+    hints: Vec<LinkHint>,
+    typed: String,
+}
+
+/// Generates `count` labels of the same length from the characters, so no label is the prefix of
+/// another one
+fn hint_labels(characters: &[char], count: usize) -> Vec<String> {
+    let base = characters.len();
+    let mut length = 1;
+    while base.pow(length) < count {
+        length += 1;
+    }
+
+    (0..count)
+        .map(|mut n| {
+            let mut label = Vec::with_capacity(length as usize);
+            for _ in 0..length {
+                label.push(characters[n % base]);
+                n /= base;
+            }
+            label.into_iter().rev().collect()
+        })
+        .collect()
+}
+
+/// Where the reader is in the page. Unlike the viewport, this doesn't depend on the width the page
+/// is rendered at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct ReadingPosition {
+    /// Index of the node of the first word at the top of the viewport
+    node: usize,
+    /// How many words of the node come before the top of the viewport
+    word: usize,
+}
+
 #[derive(Default, Clone)]
 struct PageContentsState {
     list_state: ListState,
     max_idx_section: u8,
 }
 
+/// Where the parts of the page were drawn the last time, so mouse events can be mapped to them
+#[derive(Default, Clone)]
+struct PageAreas {
+    page: Rect,
+    toc: Option<Rect>,
+    scrollbar: Option<Rect>,
+}
+
 macro_rules! rendered_page {
     ($self: ident, $width: expr) => {
         match $self.rendered_page($width) {
@@ -92,8 +162,47 @@ pub struct PageComponent {
 
     is_contents: bool,
     is_zen_mode: bool,
+    /// Anchors of the headers whose sections are folded
+    #[serde(default)]
+    folded: HashSet<String>,
+    /// Where the page was read last, `None` at the top of the page
+    #[serde(default)]
+    position: Option<ReadingPosition>,
+    /// How far the page was scrolled, in percent
+    #[serde(default)]
+    progress: u8,
+    /// When the page was displayed the last time
+    #[serde(default)]
+    read_at: Option<SystemTime>,
+    /// Scroll to the reading position the next time the page is rendered
+    #[serde(skip)]
+    restore_position: bool,
+    /// Top of the viewport after the last render, `None` before the page was rendered since it
+    /// was opened. When the top changes between two renders, the user scrolled
+    #[serde(skip)]
+    rendered_y: Option<u16>,
     #[serde(skip)]
     contents_state: PageContentsState,
+    /// Title of the page whose first link should be selected once the page has been rendered
+    #[serde(skip)]
+    link_focus: Option<String>,
+    /// Anchor of the header that should be scrolled to once the page has been rendered
+    #[serde(skip)]
+    header_focus: Option<String>,
+    /// Labels of the links in the viewport while the hint mode is active
+    #[serde(skip)]
+    link_hints: Option<LinkHints>,
+    /// Count and keys of an unfinished binding, shown in the status bar
+    #[serde(skip)]
+    pending_keys: Option<String>,
+    /// Index of the link or section that was prefetched last, so it's only prefetched once
+    #[serde(skip)]
+    prefetched: Option<usize>,
+    #[serde(skip)]
+    areas: PageAreas,
+    /// Whether the scrollbar is being dragged with the mouse
+    #[serde(skip)]
+    is_dragging: bool,
 }
 
 impl PageComponent {
@@ -103,16 +212,35 @@ impl PageComponent {
             max_idx_section: page.sections().map(|x| x.len() as u8).unwrap_or_default(),
         };
 
+        // wiktionary pages start in the dictionary layout
+        let renderer = match page.endpoint.project() {
+            Project::Wiktionary => Renderer::Dictionary,
+            _ => Renderer::default(),
+        };
+
         Self {
             page,
-            renderer: Renderer::default(),
+            renderer,
             render_cache: HashMap::new(),
             viewport: Rect::default(),
             selected: (0, 0),
 
             is_contents: false,
             is_zen_mode: config.page.default_zen,
+            folded: HashSet::new(),
+            position: None,
+            progress: 0,
+            read_at: None,
+            restore_position: false,
+            rendered_y: None,
             contents_state,
+            link_focus: None,
+            header_focus: None,
+            link_hints: None,
+            pending_keys: None,
+            prefetched: None,
+            areas: PageAreas::default(),
+            is_dragging: false,
 
             config,
             theme,
@@ -123,9 +251,44 @@ impl PageComponent {
         self.is_zen_mode
     }
 
+    pub fn set_pending_keys(&mut self, keys: Option<String>) {
+        self.pending_keys = keys;
+    }
+
+    /// Selects the first link to the page with this title and scrolls to it. The link is
+    /// selected the next time the page is rendered, as the viewport is needed for scrolling
+    pub fn focus_link_to(&mut self, title: String) {
+        self.link_focus = Some(title);
+    }
+
+    /// Scrolls to the header with this anchor. Like [`focus_link_to`], this happens the next time
+    /// the page is rendered
+    ///
+    /// [`focus_link_to`]: PageComponent::focus_link_to
+    pub fn focus_header(&mut self, anchor: String) {
+        self.header_focus = Some(anchor);
+    }
+
+    /// Whether the hint mode is active. All keys are handled by the page in the hint mode
+    pub fn is_hinting(&self) -> bool {
+        self.link_hints.is_some()
+    }
+
+    /// How far the page was scrolled the last time it was displayed, in percent
+    pub fn progress(&self) -> u8 {
+        self.progress
+    }
+
+    pub fn read_at(&self) -> Option<SystemTime> {
+        self.read_at
+    }
+
     pub fn rebuild(&mut self, config: Arc<Config>, theme: Arc<Theme>) {
         self.config = config;
         self.theme = theme;
+        // the page could be rendered at another width than before
+        self.restore_position = true;
+        self.rendered_y = None;
         self.contents_state = PageContentsState {
             list_state: ListState::default().with_selected(Some(0)),
             max_idx_section: self
@@ -139,7 +302,31 @@ impl PageComponent {
     fn render_page(&mut self, width: u16) {
         info!("rendering page '{}' at width {}", self.page.title, width);
         let page = match self.renderer {
-            Renderer::Default => render_document(&self.page.content, width),
+            Renderer::Default => render_document(
+                &self.page.content,
+                width,
+                &self.folded,
+                self.page.language.direction(),
+            ),
+            Renderer::Dictionary => {
+                let dictionary = Dictionary::from_document(&self.page.content);
+                // pages without any entries are shown like every other page
+                if dictionary.is_empty() {
+                    render_document(
+                        &self.page.content,
+                        width,
+                        &self.folded,
+                        self.page.language.direction(),
+                    )
+                } else {
+                    render_dictionary(
+                        &self.page.content,
+                        &dictionary,
+                        width,
+                        self.page.language.direction(),
+                    )
+                }
+            }
             #[cfg(debug_assertions)]
             Renderer::TestRendererTreeData => render_tree_data(&self.page.content),
             #[cfg(debug_assertions)]
@@ -191,6 +378,11 @@ impl PageComponent {
         let sections = sections.unwrap();
         let list = List::new(sections.iter().map(|x| {
             let mut item = self.config.page.toc.formatted_item(&x.number, &x.text);
+            let fg = if self.folded.contains(&x.anchor) {
+                self.theme.inactive_fg
+            } else {
+                self.theme.fg
+            };
 
             if item
                 .char_indices()
@@ -202,9 +394,9 @@ impl PageComponent {
                     .nth(block.inner(area).width.saturating_sub(3) as usize)
                     .map_or(0, |(idx, _)| idx);
                 let trunc = item.drain(0..idx).collect::<String>() + "...";
-                trunc.fg(self.theme.fg)
+                trunc.fg(fg)
             } else {
-                item.fg(self.theme.fg)
+                item.fg(fg)
             }
         }))
         .block(block)
@@ -215,6 +407,7 @@ impl PageComponent {
                 .add_modifier(Modifier::ITALIC),
         );
         f.render_stateful_widget(list, area, &mut self.contents_state.list_state);
+        self.areas.toc = Some(area);
     }
 
     fn switch_renderer(&mut self, renderer: Renderer) {
@@ -253,8 +446,166 @@ impl PageComponent {
             return;
         }
 
-        let header_node = header_node.unwrap();
-        self.scroll_to_node(header_node.index());
+        let header_idx = header_node.unwrap().index();
+        // a header inside of a folded section isn't rendered
+        self.unfold_around(header_idx);
+        self.scroll_to_node(header_idx);
+    }
+
+    /// Returns the line, kind and anchor of the headers of the page, from top to bottom. Headers
+    /// hidden in folded sections are left out
+    fn headers(&mut self) -> Vec<(u16, HeaderKind, String)> {
+        let page = rendered_page!(self, self.viewport.width);
+        self.page
+            .content
+            .nth(0)
+            .unwrap()
+            .descendants()
+            .filter_map(|node| {
+                let (id, kind) = match node.data() {
+                    Data::Header { id, kind } => (id, kind),
+                    _ => return None,
+                };
+                let first = node.index();
+                let last = node.last_child().map(|x| x.index()).unwrap_or(first);
+                let y = page.lines.iter().position(|line| {
+                    line.iter()
+                        .any(|word| first <= word.index && word.index <= last)
+                })?;
+                Some((y as u16, kind.clone(), id.clone()))
+            })
+            .collect()
+    }
+
+    fn next_section(&mut self) {
+        let y = self.viewport.y;
+        if let Some((next, ..)) = self.headers().into_iter().find(|(line, ..)| *line > y) {
+            self.scroll_to_y(next);
+        }
+    }
+
+    fn prev_section(&mut self) {
+        let y = self.viewport.y;
+        let prev = self
+            .headers()
+            .into_iter()
+            .rev()
+            .find(|(line, ..)| *line < y);
+        self.scroll_to_y(prev.map(|(line, ..)| line).unwrap_or_default());
+    }
+
+    /// Scrolls to the next or previous header of the same kind as the header of the current
+    /// section. Before the first header, any header is used
+    fn section_same_level(&mut self, forward: bool) {
+        let y = self.viewport.y;
+        let headers = self.headers();
+        let kind = match headers.iter().rev().find(|(line, ..)| *line <= y) {
+            Some((_, kind, _)) => kind.clone(),
+            None if forward => return self.next_section(),
+            None => return self.prev_section(),
+        };
+
+        let same_level = headers.into_iter().filter(|(_, x, _)| x == &kind);
+        let target = if forward {
+            same_level.into_iter().find(|(line, ..)| *line > y)
+        } else {
+            same_level.into_iter().rev().find(|(line, ..)| *line < y)
+        };
+        if let Some((line, ..)) = target {
+            self.scroll_to_y(line);
+        }
+    }
+
+    /// Returns the anchor of the header of the section at the top of the page
+    fn current_header(&mut self) -> Option<String> {
+        let y = self.viewport.y;
+        self.headers()
+            .into_iter()
+            .rev()
+            .find(|(line, ..)| *line <= y)
+            .map(|(.., anchor)| anchor)
+    }
+
+    /// The folded sections are rendered differently, so the cached renders can't be used anymore
+    fn refold(&mut self) {
+        self.render_cache.clear();
+        // the selected link could be inside of a folded section now
+        self.selected = (0, 0);
+    }
+
+    fn toggle_fold(&mut self) {
+        let anchor = match self.current_header() {
+            Some(anchor) => anchor,
+            None => {
+                info!("no section at the top of the page");
+                return;
+            }
+        };
+
+        if !self.folded.remove(&anchor) {
+            self.folded.insert(anchor.clone());
+        }
+        self.refold();
+        self.select_header(anchor);
+    }
+
+    fn fold_to_level(&mut self, level: u8) {
+        let root = match self.page.content.nth(0) {
+            Some(root) => root,
+            None => return,
+        };
+
+        // the headers of the top-level sections (`h2`) are on level 1
+        self.folded = root
+            .descendants()
+            .filter(|node| matches!(node.data(), Data::Section { .. }))
+            .filter_map(section_header)
+            .filter_map(|(header, anchor)| match header.data() {
+                Data::Header { kind, .. } if kind.clone() as usize - 1 > level as usize => {
+                    Some(anchor.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        self.refold();
+        self.scroll_to_y(0);
+    }
+
+    fn unfold_all(&mut self) {
+        self.folded.clear();
+        self.refold();
+    }
+
+    /// Unfolds the sections containing the node, so it's rendered. The header of a folded section
+    /// is rendered, so its own section stays folded
+    fn unfold_around(&mut self, idx: usize) {
+        let node = match Node::new(&self.page.content, idx) {
+            Some(node) => node,
+            None => return,
+        };
+        let own_anchor = match node.data() {
+            Data::Header { id, .. } => Some(id),
+            _ => None,
+        };
+
+        let mut anchors = Vec::new();
+        let mut parent = node.parent();
+        while let Some(node) = parent {
+            if let Some((_, anchor)) = section_header(node) {
+                if Some(anchor) != own_anchor {
+                    anchors.push(anchor.clone());
+                }
+            }
+            parent = node.parent();
+        }
+
+        let mut is_changed = false;
+        for anchor in anchors {
+            is_changed |= self.folded.remove(&anchor);
+        }
+        if is_changed {
+            self.refold();
+        }
     }
 
     fn selected_header(&self) -> Option<&Section> {
@@ -300,6 +651,103 @@ impl PageComponent {
         self.page.content.nth(self.selected.0)
     }
 
+    fn select_link_to(&mut self, title: &str) {
+        if self.page.content.nth(0).is_none() {
+            return;
+        }
+
+        let selectable_node =
+            self.page
+                .content
+                .nth(0)
+                .unwrap()
+                .descendants()
+                .find(|node| match node.data() {
+                    Data::Link(Link::Internal(link_data)) => {
+                        link_data.title == title || link_data.page.replace('_', " ") == title
+                    }
+                    _ => false,
+                });
+
+        let node = match selectable_node {
+            Some(node) => node,
+            None => {
+                warn!("no link to '{}' could be found", title);
+                return;
+            }
+        };
+
+        self.select_node(node.index());
+        // the position of the link is only known after the page has been rendered
+        rendered_page!(self, self.viewport.width);
+        // keep some context above the link
+        let selected_y = self.selected_y() as u16;
+        self.scroll_to_y(selected_y.saturating_sub(self.viewport.height / 3));
+    }
+
+    /// Returns the links that should be prefetched when the selected link or the section at the
+    /// top of the page changed since the last call, depending on the prefetch mode
+    pub fn prefetch_links(&mut self) -> Option<Vec<InternalData>> {
+        let root = self.page.content.nth(0)?;
+        let prefetch = &self.config.page.prefetch;
+        // only articles are prefetched, not files or categories
+        let is_article = |link: &InternalData| link.namespace == Namespace::Main;
+
+        let (key, links) = match prefetch.mode {
+            PrefetchMode::Selected => {
+                let links = match self.selected_node().map(|node| node.data().to_owned()) {
+                    Some(Data::Link(Link::Internal(link))) if is_article(&link) => vec![link],
+                    _ => Vec::new(),
+                };
+                (self.selected.0, links)
+            }
+            PrefetchMode::Section => {
+                let top = self
+                    .rendered_page(self.viewport.width)
+                    .and_then(|page| page.lines.get(self.viewport.top() as usize))
+                    .and_then(|line| line.first())
+                    .map(|word| word.index)
+                    .unwrap_or_default();
+
+                let is_header = |node: &Node| matches!(node.data(), Data::Header { .. });
+                let start = root
+                    .descendants()
+                    .filter(|node| is_header(node) && node.index() <= top)
+                    .last()
+                    .map(|node| node.index())
+                    .unwrap_or_default();
+                let end = root
+                    .descendants()
+                    .find(|node| is_header(node) && node.index() > start)
+                    .map(|node| node.index())
+                    .unwrap_or(usize::MAX);
+
+                let mut links: Vec<InternalData> = Vec::new();
+                for node in root
+                    .descendants()
+                    .filter(|node| start <= node.index() && node.index() < end)
+                {
+                    if links.len() >= prefetch.links {
+                        break;
+                    }
+                    if let Data::Link(Link::Internal(link)) = node.data() {
+                        if is_article(link) && !links.iter().any(|x| x.page == link.page) {
+                            links.push(link.to_owned());
+                        }
+                    }
+                }
+                (start, links)
+            }
+        };
+
+        if self.prefetched == Some(key) {
+            return None;
+        }
+        self.prefetched = Some(key);
+
+        Some(links)
+    }
+
     fn select_first(&mut self) {
         if self.page.content.nth(0).is_none() {
             return;
@@ -515,7 +963,15 @@ impl PageComponent {
         }
     }
 
-    fn open_link(&self) -> ActionResult {
+    /// Whether the link points to a section of this page
+    fn is_link_to_self(&self, link_data: &InternalData) -> bool {
+        link_data.anchor.is_some()
+            && link_data.language == self.page.language
+            && link_data.page.replace('_', " ") == self.page.title
+    }
+
+    /// Opens the selected link, in the other pane of the split view when `in_other_pane` is set
+    fn open_link(&self, in_other_pane: bool) -> ActionResult {
         let index = self.selected.0;
         let node = Node::new(&self.page.content, index).unwrap();
         let data = node.data().to_owned();
@@ -529,7 +985,26 @@ impl PageComponent {
         };
 
         match link {
-            Link::Internal(_) | Link::Anchor(_) => (),
+            // the other pane needs to load this page first
+            Link::Anchor(ref link_data) if in_other_pane => {
+                return Action::InOtherPane(Box::new(Action::TryLoadPage(
+                    self.page.title.clone(),
+                    self.page.language,
+                    self.page.endpoint.clone(),
+                    Some(PageFocus::Header(link_data.anchor.clone())),
+                )))
+                .into()
+            }
+            // anchors on this page are scrolled to directly, without loading the page again
+            Link::Anchor(ref link_data) => {
+                return Action::Page(PageAction::GoToHeader(link_data.anchor.clone())).into()
+            }
+            Link::Internal(ref link_data) if !in_other_pane && self.is_link_to_self(link_data) => {
+                if let Some(ref anchor) = link_data.anchor {
+                    return Action::Page(PageAction::GoToHeader(anchor.anchor.clone())).into();
+                }
+            }
+            Link::Internal(_) => (),
             Link::External(link_data) => return Action::PopupMessage(
                 "Warning".to_string(), 
                 format!("This link doesn't point to another page. \nInstead, it leads to the following external webpage: \n\n{}", link_data.url.as_str())
@@ -547,17 +1022,312 @@ impl PageComponent {
             }
         }
 
+        let title = link.title().unwrap_or("UNKNOWN").to_string();
+        let action = if in_other_pane {
+            Action::InOtherPane(Box::new(Action::LoadLink(link)))
+        } else {
+            Action::LoadLink(link)
+        };
         Action::PopupDialog(
             "Information".to_string(),
-            format!(
-                "Do you want to open the page '{}'",
-                link.title().unwrap_or("UNKNOWN")
-            ),
-            Box::<ActionPacket>::new(Action::LoadLink(link).into()),
+            format!("Do you want to open the page '{}'", title),
+            Box::<ActionPacket>::new(action.into()),
         )
         .into()
     }
 
+    fn show_link_hints(&mut self) {
+        // typed characters are compared in lowercase
+        let mut characters: Vec<char> = Vec::new();
+        for c in self.config.page.hint_characters.chars() {
+            let c = c.to_ascii_lowercase();
+            if !characters.contains(&c) {
+                characters.push(c);
+            }
+        }
+        if characters.len() < 2 {
+            warn!("at least two hint characters are needed, using the default ones");
+            characters = "sadfjklewcmpgh".chars().collect();
+        }
+
+        // the title takes up the first line at the top of the page
+        let bottom = self.viewport.bottom().saturating_sub(self.title_offset());
+        let page = rendered_page!(self, self.viewport.width);
+        let visible: Vec<usize> = page
+            .links
+            .iter()
+            .filter(|(y, _)| self.viewport.top() as usize <= *y && *y < bottom as usize)
+            .map(|(_, idx)| *idx)
+            .collect();
+
+        if visible.is_empty() {
+            info!("no links are visible");
+            return;
+        }
+
+        let hints = hint_labels(&characters, visible.len())
+            .into_iter()
+            .zip(visible)
+            .filter_map(|(label, idx)| {
+                let node = Node::new(&self.page.content, idx)?;
+                Some(LinkHint {
+                    label,
+                    first: idx,
+                    last: node.last_child().map(|x| x.index()).unwrap_or(idx),
+                })
+            })
+            .collect();
+
+        self.link_hints = Some(LinkHints {
+            hints,
+            typed: String::new(),
+        });
+    }
+
+    /// Handles a key in the hint mode. When the typed characters match a label, the link is
+    /// followed: the link is opened without asking, previewed with `SHIFT` or only selected with
+    /// `CONTROL`
+    fn handle_hint_key(&mut self, key: KeyEvent) -> ActionResult {
+        let link_hints = match self.link_hints {
+            Some(ref mut link_hints) => link_hints,
+            None => return ActionResult::Ignored,
+        };
+
+        match key.code {
+            KeyCode::Char(c) => link_hints.typed.push(c.to_ascii_lowercase()),
+            KeyCode::Backspace if !link_hints.typed.is_empty() => {
+                link_hints.typed.pop();
+                return ActionResult::consumed();
+            }
+            _ => {
+                self.link_hints = None;
+                return ActionResult::consumed();
+            }
+        }
+
+        let typed = &link_hints.typed;
+        let hint = link_hints.hints.iter().find(|hint| &hint.label == typed);
+        let (first, last) = match hint {
+            Some(hint) => (hint.first, hint.last),
+            None if link_hints
+                .hints
+                .iter()
+                .any(|hint| hint.label.starts_with(typed.as_str())) =>
+            {
+                return ActionResult::consumed()
+            }
+            // nothing matches what was typed
+            None => {
+                self.link_hints = None;
+                return ActionResult::consumed();
+            }
+        };
+
+        self.link_hints = None;
+        self.selected = (first, last);
+
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return ActionResult::consumed();
+        }
+        if key.modifiers.contains(KeyModifiers::SHIFT) {
+            return self.show_summary();
+        }
+        self.follow_link()
+    }
+
+    /// Amount of lines the title moves the content down
+    fn title_offset(&self) -> u16 {
+        if self.viewport.y == 0 {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Draws the labels of the link hints over the first word of their links
+    fn render_link_hints(&self, f: &mut Frame<'_>, area: Rect, page: &RenderedDocument) {
+        let link_hints = match self.link_hints {
+            Some(ref link_hints) => link_hints,
+            None => return,
+        };
+
+        let offset = self.title_offset();
+        let typed = link_hints.typed.as_str();
+        let mut labelled = HashSet::new();
+
+        let lines = page
+            .lines
+            .iter()
+            .skip(self.viewport.top() as usize)
+            .take(area.height.saturating_sub(offset) as usize);
+        for (row, line) in lines.enumerate() {
+            let mut x = 0.0;
+            for word in line {
+                let hint = link_hints.hints.iter().find(|hint| {
+                    hint.first <= word.index
+                        && word.index <= hint.last
+                        && hint.label.starts_with(typed)
+                });
+
+                if let Some(hint) = hint.filter(|hint| labelled.insert(hint.first)) {
+                    let label_x = area.x + x as u16;
+                    let width = (hint.label.chars().count() as u16)
+                        .min(area.right().saturating_sub(label_x));
+                    let style = Style::default()
+                        .bg(self.theme.hint_bg)
+                        .add_modifier(Modifier::BOLD);
+                    let label = Line::from(vec![
+                        Span::styled(typed.to_string(), style.fg(self.theme.hint_typed_fg)),
+                        Span::styled(
+                            hint.label[typed.len()..].to_string(),
+                            style.fg(self.theme.hint_fg),
+                        ),
+                    ]);
+                    f.render_widget(
+                        Paragraph::new(label),
+                        Rect::new(label_x, area.y + row as u16 + offset, width, 1),
+                    );
+                }
+
+                x += word.width + word.whitespace_width;
+            }
+        }
+    }
+
+    /// Opens the selected link without asking first
+    fn follow_link(&self) -> ActionResult {
+        match self.selected_node().map(|node| node.data().to_owned()) {
+            Some(Data::Link(Link::Internal(link_data))) if !self.is_link_to_self(&link_data) => {
+                Action::LoadLink(Link::Internal(link_data)).into()
+            }
+            _ => self.open_link(false),
+        }
+    }
+
+    fn show_summary(&self) -> ActionResult {
+        let link = match self.selected_node().map(|node| node.data().to_owned()) {
+            Some(Data::Link(link)) => link,
+            _ => return ActionResult::Ignored,
+        };
+
+        match link {
+            Link::Internal(link_data) => {
+                Action::ShowSummary(link_data.page, link_data.language, link_data.endpoint).into()
+            }
+            _ => Action::PopupMessage(
+                "Information".to_string(),
+                "Only links to other pages can be previewed".to_string(),
+            )
+            .into(),
+        }
+    }
+
+    /// Reloads the page in the next variant of its language. The variant is kept for the other
+    /// pages and searches in the language
+    fn switch_variant(&self) -> ActionResult {
+        let language = self.page.language;
+        let variants = language.variants();
+        if variants.is_empty() {
+            return Action::PopupMessage(
+                "Information".to_string(),
+                format!("{} has no variants", language.name()),
+            )
+            .into();
+        }
+        if self.page.is_old_revision {
+            return Action::PopupMessage(
+                "Information".to_string(),
+                "Old revisions can't be converted to another variant".to_string(),
+            )
+            .into();
+        }
+
+        let current = self
+            .page
+            .variant
+            .as_deref()
+            .and_then(|code| variants.iter().position(|variant| variant.code == code))
+            .unwrap_or_default();
+        let next = variants[(current + 1) % variants.len()];
+
+        ActionPacket::single(Action::SetVariant(language, next.code.to_string()))
+            .action(Action::TryLoadPage(
+                self.page.title.clone(),
+                language,
+                self.page.endpoint.clone(),
+                None,
+            ))
+            .into()
+    }
+
+    /// Returns the reading position at the top of the viewport
+    fn reading_position(&self) -> Option<ReadingPosition> {
+        if self.viewport.y == 0 {
+            return None;
+        }
+
+        let page = self.rendered_page(self.viewport.width)?;
+        let top = self.viewport.y as usize;
+        // whitespace and horizontal lines don't belong to a node
+        let node = page
+            .lines
+            .get(top..)?
+            .iter()
+            .flatten()
+            .find(|word| word.index != usize::MAX)?
+            .index;
+        let word = page.lines[..top]
+            .iter()
+            .flatten()
+            .filter(|word| word.index == node)
+            .count();
+        Some(ReadingPosition { node, word })
+    }
+
+    fn restore_reading_position(&mut self) {
+        let position = match self.position {
+            Some(position) => position,
+            None => return self.scroll_to_y(0),
+        };
+
+        let page = rendered_page!(self, self.viewport.width);
+        let mut words = 0;
+        let y = page.lines.iter().position(|line| {
+            words += line
+                .iter()
+                .filter(|word| word.index == position.node)
+                .count();
+            words > position.word
+        });
+        if let Some(y) = y {
+            self.scroll_to_y(y as u16);
+        }
+    }
+
+    /// Saves the reading position when the page is left, e.g. when going back or opening another
+    /// page
+    pub fn close(&mut self) {
+        if self.rendered_y.is_some() {
+            self.update_reading_position();
+        }
+    }
+
+    fn update_reading_position(&mut self) {
+        self.position = self.reading_position();
+        self.read_at = Some(SystemTime::now());
+
+        let n_lines = self
+            .rendered_page(self.viewport.width)
+            .map(|page| page.lines.len())
+            .unwrap_or_default();
+        let max_y = n_lines.saturating_sub(self.viewport.height as usize);
+        self.progress = if max_y == 0 {
+            100
+        } else {
+            (self.viewport.y as usize * 100 / max_y).min(100) as u8
+        };
+    }
+
     fn resize(&mut self, width: u16, height: u16) {
         self.viewport.width = width;
         self.viewport.height = height;
@@ -572,24 +1342,66 @@ impl PageComponent {
             (splits[0], splits[1])
         };
 
-        let status_msg = format!(
+        let mut status_msg = vec![Span::raw(format!(
             " wiki-tui | Page '{}' | Language '{}' | '{}' other languages available",
             self.page.title,
             self.page.language.name(),
             self.page.available_languages().unwrap_or_default()
-        );
+        ))];
+        let categories = self
+            .page
+            .categories
+            .iter()
+            .flatten()
+            .filter(|category| !category.hidden)
+            .count();
+        if categories > 0 {
+            status_msg.push(Span::raw(format!(" | '{}' categories", categories)));
+        }
+        if let Some(variant) = self
+            .page
+            .variant
+            .as_deref()
+            .and_then(|code| self.page.language.variant(code))
+        {
+            status_msg.push(Span::raw(format!(" | Variant '{}'", variant.name)));
+        }
+        if self.page.is_old_revision {
+            status_msg.push(Span::raw(" | "));
+            status_msg.push(
+                Span::raw(format!(
+                    "Old revision {} (read-only)",
+                    self.page.revision_id.unwrap_or_default()
+                ))
+                .add_modifier(Modifier::BOLD),
+            );
+        }
         f.render_widget(
-            self.theme.default_paragraph(status_msg).style(
+            self.theme.default_paragraph(Line::from(status_msg)).style(
                 Style::default()
                     .fg(self.theme.status_bar_fg)
                     .bg(self.theme.status_bar_bg),
             ),
             status_area,
         );
+        self.render_pending_keys(f, status_area);
 
         area
     }
 
+    /// Shows the count and keys of an unfinished binding at the right of the status bar
+    fn render_pending_keys(&self, f: &mut Frame<'_>, area: Rect) {
+        if let Some(ref keys) = self.pending_keys {
+            f.render_widget(
+                Line::from(format!("{} ", keys))
+                    .fg(self.theme.status_bar_fg)
+                    .add_modifier(Modifier::BOLD)
+                    .right_aligned(),
+                area,
+            );
+        }
+    }
+
     fn render_toc(&mut self, f: &mut Frame<'_>, area: Rect) -> Rect {
         if self.config.page.toc.enabled {
             let mut constraints = [
@@ -638,15 +1450,100 @@ impl PageComponent {
         let mut scrollbar_state =
             ScrollbarState::new(content_length).position(self.viewport.top() as usize);
         f.render_stateful_widget(scrollbar, area, &mut scrollbar_state);
+        self.areas.scrollbar = Some(Rect::new(
+            area.right().saturating_sub(1),
+            area.y,
+            1,
+            area.height,
+        ));
+    }
+
+    /// Returns the first and last index of the link at this position on the screen
+    fn link_at(&self, position: Position) -> Option<(usize, usize)> {
+        let area = self.areas.page;
+        if !area.contains(position) {
+            return None;
+        }
+
+        let row = (position.y - area.y).checked_sub(self.title_offset())?;
+        let column = (position.x - area.x) as f64;
+        let page = self.rendered_page(self.viewport.width)?;
+        let line = page.lines.get((self.viewport.top() + row) as usize)?;
+
+        let mut x = 0.0;
+        let word = line.iter().find(|word| {
+            let start = x;
+            x += word.width + word.whitespace_width;
+            start <= column && column < x
+        })?;
+
+        page.links.iter().find_map(|(_, idx)| {
+            let node = Node::new(&self.page.content, *idx)?;
+            let last = node.last_child().map(|x| x.index()).unwrap_or(*idx);
+            (*idx <= word.index && word.index <= last).then_some((*idx, last))
+        })
+    }
+
+    /// Returns the index of the section whose entry in the table of contents is at this position
+    /// on the screen
+    fn header_at(&self, position: Position) -> Option<usize> {
+        // the entries are drawn inside the border
+        let area = self.areas.toc?.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        if !area.contains(position) {
+            return None;
+        }
+
+        let idx = self.contents_state.list_state.offset() + (position.y - area.y) as usize;
+        (idx < self.page.sections.as_ref()?.len()).then_some(idx)
+    }
+
+    /// Scrolls the page so the thumb of the scrollbar is at this row
+    fn drag_scrollbar(&mut self, row: u16) {
+        let area = match self.areas.scrollbar {
+            Some(area) if area.height > 1 => area,
+            _ => return,
+        };
+
+        let content_length = rendered_page!(self, self.viewport.width)
+            .lines
+            .len()
+            .saturating_sub(self.viewport.height as usize);
+        let row = row.clamp(area.top(), area.bottom() - 1) - area.y;
+        let y = content_length * row as usize / (area.height - 1) as usize;
+        self.scroll_to_y(y as u16);
+    }
+
+    /// Moves the selection in the table of contents, without wrapping around like the keys do
+    fn scroll_contents(&mut self, up: bool) {
+        let list_state = &mut self.contents_state.list_state;
+        let selected = list_state.selected().unwrap_or_default();
+        let max_idx = (self.contents_state.max_idx_section as usize).saturating_sub(1);
+        list_state.select(Some(if up {
+            selected.saturating_sub(MOUSE_SCROLL_LINES as usize)
+        } else {
+            (selected + MOUSE_SCROLL_LINES as usize).min(max_idx)
+        }));
     }
 }
 
 impl Component for PageComponent {
-    fn handle_key_events(&mut self, key: KeyEvent) -> ActionResult {
+    fn reconfigure(&mut self, config: Arc<Config>, theme: Arc<Theme>) {
+        self.config = config;
+        self.theme = theme;
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent, pending: &[Binding]) -> ActionResult {
+        if self.is_hinting() {
+            return self.handle_hint_key(key);
+        }
+
         let page_bindings = &self.config.bindings.page;
         macro_rules! matches_binding {
             ($binding:ident, $action:expr) => {
-                if page_bindings.$binding.matches_event(key) {
+                if page_bindings.$binding.matches_event(key, pending) {
                     return $action.into();
                 }
             };
@@ -674,11 +1571,27 @@ impl Component for PageComponent {
         matches_binding!(select_prev_link, Action::Page(PageAction::SelectPrevLink));
         matches_binding!(select_next_link, Action::Page(PageAction::SelectNextLink));
 
-        matches_binding!(open_link, self.open_link());
-        matches_binding!(toggle_zen_mode, {
-            self.is_zen_mode = !self.is_zen_mode;
-            ActionResult::Ignored
-        });
+        matches_binding!(next_section, Action::Page(PageAction::NextSection));
+        matches_binding!(prev_section, Action::Page(PageAction::PrevSection));
+        matches_binding!(
+            next_section_same_level,
+            Action::Page(PageAction::NextSectionSameLevel)
+        );
+        matches_binding!(
+            prev_section_same_level,
+            Action::Page(PageAction::PrevSectionSameLevel)
+        );
+
+        matches_binding!(toggle_fold, Action::Page(PageAction::ToggleFold));
+        matches_binding!(fold_all, Action::Page(PageAction::FoldToLevel(0)));
+        matches_binding!(unfold_all, Action::Page(PageAction::UnfoldAll));
+
+        matches_binding!(open_link, self.open_link(false));
+        matches_binding!(open_link_in_other_pane, self.open_link(true));
+        matches_binding!(show_summary, self.show_summary());
+        matches_binding!(switch_variant, self.switch_variant());
+        matches_binding!(link_hints, Action::Page(PageAction::ShowLinkHints));
+        matches_binding!(toggle_zen_mode, Action::Page(PageAction::ToggleZenMode));
 
         match key.code {
             KeyCode::Char('r') if has_modifier!(key, Modifier::CONTROL) => {
@@ -688,6 +1601,51 @@ impl Component for PageComponent {
         }
     }
 
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> ActionResult {
+        let position = Position::new(mouse.column, mouse.row);
+        let is_over_toc = self.areas.toc.is_some_and(|area| area.contains(position));
+
+        match mouse.kind {
+            MouseEventKind::ScrollUp if is_over_toc => self.scroll_contents(true),
+            MouseEventKind::ScrollDown if is_over_toc => self.scroll_contents(false),
+            MouseEventKind::ScrollUp => {
+                self.scroll_to_y(self.viewport.y.saturating_sub(MOUSE_SCROLL_LINES))
+            }
+            MouseEventKind::ScrollDown => self.scroll_to_y(self.viewport.y + MOUSE_SCROLL_LINES),
+
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.link_hints = None;
+
+                if self
+                    .areas
+                    .scrollbar
+                    .is_some_and(|area| area.contains(position))
+                {
+                    self.is_dragging = true;
+                    self.drag_scrollbar(mouse.row);
+                } else if let Some(idx) = self.header_at(position) {
+                    self.contents_state.list_state.select(Some(idx));
+                    let anchor = self.page.sections.as_ref().unwrap()[idx].anchor.clone();
+                    return Action::Page(PageAction::GoToHeader(anchor)).into();
+                } else if let Some(link) = self.link_at(position) {
+                    // clicking the selected link again opens it
+                    if self.selected == link {
+                        return self.follow_link();
+                    }
+                    self.selected = link;
+                } else {
+                    return ActionResult::Ignored;
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.is_dragging => {
+                self.drag_scrollbar(mouse.row)
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.is_dragging => self.is_dragging = false,
+            _ => return ActionResult::Ignored,
+        }
+        ActionResult::consumed()
+    }
+
     fn update(&mut self, action: Action) -> ActionResult {
         match action {
             Action::Page(page_action) => match page_action {
@@ -703,6 +1661,15 @@ impl Component for PageComponent {
                 PageAction::SelectNextLink => self.select_next(),
 
                 PageAction::GoToHeader(anchor) => self.select_header(anchor),
+                PageAction::NextSection => self.next_section(),
+                PageAction::PrevSection => self.prev_section(),
+                PageAction::NextSectionSameLevel => self.section_same_level(true),
+                PageAction::PrevSectionSameLevel => self.section_same_level(false),
+                PageAction::ToggleFold => self.toggle_fold(),
+                PageAction::FoldToLevel(level) => self.fold_to_level(level),
+                PageAction::UnfoldAll => self.unfold_all(),
+                PageAction::ShowLinkHints => self.show_link_hints(),
+                PageAction::ToggleZenMode => self.is_zen_mode = !self.is_zen_mode,
             },
             Action::ScrollUp(amount) => self.scroll_up(amount),
             Action::ScrollDown(amount) => self.scroll_down(amount),
@@ -738,6 +1705,9 @@ impl Component for PageComponent {
             area = self.render_status_bar(f, area);
         }
 
+        // the table of contents and the scrollbar are not drawn in every render
+        self.areas = PageAreas::default();
+
         if !self.is_zen_mode || zen_mode.contains(ZenModeComponents::TOC) {
             area = self.render_toc(f, area);
         }
@@ -751,8 +1721,28 @@ impl Component for PageComponent {
             area
         };
 
+        // the lines at the top of the viewport change with the width
+        let is_width_changed = self.viewport.width != page_area.width;
         self.viewport.width = page_area.width;
         self.viewport.height = page_area.height;
+        self.areas.page = page_area;
+
+        if is_width_changed || self.restore_position {
+            self.restore_position = false;
+            self.restore_reading_position();
+            // restoring the reading position doesn't count as scrolling
+            if self.rendered_y.is_some() {
+                self.rendered_y = Some(self.viewport.y);
+            }
+        }
+
+        if let Some(title) = self.link_focus.take() {
+            self.select_link_to(&title);
+        }
+
+        if let Some(anchor) = self.header_focus.take() {
+            self.select_header(anchor);
+        }
 
         let rendered_page = rendered_page!(self, page_area.width);
         let mut lines: Vec<Line> = rendered_page
@@ -800,6 +1790,7 @@ impl Component for PageComponent {
         }
 
         f.render_widget(Paragraph::new(lines), page_area);
+        self.render_link_hints(f, page_area, rendered_page);
 
         if !self.is_zen_mode || zen_mode.contains(ZenModeComponents::SCROLLBAR) {
             self.render_scrollbar(
@@ -811,5 +1802,11 @@ impl Component for PageComponent {
                     .saturating_sub(self.viewport.height as usize),
             )
         }
+
+        // the page was opened or scrolled since the last render
+        if self.rendered_y != Some(self.viewport.y) {
+            self.update_reading_position();
+        }
+        self.rendered_y = Some(self.viewport.y);
     }
 }
@@ -3,17 +3,17 @@ use std::sync::Arc;
 use crossterm::event::KeyCode;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style, Stylize},
-    widgets::{Clear, List, ListItem},
+    style::{Modifier, Style},
+    widgets::{Clear, List},
 };
 use tui_input::{backend::crossterm::EventHandler, Input};
 use wiki_api::page::LanguageLink;
 
 use crate::{
     action::{Action, ActionPacket, ActionResult},
-    config::{Config, Theme},
+    config::{Binding, Config, Theme},
     terminal::Frame,
-    ui::{centered_rect, StatefulList},
+    ui::{centered_rect, filter_languages, LanguageItem, StatefulList},
 };
 
 use super::Component;
@@ -24,8 +24,8 @@ const FOCUS_LIST: u8 = 1;
 pub struct PageLanguageSelectionComponent {
     input: Input,
     focus: u8,
-    list: StatefulList<LanguageLink>,
-    language_links: Vec<LanguageLink>,
+    list: StatefulList<LanguageItem<LanguageLink>>,
+    language_links: Vec<LanguageItem<LanguageLink>>,
 
     config: Arc<Config>,
     theme: Arc<Theme>,
@@ -33,9 +33,20 @@ pub struct PageLanguageSelectionComponent {
 
 impl PageLanguageSelectionComponent {
     pub fn new(language_links: Vec<LanguageLink>, config: Arc<Config>, theme: Arc<Theme>) -> Self {
+        let language_links: Vec<LanguageItem<LanguageLink>> = language_links
+            .into_iter()
+            .map(|link| {
+                let (language, autonym, title) =
+                    (link.language, link.autonym.clone(), link.title.clone());
+                LanguageItem::new(link, language, autonym)
+                    .detail(title)
+                    .pin(&config.ui.pinned_languages)
+            })
+            .collect();
+
         Self {
             input: Input::default(),
-            list: StatefulList::with_items(language_links.clone()),
+            list: StatefulList::with_items(filter_languages("", &language_links)),
             language_links,
             focus: 0,
 
@@ -45,25 +56,29 @@ impl PageLanguageSelectionComponent {
     }
 
     fn update_list(&mut self) {
-        let input_value = self.input.value();
-        let sorted_languages = self
-            .language_links
-            .iter()
-            .filter(|lang_link| {
-                let lang = lang_link.language.name().to_lowercase();
-                let query = input_value.to_lowercase();
-                lang.contains(&query)
-            })
-            .map(|x| x.to_owned())
-            .collect::<Vec<LanguageLink>>();
-        self.list = StatefulList::with_items(sorted_languages);
+        self.list =
+            StatefulList::with_items(filter_languages(self.input.value(), &self.language_links));
     }
 }
 
 impl Component for PageLanguageSelectionComponent {
-    fn handle_key_events(&mut self, key: crossterm::event::KeyEvent) -> ActionResult {
-        if self.config.bindings.global.submit.matches_event(key) {
-            if let Some(link) = self.list.selected() {
+    fn is_typing(&self) -> bool {
+        self.focus == FOCUS_INPUT
+    }
+
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[Binding],
+    ) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .submit
+            .matches_event(key, pending)
+        {
+            if let Some(link) = self.list.selected().map(|item| &item.value) {
                 let mut packet = ActionPacket::single(Action::PopPopup);
                 if self.config.ui.popup_page_language_changed {
                     packet = packet.action(Action::PopupMessage(
@@ -81,13 +96,18 @@ impl Component for PageLanguageSelectionComponent {
             return ActionResult::Ignored;
         }
 
-        if self.config.bindings.global.pop_popup.matches_event(key)
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
             || self
                 .config
                 .bindings
                 .page
                 .toggle_page_language_selection
-                .matches_event(key)
+                .matches_event(key, pending)
         {
             return Action::PopPopup.into();
         }
@@ -145,7 +165,7 @@ impl Component for PageLanguageSelectionComponent {
             .default_block()
             .title("Switch Page Language")
             .style(Style::default().bg(self.theme.bg));
-        let area = centered_rect(area, 25, 60);
+        let area = centered_rect(area, 40, 60);
         f.render_widget(Clear, area);
         f.render_widget(popup_block, area);
 
@@ -183,7 +203,7 @@ impl Component for PageLanguageSelectionComponent {
             .list
             .get_items()
             .iter()
-            .map(|x| ListItem::new(x.language.name().to_owned()).fg(self.theme.fg));
+            .map(|x| x.to_list_item(&self.theme));
         let list_widget = List::new(list_items).highlight_style(if self.focus == FOCUS_LIST {
             Style::default()
                 .fg(self.theme.selected_fg)
@@ -1,26 +1,40 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use ratatui::{
     prelude::{Alignment, Rect},
-    style::Style,
+    style::{Style, Stylize},
+    text::{Line, Text},
 };
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::{debug, error};
 use uuid::Uuid;
 
 use crate::{
-    action::{Action, ActionResult, PageViewerAction},
-    config::{Config, Theme},
+    action::{Action, ActionResult, PageFocus, PageViewerAction, PaneSender},
+    app::PaneId,
+    bookmarks::{Bookmark, Bookmarks},
+    config::{Binding, Config, Theme},
+    export::{export_page, ExportFormat},
+    page_cache::SharedPageCache,
+    prefetcher::Prefetcher,
+    request::RequestHandle,
     terminal::Frame,
     ui::centered_rect,
 };
 
 use super::{page::PageComponent, page_language_popup::PageLanguageSelectionComponent, Component};
 
-use wiki_api::{
-    languages::Language,
-    page::{Page, Property},
-};
+use wiki_api::{languages::Language, page::Page, Endpoint};
+
+/// A page that was viewed before
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadPage {
+    pub title: String,
+    pub language: Language,
+    pub endpoint: Endpoint,
+    /// How far the page was read, in percent
+    pub progress: u8,
+}
 
 /// Can display multiple pages and supports selecting between them
 /// Responsible for fetching the pages and managing them (NOT rendering)
@@ -28,12 +42,19 @@ use wiki_api::{
 pub struct PageViewer {
     page: Vec<PageComponent>,
     page_n: usize,
-    page_cache: HashMap<Uuid, PageComponent>,
-    /// Maps (title, language_code) -> UUID for quick cache lookups
-    page_identifier_index: HashMap<(String, String), Uuid>,
+    /// Identifies the pane showing the viewer, results of requests are sent back to it
+    pane: PaneId,
+    cache: SharedPageCache,
 
     is_processing: bool,
+    /// The request loading the next page, shown on the processing screen
+    loading: Option<RequestHandle>,
     changing_page_language_popup: Option<PageLanguageSelectionComponent>,
+    /// Count and keys of an unfinished binding, shown in the status bar of the current page
+    pending_keys: Option<String>,
+    /// Only created when prefetching is enabled
+    prefetcher: Option<Prefetcher>,
+    bookmarks: Bookmarks,
 
     config: Arc<Config>,
     theme: Arc<Theme>,
@@ -42,182 +63,265 @@ pub struct PageViewer {
 }
 
 impl PageViewer {
-    fn cache_path() -> std::path::PathBuf {
-        let mut path = directories::ProjectDirs::from("com", "github", "wiki-tui")
-            .unwrap()
-            .cache_dir()
-            .to_path_buf();
-        path.push("page_cache.json");
-        path
-    }
-
-    fn load_cache(&mut self) {
-        let path = Self::cache_path();
-        if !path.exists() {
-            debug!("no cache file found at {:?}", path);
-            return;
+    /// Creates the viewer of a pane, sharing the cache with the other panes
+    pub fn new(pane: PaneId, cache: SharedPageCache) -> Self {
+        Self {
+            pane,
+            cache,
+            ..Self::default()
         }
-
-        let file = match std::fs::File::open(&path) {
-            Ok(file) => file,
-            Err(e) => {
-                error!("failed to open cache file at {:?}: {}", path, e);
-                return;
-            }
-        };
-
-        let reader = std::io::BufReader::new(file);
-        match serde_json::from_reader(reader) {
-            Ok(cache) => {
-                debug!("successfully loaded cache from {:?}", path);
-                self.page_cache = cache;
-                self.rebuild_identifier_index();
-            }
-            Err(e) => {
-                error!("failed to deserialize cache from {:?}: {}", path, e);
-            }
-        };
     }
 
-    fn rebuild_identifier_index(&mut self) {
-        self.page_identifier_index.clear();
-        for (uuid, page_component) in &self.page_cache {
-            let key = (
-                page_component.page.title.clone(),
-                page_component.page.language.code().to_string(),
-            );
-            self.page_identifier_index.insert(key, *uuid);
-        }
-        debug!(
-            "rebuilt identifier index with {} entries",
-            self.page_identifier_index.len()
-        );
-    }
-
-    fn save_cache(&self) {
-        let path = Self::cache_path();
-        let file = match std::fs::File::create(&path) {
-            Ok(file) => file,
-            Err(e) => {
-                error!("failed to create cache file at {:?}: {}", path, e);
-                return;
-            }
-        };
-
-        let writer = std::io::BufWriter::new(file);
-        match serde_json::to_writer(writer, &self.page_cache) {
-            Ok(_) => debug!("successfully saved cache to {:?}", path),
-            Err(e) => error!("failed to serialize and save cache to {:?}: {}", path, e),
-        }
+    pub fn pane(&self) -> PaneId {
+        self.pane
     }
 
     /// Syncs all currently active pages back to the page_cache, then saves to disk
     pub fn sync_and_save_cache(&mut self) {
+        self.sync_cache();
+        self.cache.lock().unwrap().save();
+    }
+
+    /// Copies the open pages into the cache, e.g. before a pane of the split view is closed
+    pub fn sync_cache(&mut self) {
         debug!("syncing {} active pages to cache", self.page.len());
-        for page_component in &self.page {
-            let key = (
-                page_component.page.title.clone(),
-                page_component.page.language.code().to_string(),
-            );
-            self.page_cache
-                .insert(page_component.page.uuid, page_component.clone());
-            self.page_identifier_index
-                .insert(key, page_component.page.uuid);
+        if let Some(page) = self.current_page_mut() {
+            page.close();
+        }
+        let mut cache = self.cache.lock().unwrap();
+        for page_component in self.page.iter().filter(|x| !x.page.is_old_revision) {
+            cache.insert(page_component.clone());
         }
-        self.save_cache();
     }
 
-    /// Check if a page is already cached by its identifier
+    /// Check if a page is already cached by its identifier. Only pages in the variant the
+    /// language is converted to count
     pub fn get_cached_page(&self, title: &str, language: Language) -> Option<Page> {
-        let key = (title.to_string(), language.code().to_string());
+        let variant = self.config.api.variant(language).map(|x| x.to_string());
+        let key = (title.to_string(), language.code().to_string(), variant);
         debug!(
-            "cache lookup for: title='{}', language='{}'",
+            "cache lookup for: title='{}', language='{}', variant='{:?}'",
             title,
-            language.code()
+            language.code(),
+            key.2
         );
-        let uuid = self.page_identifier_index.get(&key)?;
-        debug!("found uuid in index: {}", uuid);
-        let page_component = self.page_cache.get(uuid)?;
-        debug!("found page component in cache");
-        Some(page_component.page.clone())
+        self.cache.lock().unwrap().get(&key)
+    }
+
+    /// Prefetches the links of the current page that aren't cached yet
+    fn prefetch(&mut self) {
+        if self.prefetcher.is_none() {
+            return;
+        }
+
+        let links = match self
+            .current_page_mut()
+            .and_then(|page| page.prefetch_links())
+        {
+            Some(links) => links,
+            None => return,
+        };
+        let links = links
+            .into_iter()
+            .filter(|link| {
+                self.get_cached_page(&link.page, link.language).is_none()
+                    && self.get_cached_page(&link.title, link.language).is_none()
+            })
+            .collect();
+
+        if let Some(ref prefetcher) = self.prefetcher {
+            prefetcher.prefetch(links);
+        }
     }
 
     fn current_page_mut(&mut self) -> Option<&mut PageComponent> {
         self.page.get_mut(self.page_n)
     }
 
+    pub fn set_pending_keys(&mut self, keys: Option<String>) {
+        self.pending_keys = keys;
+    }
+
     pub fn current_page(&self) -> Option<&PageComponent> {
         self.page.get(self.page_n)
     }
 
-    fn display_page(&mut self, page: Page) {
+    fn display_page(&mut self, page: Page, focus: Option<PageFocus>) {
+        if let Some(current) = self.current_page_mut() {
+            current.close();
+        }
         self.page_n = self.page.len();
         debug!(
             "display_page called for '{}' with uuid {}",
             page.title, page.uuid
         );
 
-        // First try to find by UUID (exact match)
-        if let Some(mut cached_page) = self.page_cache.get(&page.uuid).cloned() {
-            debug!("found page in cache by uuid, using cached version");
-            cached_page.rebuild(self.config.clone(), self.theme.clone());
-            self.page.push(cached_page);
+        if page.is_old_revision {
+            // old revisions are read-only snapshots, keep them out of the cache so they don't
+            // replace the current version of the page
+            debug!("page is an old revision, skipping the cache");
+            let new_page = PageComponent::new(page, self.config.clone(), self.theme.clone());
+            self.page.push(new_page);
         } else {
-            // UUID not found, check if we have this page by (title, language)
-            let key = (page.title.clone(), page.language.code().to_string());
-
-            if let Some(&existing_uuid) = self.page_identifier_index.get(&key) {
-                // We have this page cached, but with a different UUID
-                debug!(
-                    "found existing page in index with different uuid {}, updating uuid to {}",
-                    existing_uuid, page.uuid
-                );
-
-                // Remove the old UUID entry and add with new UUID
-                if let Some(mut existing_page) = self.page_cache.remove(&existing_uuid) {
-                    // Update the page data with the new fetch (in case content changed)
-                    existing_page.page = page.clone();
-                    existing_page.rebuild(self.config.clone(), self.theme.clone());
-
-                    // Store with new UUID and update index
-                    self.page_cache.insert(page.uuid, existing_page.clone());
-                    self.page_identifier_index.insert(key, page.uuid);
-                    self.page.push(existing_page);
-                } else {
-                    // Index pointed to non-existent UUID, treat as new page
-                    debug!("index pointed to non-existent uuid, creating new page");
-                    let new_page =
-                        PageComponent::new(page.clone(), self.config.clone(), self.theme.clone());
-                    self.page_cache.insert(page.uuid, new_page.clone());
-                    self.page_identifier_index.insert(key, page.uuid);
-                    self.page.push(new_page);
-                }
-            } else {
-                // Truly new page, not in index at all
-                debug!("page not in cache or index, creating new PageComponent");
-                let new_page =
-                    PageComponent::new(page.clone(), self.config.clone(), self.theme.clone());
-                debug!("adding page to cache and index with key: {:?}", key);
-                self.page_cache.insert(new_page.page.uuid, new_page.clone());
-                self.page_identifier_index.insert(key, new_page.page.uuid);
-                self.page.push(new_page);
-            }
-            self.save_cache();
+            let page_component =
+                self.cache
+                    .lock()
+                    .unwrap()
+                    .open(page, self.config.clone(), self.theme.clone());
+            self.page.push(page_component);
         }
 
         if self.changing_page_language_popup.is_some() {
             self.changing_page_language_popup = None;
         }
 
+        if let (Some(focus), Some(page)) = (focus, self.current_page_mut()) {
+            match focus {
+                PageFocus::Header(anchor) => page.focus_header(anchor),
+                PageFocus::LinkTo(title) => page.focus_link_to(title),
+            }
+        }
+
         // always disable the processing screen when displaying a page
         self.is_processing = false;
+        self.loading = None;
+
+        self.prefetch();
     }
 
     fn pop(&mut self) {
-        self.page.pop();
+        // the reading position of the closed page is kept in the cache
+        if let Some(mut page_component) = self.page.pop() {
+            page_component.close();
+            if !page_component.page.is_old_revision {
+                self.cache.lock().unwrap().insert(page_component);
+            }
+        }
         self.page_n = self.page_n.saturating_sub(1);
     }
 
+    /// Fetches the current version of the page to compare it with the version that is displayed.
+    /// Old revisions are compared in the history instead
+    fn show_changes(&self) -> ActionResult {
+        match self.current_page() {
+            Some(page) if !page.page.is_old_revision => {
+                Action::LoadChanges(Box::new(page.page.clone())).into()
+            }
+            Some(_) => Action::PopupMessage(
+                "Information".to_string(),
+                "Use the history to compare old revisions".to_string(),
+            )
+            .into(),
+            None => ActionResult::Ignored,
+        }
+    }
+
+    /// Writes the current page to the file, reporting the result in a popup
+    fn export(&self, format: ExportFormat, path: Option<PathBuf>) -> ActionResult {
+        let page = match self.current_page() {
+            Some(page) => &page.page,
+            None => return Action::PopupError("There is no page to export".to_string()).into(),
+        };
+
+        let path = path.unwrap_or_else(|| {
+            // titles can contain slashes, e.g. "AC/DC"
+            PathBuf::from(format!(
+                "{}.{}",
+                page.title.replace('/', "_"),
+                format.extension()
+            ))
+        });
+
+        match std::fs::write(&path, export_page(page, format)) {
+            Ok(_) => {
+                debug!("exported '{}' to {:?}", page.title, path);
+                Action::PopupMessage(
+                    "Information".to_string(),
+                    format!("Exported '{}' to '{}'", page.title, path.display()),
+                )
+                .into()
+            }
+            Err(error) => {
+                error!("failed exporting '{}' to {:?}: {}", page.title, path, error);
+                Action::PopupError(format!(
+                    "Unable to export '{}' to '{}': {}",
+                    page.title,
+                    path.display(),
+                    error
+                ))
+                .into()
+            }
+        }
+    }
+
+    fn cancel_loading(&mut self) -> ActionResult {
+        let loading = match self.loading.take() {
+            Some(loading) => loading,
+            None => return ActionResult::Ignored,
+        };
+
+        debug!("cancelled the request '{}'", loading.description());
+        loading.cancel();
+        self.is_processing = false;
+
+        // without a page, there is nothing to go back to
+        if self.current_page().is_none() {
+            return Action::SwitchContextSearch.into();
+        }
+        ActionResult::consumed()
+    }
+
+    /// Returns the viewed pages, the most recently read first. The open pages are newer than
+    /// their cached versions
+    pub fn read_pages(&self) -> Vec<ReadPage> {
+        let cache = self.cache.lock().unwrap();
+        let mut pages: HashMap<Uuid, &PageComponent> =
+            cache.pages().map(|page| (page.page.uuid, page)).collect();
+        for page in self.page.iter().filter(|x| !x.page.is_old_revision) {
+            pages.insert(page.page.uuid, page);
+        }
+
+        let mut pages: Vec<&PageComponent> = pages
+            .into_values()
+            .filter(|page| page.read_at().is_some())
+            .collect();
+        pages.sort_by_key(|page| std::cmp::Reverse(page.read_at()));
+
+        pages
+            .into_iter()
+            .map(|page| ReadPage {
+                title: page.page.title.clone(),
+                language: page.page.language,
+                endpoint: page.page.endpoint.clone(),
+                progress: page.progress(),
+            })
+            .collect()
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        self.bookmarks.get()
+    }
+
+    fn toggle_bookmark(&mut self) -> ActionResult {
+        let page = match self.current_page() {
+            Some(page) => &page.page,
+            None => return Action::PopupError("There is no page to bookmark".to_string()).into(),
+        };
+
+        let title = page.title.clone();
+        let bookmarked = self.bookmarks.toggle(Bookmark {
+            title: title.clone(),
+            language: page.language,
+            endpoint: page.endpoint.clone(),
+        });
+        let message = if bookmarked {
+            format!("Added '{}' to the bookmarks", title)
+        } else {
+            format!("Removed '{}' from the bookmarks", title)
+        };
+        Action::PopupMessage("Information".to_string(), message).into()
+    }
+
     pub fn get_page_language_selection_popup(&self) -> PageLanguageSelectionComponent {
         let language_links = self
             .current_page()
@@ -236,27 +340,145 @@ impl Component for PageViewer {
     ) -> anyhow::Result<()> {
         self.action_tx = Some(action_tx);
         self.config = config;
+        if self.config.page.prefetch.enabled {
+            self.prefetcher = Some(Prefetcher::new(
+                self.config.clone(),
+                PaneSender::new(self.action_tx.clone().unwrap(), self.pane),
+            ));
+        }
         self.theme = theme;
-        self.load_cache();
+        self.bookmarks = Bookmarks::load();
         Ok(())
     }
-    fn handle_key_events(&mut self, key: crossterm::event::KeyEvent) -> ActionResult {
+
+    fn reconfigure(&mut self, config: Arc<Config>, theme: Arc<Theme>) {
+        // cached pages get the new config when they are displayed again
+        for page in self.page.iter_mut() {
+            page.reconfigure(config.clone(), theme.clone());
+        }
+        if let Some(ref mut prefetcher) = self.prefetcher {
+            prefetcher.reconfigure(config.clone());
+        }
+        self.config = config;
+        self.theme = theme;
+    }
+    fn is_typing(&self) -> bool {
+        // the labels of the hint mode can contain any key
+        self.current_page().is_some_and(|page| page.is_hinting())
+    }
+
+    fn handle_mouse_events(&mut self, mouse: crossterm::event::MouseEvent) -> ActionResult {
+        if self.is_processing {
+            return ActionResult::Ignored;
+        }
+
+        let result = match self.current_page_mut() {
+            Some(page) => page.handle_mouse_events(mouse),
+            None => return ActionResult::Ignored,
+        };
+        // clicking a link selects it, which can change what's prefetched
+        if result.is_consumed() {
+            self.prefetch();
+        }
+        result
+    }
+
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[Binding],
+    ) -> ActionResult {
+        if self.is_processing {
+            if self.loading.is_some()
+                && self
+                    .config
+                    .bindings
+                    .global
+                    .cancel_request
+                    .matches_event(key, pending)
+            {
+                return Action::PageViewer(PageViewerAction::CancelLoading).into();
+            }
+            return ActionResult::Ignored;
+        }
+
+        // the labels of the hint mode can contain any key, e.g. the one for popping the page
+        if let Some(page) = self.current_page_mut().filter(|page| page.is_hinting()) {
+            return page.handle_key_events(key, pending);
+        }
+
         if self
             .config
             .bindings
             .page
             .toggle_page_language_selection
-            .matches_event(key)
+            .matches_event(key, pending)
         {
             return Action::ShowPageLanguageSelection.into();
         }
 
-        if self.config.bindings.page.pop_page.matches_event(key) {
+        if self
+            .config
+            .bindings
+            .page
+            .show_history
+            .matches_event(key, pending)
+        {
+            return Action::ShowPageHistory.into();
+        }
+
+        if self
+            .config
+            .bindings
+            .page
+            .show_categories
+            .matches_event(key, pending)
+        {
+            return Action::ShowPageCategories.into();
+        }
+
+        if self
+            .config
+            .bindings
+            .page
+            .show_backlinks
+            .matches_event(key, pending)
+        {
+            return Action::ShowPageBacklinks.into();
+        }
+
+        if self
+            .config
+            .bindings
+            .page
+            .show_changes
+            .matches_event(key, pending)
+        {
+            return Action::PageViewer(PageViewerAction::ShowChanges).into();
+        }
+
+        if self
+            .config
+            .bindings
+            .page
+            .pop_page
+            .matches_event(key, pending)
+        {
             return Action::PageViewer(PageViewerAction::PopPage).into();
         }
 
+        if self
+            .config
+            .bindings
+            .page
+            .toggle_bookmark
+            .matches_event(key, pending)
+        {
+            return Action::PageViewer(PageViewerAction::ToggleBookmark).into();
+        }
+
         if let Some(page) = self.current_page_mut() {
-            return page.handle_key_events(key);
+            return page.handle_key_events(key, pending);
         }
 
         ActionResult::Ignored
@@ -264,71 +486,57 @@ impl Component for PageViewer {
 
     fn update(&mut self, action: Action) -> ActionResult {
         match action {
-            Action::TryLoadPage(title, language, endpoint) => {
+            Action::TryLoadPage(title, language, endpoint, focus) => {
                 if let Some(cached_page) = self.get_cached_page(&title, language) {
                     debug!("cache hit for page '{}' - loading instantly", title);
-                    self.action_tx
-                        .as_ref()
-                        .unwrap()
-                        .send(Action::SwitchContextPage)
-                        .unwrap();
-                    self.display_page(cached_page);
-                    return ActionResult::consumed();
+                    // the cached page is newer than the one still loading
+                    if let Some(loading) = self.loading.take() {
+                        loading.cancel();
+                    }
+                    self.display_page(cached_page, focus);
+                    return Action::SwitchContextPage.into();
                 } else {
                     debug!("cache miss for page '{}' - fetching from API", title);
-                    // Cache miss - fetch from API
-                    let page_request = wiki_api::page::Page::builder()
-                        .page(title.clone())
-                        .properties(vec![
-                            Property::Text,
-                            Property::Sections,
-                            Property::LangLinks,
-                        ])
-                        .endpoint(endpoint)
-                        .language(language)
-                        .redirects(self.config.api.page_redirects);
-
-                    let tx = self.action_tx.clone().unwrap();
-                    tokio::spawn(async move {
-                        tx.send(Action::SwitchContextPage).unwrap();
-                        tx.send(Action::EnterProcessing).unwrap();
-
-                        match page_request.fetch().await {
-                            Ok(page) => tx
-                                .send(Action::PageViewer(
-                                    crate::action::PageViewerAction::DisplayPage(page),
-                                ))
-                                .unwrap(),
-                            Err(error) => {
-                                let error_msg =
-                                    format!("Unable to fetch the page '{}': {}", title, error);
-                                tracing::error!("{}", error_msg);
-                                tx.send(Action::PageViewer(
-                                    crate::action::PageViewerAction::ExitLoading,
-                                ))
-                                .unwrap();
-                                tx.send(Action::PopupError(error_msg)).unwrap();
-                            }
-                        };
-
-                        tx.send(Action::EnterNormal).unwrap();
-                    });
-                    return ActionResult::consumed();
+                    return Action::LoadPage(title, language, endpoint, focus).into();
                 }
             }
             Action::PageViewer(page_viewer_action) => match page_viewer_action {
-                PageViewerAction::DisplayPage(page) => self.display_page(page),
+                PageViewerAction::DisplayPage(page, focus) => self.display_page(*page, focus),
                 PageViewerAction::PopPage => self.pop(),
-                PageViewerAction::ExitLoading => self.is_processing = false,
+                PageViewerAction::StartLoading(request) => {
+                    self.is_processing = true;
+                    self.loading = Some(request);
+                }
+                PageViewerAction::CancelLoading => return self.cancel_loading(),
+                PageViewerAction::ExitLoading(request) => {
+                    // a newer request could be loading already
+                    if self.loading.as_ref() == Some(&request) {
+                        self.is_processing = false;
+                        self.loading = None;
+                    }
+                }
                 PageViewerAction::SaveCache => self.sync_and_save_cache(),
+                PageViewerAction::ShowChanges => return self.show_changes(),
+                PageViewerAction::CachePage(page, requested_title) => self
+                    .cache
+                    .lock()
+                    .unwrap()
+                    .insert_prefetched(*page, requested_title),
+                PageViewerAction::Export(format, path) => return self.export(format, path),
+                PageViewerAction::ToggleBookmark => return self.toggle_bookmark(),
             },
             Action::EnterProcessing => self.is_processing = true,
             Action::EnterNormal => self.is_processing = false,
             _ => {
-                if let Some(page) = self.current_page_mut() {
-                    return page.update(action);
+                let result = match self.current_page_mut() {
+                    Some(page) => page.update(action),
+                    None => return ActionResult::Ignored,
+                };
+                // scrolling and selecting links can change what's prefetched
+                if result.is_consumed() {
+                    self.prefetch();
                 }
-                return ActionResult::Ignored;
+                return result;
             }
         }
         ActionResult::consumed()
@@ -344,9 +552,31 @@ impl Component for PageViewer {
                 ),
                 area,
             );
+            let text = match self.loading {
+                Some(ref loading) => {
+                    let cancel_hint = match self
+                        .config
+                        .bindings
+                        .global
+                        .cancel_request
+                        .bindings()
+                        .first()
+                    {
+                        Some(binding) => format!("Press '{}' to cancel", binding),
+                        None => String::new(),
+                    };
+                    Text::from(vec![
+                        Line::from(loading.description().to_string()),
+                        Line::from(loading.status()).fg(self.theme.inactive_fg),
+                        Line::default(),
+                        Line::from(cancel_hint).fg(self.theme.inactive_fg),
+                    ])
+                }
+                None => Text::from("Processing"),
+            };
             f.render_widget(
                 self.theme
-                    .default_paragraph("Processing")
+                    .default_paragraph(text)
                     .alignment(Alignment::Center),
                 centered_rect(area, 100, 50),
             );
@@ -363,7 +593,9 @@ impl Component for PageViewer {
             return;
         }
 
+        let pending_keys = self.pending_keys.clone();
         if let Some(page) = self.current_page_mut() {
+            page.set_pending_keys(pending_keys);
             page.render(f, area);
         }
     }
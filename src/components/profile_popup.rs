@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Clear, HighlightSpacing, List, ListItem},
+};
+use wiki_api::{languages::Language, Endpoint};
+
+use crate::{
+    action::{Action, ActionPacket, ActionResult, SearchAction},
+    config::{Binding, Config, Theme, WikiProfile},
+    terminal::Frame,
+    ui::{centered_rect, StatefulList},
+};
+
+use super::Component;
+
+/// Lists the configured wiki profiles. Selecting a profile switches the site used for searching
+/// and for random pages and the feed
+pub struct ProfileSelectionComponent {
+    list: StatefulList<WikiProfile>,
+    current_endpoint: Endpoint,
+    language: Language,
+
+    config: Arc<Config>,
+    theme: Arc<Theme>,
+}
+
+impl ProfileSelectionComponent {
+    pub fn new(
+        current_endpoint: Endpoint,
+        language: Language,
+        config: Arc<Config>,
+        theme: Arc<Theme>,
+    ) -> Self {
+        Self {
+            list: StatefulList::with_items(config.api.profiles.clone()),
+            current_endpoint,
+            language,
+
+            config,
+            theme,
+        }
+    }
+
+    fn is_current(&self, profile: &WikiProfile) -> bool {
+        profile.endpoint.for_language(self.language) == self.current_endpoint
+    }
+
+    fn submit(&self) -> ActionResult {
+        let profile = match self.list.selected() {
+            Some(profile) => profile,
+            None => return ActionResult::Ignored,
+        };
+
+        ActionPacket::single(Action::SwitchContextSearch)
+            .action(Action::PopPopup)
+            .action(Action::PopupMessage(
+                "Information".to_string(),
+                format!(
+                    "Switched to '{}' ({})",
+                    profile.name,
+                    profile.endpoint.site_name()
+                ),
+            ))
+            .action(Action::Search(SearchAction::ChangeProfile(profile.clone())))
+            .into()
+    }
+
+    fn render_profile(&self, profile: &WikiProfile) -> ListItem<'static> {
+        let mut header = vec![
+            Span::raw(profile.name.clone()).fg(self.theme.search_title_fg),
+            Span::raw(format!(" | {}", profile.endpoint.site_name())).fg(self.theme.fg),
+        ];
+        if self.is_current(profile) {
+            header.push(Span::raw(" | current").add_modifier(Modifier::BOLD));
+        }
+
+        let url = match profile.endpoint.url_template() {
+            Some(template) => template.to_string(),
+            None => profile.endpoint.api_url().to_string(),
+        };
+
+        ListItem::new(Text::from(vec![
+            Line::from(header),
+            Line::from(url).fg(self.theme.inactive_fg),
+        ]))
+    }
+}
+
+impl Component for ProfileSelectionComponent {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[Binding],
+    ) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .submit
+            .matches_event(key, pending)
+        {
+            return self.submit();
+        }
+
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
+            || self
+                .config
+                .bindings
+                .global
+                .toggle_profile_selection
+                .matches_event(key, pending)
+        {
+            return Action::PopPopup.into();
+        }
+
+        ActionResult::Ignored
+    }
+
+    fn update(&mut self, action: Action) -> ActionResult {
+        match action {
+            Action::ScrollUp(n) => {
+                for _ in 0..n {
+                    self.list.previous()
+                }
+                ActionResult::consumed()
+            }
+            Action::ScrollDown(n) => {
+                for _ in 0..n {
+                    self.list.next()
+                }
+                ActionResult::consumed()
+            }
+            Action::UnselectScroll => {
+                self.list.unselect();
+                ActionResult::consumed()
+            }
+            _ => ActionResult::Ignored,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let area = centered_rect(area, 50, 50);
+        f.render_widget(Clear, area);
+
+        let block = self
+            .theme
+            .default_block()
+            .title("Switch Wiki")
+            .style(Style::default().bg(self.theme.bg));
+
+        let items: Vec<ListItem> = self
+            .list
+            .get_items()
+            .iter()
+            .map(|profile| self.render_profile(profile))
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_symbol("| ")
+            .repeat_highlight_symbol(true)
+            .highlight_spacing(HighlightSpacing::Always)
+            .highlight_style(
+                Style::default()
+                    .fg(self.theme.selected_fg)
+                    .bg(self.theme.selected_bg)
+                    .add_modifier(Modifier::ITALIC),
+            );
+        f.render_stateful_widget(list, area, self.list.get_state_mut());
+    }
+}
@@ -18,12 +18,15 @@ use wiki_api::{
 
 use crate::{
     action::{Action, ActionPacket, ActionResult, SearchAction},
-    config::{Config, Theme},
+    bookmarks::Bookmark,
+    config::{Binding, Config, Theme, WikiProfile},
+    page_loader::PageLoader,
+    request::{RequestHandle, RequestTracker},
     terminal::Frame,
     ui::{centered_rect, ScrollBehaviour, StatefulList},
 };
 
-use super::Component;
+use super::{dashboard::DashboardComponent, page_viewer::ReadPage, Component};
 
 #[derive(Default, Debug, PartialEq, Eq, Clone)]
 pub enum Mode {
@@ -42,9 +45,18 @@ pub struct SearchComponent {
     pub language: Option<Language>,
 
     search_results: StatefulList<SearchResult>,
+    /// Shown before anything was searched
+    dashboard: DashboardComponent,
     search_info: Option<SearchInfo>,
     continue_search: Option<SearchContinue>,
 
+    /// Only the newest search is kept, starting a search cancels the running one
+    requests: RequestTracker,
+    /// The running search, shown while searching
+    searching: Option<RequestHandle>,
+    /// Count and keys of an unfinished binding, shown in the status bar
+    pending_keys: Option<String>,
+
     config: Arc<Config>,
     theme: Arc<Theme>,
 
@@ -60,9 +72,14 @@ impl Default for SearchComponent {
 
             search_results: StatefulList::with_items(Vec::new())
                 .scroll_behavior(ScrollBehaviour::StickToEnds),
+            dashboard: DashboardComponent::default(),
             search_info: None,
             continue_search: None,
 
+            requests: RequestTracker::default(),
+            searching: None,
+            pending_keys: None,
+
             config: Arc::new(Config::default()),
             theme: Arc::new(Theme::default()),
 
@@ -72,13 +89,23 @@ impl Default for SearchComponent {
 }
 
 impl SearchComponent {
+    pub fn set_pending_keys(&mut self, keys: Option<String>) {
+        self.pending_keys = keys;
+    }
+
+    /// Updates the dashboard with the viewed and bookmarked pages
+    pub fn refresh_dashboard(&mut self, read_pages: Vec<ReadPage>, bookmarks: &[Bookmark]) {
+        self.dashboard.refresh(read_pages, bookmarks);
+    }
+
     fn build_search(&self, query: String) -> Result<SearchRequest> {
         let api_config = &self.config.api;
 
-        let endpoint = self.endpoint.clone().unwrap_or(api_config.endpoint.clone());
+        let endpoint = self.current_endpoint();
         let language = self.language.unwrap_or(api_config.language);
 
-        Ok(ApiSearch::builder()
+        let mut search = ApiSearch::builder()
+            .client(api_config.client.clone())
             .query(query)
             .endpoint(endpoint)
             .language(language)
@@ -87,33 +114,51 @@ impl SearchComponent {
             .search_type(api_config.search_type.clone())
             .info(api_config.search_info.clone())
             .rewrites(api_config.search_rewrites)
-            .sort_order(api_config.search_sort_order.clone()))
+            .sort_order(api_config.search_sort_order.clone());
+        if let Some(variant) = api_config.variant(language) {
+            search = search.variant(variant);
+        }
+        Ok(search)
     }
 
     fn start_search(&mut self, query: String) -> ActionResult {
+        self.dashboard.add_search(&query);
+
         let tx = self.action_tx.clone().unwrap();
+        let retry = Action::Search(SearchAction::StartSearch(query.clone()));
+        let handle = self.requests.start(format!("Searching for '{}'", query));
         let search_request = match self.build_search(query) {
-            Ok(search_request) => search_request,
+            Ok(search_request) => search_request.client(handle.client(&self.config.api.client)),
             Err(error) => {
                 error!("Unable to build the search request: {:?}", error);
                 return ActionResult::consumed();
             }
         };
+        // the mode changes before the search can finish, so its results are never cleared
+        self.clear_search_results();
+        self.mode = Mode::Searching;
+        self.searching = Some(handle.clone());
+
+        let requests = self.requests.clone();
         tokio::spawn(async move {
-            tx.send(Action::Search(SearchAction::ChangeMode(Mode::Searching)))
-                .unwrap();
-            tx.send(Action::Search(SearchAction::ClearSearchResults))
-                .unwrap();
-            match search_request.search().await {
-                Ok(search) => tx
+            let result = handle.run(search_request.search()).await;
+            requests.finish(&handle);
+
+            match result {
+                // a newer search or the user cancelled this one, both changed the mode already
+                None => {}
+                Some(Ok(search)) => tx
                     .send(Action::Search(SearchAction::FinshSearch(search)))
                     .unwrap(),
-                Err(error) => {
-                    let error = error.context("Unable to execute the search");
+                Some(Err(error)) => {
                     tx.send(Action::Search(SearchAction::ChangeMode(Mode::NoSearch)))
                         .unwrap();
-                    tx.send(Action::PopupError(format!("{:?}", error))).unwrap();
-                    error!("{:?}", error);
+                    tx.send(PageLoader::request_failed(
+                        "Unable to execute the search",
+                        &error,
+                        retry,
+                    ))
+                    .unwrap();
                 }
             };
         });
@@ -165,24 +210,40 @@ impl SearchComponent {
 
         let code = self.continue_search.as_ref().unwrap();
         let tx = self.action_tx.clone().unwrap();
-        let search_request = ApiSearch::builder()
+        let handle = self
+            .requests
+            .start(format!("Continuing the search for '{}'", code.query));
+        let mut search_request = ApiSearch::builder()
+            .client(handle.client(&self.config.api.client))
             .query(code.query.clone())
             .endpoint(code.endpoint.clone())
             .language(code.language)
             .offset(code.offset);
+        if let Some(variant) = self.config.api.variant(code.language) {
+            search_request = search_request.variant(variant);
+        }
+        self.mode = Mode::Searching;
+        self.searching = Some(handle.clone());
+
+        let requests = self.requests.clone();
         tokio::spawn(async move {
-            tx.send(Action::Search(SearchAction::ChangeMode(Mode::Searching)))
-                .unwrap();
-            match search_request.search().await {
-                Ok(search) => tx
+            let result = handle.run(search_request.search()).await;
+            requests.finish(&handle);
+
+            match result {
+                None => {}
+                Some(Ok(search)) => tx
                     .send(Action::Search(SearchAction::FinshSearch(search)))
                     .unwrap(),
-                Err(error) => {
-                    let error = error.context("Unable to continue the search");
+                Some(Err(error)) => {
                     tx.send(Action::Search(SearchAction::ChangeMode(Mode::NoSearch)))
                         .unwrap();
-                    tx.send(Action::PopupError(error.to_string())).unwrap();
-                    error!("{:?}", error)
+                    tx.send(PageLoader::request_failed(
+                        "Unable to continue the search",
+                        &error,
+                        Action::Search(SearchAction::ContinueSearch),
+                    ))
+                    .unwrap();
                 }
             };
         });
@@ -209,18 +270,56 @@ impl SearchComponent {
     }
 
     fn change_mode(&mut self, mode: Mode) -> ActionResult {
+        if mode != Mode::Searching {
+            self.searching = None;
+        }
         self.mode = mode;
         ActionResult::consumed()
     }
 
+    fn cancel_search(&mut self) -> ActionResult {
+        if !self.requests.cancel() {
+            return ActionResult::Ignored;
+        }
+
+        // a continued search keeps the results it's continuing
+        let mode = if self.search_results.get_items().is_empty() {
+            Mode::NoSearch
+        } else {
+            Mode::FinishedSearch
+        };
+        self.change_mode(mode)
+    }
+
+    fn current_endpoint(&self) -> Endpoint {
+        self.endpoint
+            .clone()
+            .unwrap_or(self.config.api.endpoint.clone())
+    }
+
     fn change_language(&mut self, lang: Language) -> ActionResult {
-        self.endpoint = Some(
-            // HACK: we need a way to get the API endpoint from the language
-            Endpoint::parse(&format!("https://{}.wikipedia.org/w/api.php", lang.code())).unwrap(),
-        );
+        let endpoint = self.current_endpoint();
+        if !endpoint.supports_languages() {
+            return Action::PopupMessage(
+                "Information".to_string(),
+                format!(
+                    "'{}' has no other language editions, the language was not changed",
+                    endpoint.site_name()
+                ),
+            )
+            .into();
+        }
+
+        self.endpoint = Some(endpoint.for_language(lang));
         self.language = Some(lang);
         ActionResult::consumed()
     }
+
+    fn change_profile(&mut self, profile: WikiProfile) -> ActionResult {
+        let language = self.language.unwrap_or(self.config.api.language);
+        self.endpoint = Some(profile.endpoint.for_language(language));
+        ActionResult::consumed()
+    }
 }
 
 impl Component for SearchComponent {
@@ -230,15 +329,34 @@ impl Component for SearchComponent {
         config: Arc<Config>,
         theme: Arc<Theme>,
     ) -> anyhow::Result<()> {
+        self.dashboard
+            .init(sender.clone(), config.clone(), theme.clone())?;
         self.action_tx = Some(sender);
         self.config = config;
         self.theme = theme;
         Ok(())
     }
 
-    fn handle_key_events(&mut self, key: KeyEvent) -> ActionResult {
+    fn reconfigure(&mut self, config: Arc<Config>, theme: Arc<Theme>) {
+        self.dashboard.reconfigure(config.clone(), theme.clone());
+        self.config = config;
+        self.theme = theme;
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent, pending: &[Binding]) -> ActionResult {
         match self.mode {
+            Mode::Searching
+                if self
+                    .config
+                    .bindings
+                    .global
+                    .cancel_request
+                    .matches_event(key, pending) =>
+            {
+                Action::Search(SearchAction::CancelSearch).into()
+            }
             Mode::Searching => ActionResult::Ignored,
+            Mode::NoSearch => self.dashboard.handle_key_events(key, pending),
             Mode::Suggestion => {
                 match key.code {
                     KeyCode::Char('y') => {
@@ -261,7 +379,12 @@ impl Component for SearchComponent {
             }
             Mode::FinishedSearch => match key.code {
                 _ if self.search_results.is_selected()
-                    && self.config.bindings.global.submit.matches_event(key) =>
+                    && self
+                        .config
+                        .bindings
+                        .global
+                        .submit
+                        .matches_event(key, pending) =>
                 {
                     Action::Search(SearchAction::OpenSearchResult).into()
                 }
@@ -270,13 +393,12 @@ impl Component for SearchComponent {
                     .bindings
                     .search
                     .continue_search
-                    .matches_event(key) =>
+                    .matches_event(key, pending) =>
                 {
                     Action::Search(SearchAction::ContinueSearch).into()
                 }
                 _ => ActionResult::Ignored,
             },
-            _ => ActionResult::Ignored,
         }
     }
 
@@ -286,12 +408,25 @@ impl Component for SearchComponent {
                 SearchAction::StartSearch(query) => self.start_search(query),
                 SearchAction::FinshSearch(search) => self.finish_search(search),
                 SearchAction::ContinueSearch => self.continue_search(),
+                SearchAction::CancelSearch => self.cancel_search(),
                 SearchAction::ClearSearchResults => self.clear_search_results(),
                 SearchAction::OpenSearchResult => self.open_selected_result(),
                 SearchAction::ChangeMode(mode) => self.change_mode(mode),
                 SearchAction::ChangeLanguage(lang) => self.change_language(lang),
+                SearchAction::ChangeProfile(profile) => self.change_profile(profile),
+                SearchAction::ShowFeatured(feed) => {
+                    self.dashboard.set_featured(feed);
+                    ActionResult::consumed()
+                }
             },
 
+            // before searching, the dashboard is scrolled
+            Action::ScrollUp(_) | Action::ScrollDown(_) | Action::UnselectScroll
+                if self.mode == Mode::NoSearch =>
+            {
+                self.dashboard.update(action)
+            }
+
             Action::ScrollUp(n) => {
                 for _ in 0..n {
                     self.search_results.previous()
@@ -320,9 +455,27 @@ impl Component for SearchComponent {
                     .border_style(Style::default().fg(Color::Yellow)),
                 area,
             );
+            let mut lines = vec![Line::from("Searching. Please wait...")];
+            if let Some(ref searching) = self.searching {
+                lines.push(Line::from(searching.status()).fg(self.theme.inactive_fg));
+                if let Some(binding) = self
+                    .config
+                    .bindings
+                    .global
+                    .cancel_request
+                    .bindings()
+                    .first()
+                {
+                    lines.push(Line::default());
+                    lines.push(
+                        Line::from(format!("Press '{}' to cancel", binding))
+                            .fg(self.theme.inactive_fg),
+                    );
+                }
+            }
             f.render_widget(
                 self.theme
-                    .default_paragraph("Searching. Please wait...")
+                    .default_paragraph(Text::from(lines))
                     .alignment(Alignment::Center),
                 centered_rect(area, 100, 50),
             );
@@ -330,12 +483,7 @@ impl Component for SearchComponent {
         }
 
         if self.mode == Mode::NoSearch {
-            f.render_widget(
-                self.theme
-                    .default_paragraph("Start a search!")
-                    .alignment(Alignment::Center),
-                centered_rect(area, 100, 50),
-            );
+            self.dashboard.render(f, area);
             return;
         }
 
@@ -405,6 +553,16 @@ impl Component for SearchComponent {
             f.render_widget(info, info_area);
         }
 
+        if let Some(ref keys) = self.pending_keys {
+            f.render_widget(
+                Line::from(format!("{} ", keys))
+                    .fg(self.theme.status_bar_fg)
+                    .add_modifier(Modifier::BOLD)
+                    .right_aligned(),
+                info_area,
+            );
+        }
+
         // TODO: Somehow implement list item margin
         let results_list_width = results_area.width.saturating_sub(3); // HACK: subtract 3 for
                                                                        // border and highlight symbol
@@ -446,7 +604,8 @@ impl Component for SearchComponent {
         match event {
             Some(crate::event::Event::Quit) => Action::Quit.into(),
             Some(crate::event::Event::RenderTick) => Action::RenderTick.into(),
-            Some(crate::event::Event::Key(key_event)) => self.handle_key_events(key_event),
+            Some(crate::event::Event::Key(key_event)) => self.handle_key_events(key_event, &[]),
+            Some(crate::event::Event::Mouse(mouse_event)) => self.handle_mouse_events(mouse_event),
             Some(crate::event::Event::Resize(x, y)) => Action::Resize(x, y).into(),
             None => ActionResult::Ignored,
         }
@@ -10,7 +10,7 @@ use tui_input::{backend::crossterm::EventHandler, Input};
 
 use crate::{
     action::{Action, ActionResult, SearchAction},
-    config::{Config, Theme},
+    config::{Binding, Config, Theme},
     terminal::Frame,
     ui::centered_rect,
 };
@@ -52,8 +52,19 @@ impl Component for SearchBarComponent {
         Ok(())
     }
 
-    fn handle_key_events(&mut self, key: KeyEvent) -> ActionResult {
-        if self.config.bindings.global.submit.matches_event(key) {
+    fn reconfigure(&mut self, config: Arc<Config>, theme: Arc<Theme>) {
+        self.config = config;
+        self.theme = theme;
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent, pending: &[Binding]) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .submit
+            .matches_event(key, pending)
+        {
             return Action::SubmitSearchBar.into();
         }
 
@@ -62,7 +73,7 @@ impl Component for SearchBarComponent {
             .bindings
             .global
             .exit_search_bar
-            .matches_event(key)
+            .matches_event(key, pending)
         {
             return Action::ExitSearchBar.into();
         }
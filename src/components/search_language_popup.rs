@@ -3,17 +3,17 @@ use std::sync::Arc;
 use crossterm::event::KeyCode;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style, Stylize},
-    widgets::{Clear, List, ListItem},
+    style::{Modifier, Style},
+    widgets::{Clear, List},
 };
 use tui_input::{backend::crossterm::EventHandler, Input};
-use wiki_api::languages::{Language, LANGUAGES};
+use wiki_api::{languages::Language, sitematrix::languages};
 
 use crate::{
     action::{Action, ActionPacket, ActionResult, SearchAction},
-    config::{Config, Theme},
+    config::{Binding, Config, Theme},
     terminal::Frame,
-    ui::{centered_rect, StatefulList},
+    ui::{centered_rect, filter_languages, LanguageItem, StatefulList},
 };
 
 use super::Component;
@@ -24,7 +24,8 @@ const FOCUS_LIST: u8 = 1;
 pub struct SearchLanguageSelectionComponent {
     input: Input,
     focus: u8,
-    list: StatefulList<Language>,
+    list: StatefulList<LanguageItem<Language>>,
+    languages: Vec<LanguageItem<Language>>,
 
     config: Arc<Config>,
     theme: Arc<Theme>,
@@ -32,9 +33,23 @@ pub struct SearchLanguageSelectionComponent {
 
 impl SearchLanguageSelectionComponent {
     pub fn new(config: Arc<Config>, theme: Arc<Theme>) -> Self {
+        let languages: Vec<LanguageItem<Language>> = languages()
+            .into_iter()
+            .map(|language| {
+                let item = LanguageItem::new(language, language, language.local_name())
+                    .pin(&config.ui.pinned_languages);
+                if language.is_closed() {
+                    item.detail("closed")
+                } else {
+                    item
+                }
+            })
+            .collect();
+
         Self {
             input: Input::default(),
-            list: StatefulList::with_items(Vec::new()),
+            list: StatefulList::with_items(filter_languages("", &languages)),
+            languages,
             focus: 0,
 
             config,
@@ -43,24 +58,28 @@ impl SearchLanguageSelectionComponent {
     }
 
     fn update_list(&mut self) {
-        let input_value = self.input.value();
-        let sorted_languages = LANGUAGES
-            .iter()
-            .filter(|lang| {
-                let lang = lang.name().to_lowercase();
-                let query = input_value.to_lowercase();
-                lang.contains(&query)
-            })
-            .map(|x| x.to_owned())
-            .collect::<Vec<Language>>();
-        self.list = StatefulList::with_items(sorted_languages);
+        self.list = StatefulList::with_items(filter_languages(self.input.value(), &self.languages));
     }
 }
 
 impl Component for SearchLanguageSelectionComponent {
-    fn handle_key_events(&mut self, key: crossterm::event::KeyEvent) -> ActionResult {
-        if self.config.bindings.global.submit.matches_event(key) {
-            if let Some(lang) = self.list.selected() {
+    fn is_typing(&self) -> bool {
+        self.focus == FOCUS_INPUT
+    }
+
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[Binding],
+    ) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .submit
+            .matches_event(key, pending)
+        {
+            if let Some(lang) = self.list.selected().map(|item| item.value) {
                 let mut packet =
                     ActionPacket::single(Action::SwitchContextSearch).action(Action::PopPopup);
 
@@ -72,15 +91,19 @@ impl Component for SearchLanguageSelectionComponent {
                 }
 
                 return packet
-                    .action(Action::Search(SearchAction::ChangeLanguage(
-                        lang.to_owned(),
-                    )))
+                    .action(Action::Search(SearchAction::ChangeLanguage(lang)))
                     .into();
             }
             return ActionResult::Ignored;
         }
 
-        if self.config.bindings.global.pop_popup.matches_event(key) {
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
+        {
             return Action::PopPopup.into();
         }
 
@@ -140,7 +163,7 @@ impl Component for SearchLanguageSelectionComponent {
             .default_block()
             .title("Switch Search Language")
             .style(Style::default().bg(self.theme.bg));
-        let area = centered_rect(area, 25, 60);
+        let area = centered_rect(area, 40, 60);
         f.render_widget(Clear, area);
         f.render_widget(popup_block, area);
 
@@ -178,7 +201,7 @@ impl Component for SearchLanguageSelectionComponent {
             .list
             .get_items()
             .iter()
-            .map(|x| ListItem::new(x.name().to_owned()).fg(self.theme.fg));
+            .map(|x| x.to_list_item(&self.theme));
         let list_widget = List::new(list_items).highlight_style(if self.focus == FOCUS_LIST {
             Style::default()
                 .fg(self.theme.selected_fg)
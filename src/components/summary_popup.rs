@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Clear, Wrap},
+};
+use wiki_api::{languages::Language, summary::Summary, Endpoint};
+
+use crate::{
+    action::{Action, ActionPacket, ActionResult, SummaryAction},
+    config::{Binding, Config, Theme},
+    terminal::Frame,
+    ui::centered_rect,
+};
+
+use super::Component;
+
+/// Shows the description and introduction of a page without opening it. The page can still be
+/// opened from the popup with the `submit` binding
+pub struct SummaryPopupComponent {
+    title: String,
+    language: Language,
+    endpoint: Endpoint,
+
+    summary: Option<Summary>,
+    is_loading: bool,
+    scroll: u16,
+
+    config: Arc<Config>,
+    theme: Arc<Theme>,
+}
+
+impl SummaryPopupComponent {
+    pub fn new(
+        title: String,
+        language: Language,
+        endpoint: Endpoint,
+        config: Arc<Config>,
+        theme: Arc<Theme>,
+    ) -> Self {
+        Self {
+            title,
+            language,
+            endpoint,
+
+            summary: None,
+            is_loading: true,
+            scroll: 0,
+
+            config,
+            theme,
+        }
+    }
+
+    fn open_page(&self) -> ActionResult {
+        ActionPacket::single(Action::PopPopup)
+            .action(Action::TryLoadPage(
+                self.title.clone(),
+                self.language,
+                self.endpoint.clone(),
+                None,
+            ))
+            .into()
+    }
+}
+
+impl Component for SummaryPopupComponent {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        pending: &[Binding],
+    ) -> ActionResult {
+        if self
+            .config
+            .bindings
+            .global
+            .submit
+            .matches_event(key, pending)
+        {
+            return self.open_page();
+        }
+
+        if self
+            .config
+            .bindings
+            .global
+            .pop_popup
+            .matches_event(key, pending)
+            || self
+                .config
+                .bindings
+                .page
+                .show_summary
+                .matches_event(key, pending)
+        {
+            return Action::PopPopup.into();
+        }
+
+        ActionResult::Ignored
+    }
+
+    fn update(&mut self, action: Action) -> ActionResult {
+        match action {
+            Action::Summary(SummaryAction::FinishSummary(summary)) => {
+                self.is_loading = false;
+                self.summary = Some(summary);
+            }
+            Action::Summary(SummaryAction::FailSummary(_)) => self.is_loading = false,
+            Action::ScrollUp(n) => self.scroll = self.scroll.saturating_sub(n),
+            Action::ScrollDown(n) => self.scroll = self.scroll.saturating_add(n),
+            Action::ScrollToTop => self.scroll = 0,
+            _ => return ActionResult::Ignored,
+        }
+        ActionResult::consumed()
+    }
+
+    fn render(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let area = centered_rect(area, 60, 50);
+        f.render_widget(Clear, area);
+
+        let mut block = self
+            .theme
+            .default_block()
+            .title(format!("Summary of '{}'", self.title))
+            .style(Style::default().bg(self.theme.bg));
+        if let Some(binding) = self.config.bindings.global.submit.bindings().first() {
+            block = block
+                .title_bottom(Line::from(format!("<{}> Open the page", binding)).right_aligned());
+        }
+
+        let summary = match self.summary {
+            Some(ref summary) => summary,
+            None => {
+                let message = if self.is_loading {
+                    "Loading the summary..."
+                } else {
+                    "Unable to load the summary"
+                };
+                f.render_widget(
+                    self.theme
+                        .default_paragraph(message)
+                        .alignment(Alignment::Center)
+                        .block(block),
+                    area,
+                );
+                return;
+            }
+        };
+
+        let mut lines = vec![Line::from(summary.title.clone()).add_modifier(Modifier::BOLD)];
+        if let Some(ref description) = summary.description {
+            lines.push(
+                Line::from(description.clone())
+                    .fg(self.theme.inactive_fg)
+                    .add_modifier(Modifier::ITALIC),
+            );
+        }
+        lines.push(Line::default());
+
+        if summary.extract.is_empty() {
+            lines.push(Line::from("This page has no introduction").fg(self.theme.inactive_fg));
+        }
+        for paragraph in summary.extract.lines() {
+            lines.push(Line::from(paragraph.to_string()));
+            lines.push(Line::default());
+        }
+
+        f.render_widget(
+            self.theme
+                .default_paragraph(Text::from(lines))
+                .wrap(Wrap { trim: true })
+                .scroll((self.scroll, 0))
+                .block(block),
+            area,
+        );
+    }
+}
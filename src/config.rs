@@ -8,15 +8,16 @@ use ratatui::{
     widgets::{BorderType, Padding},
 };
 use serde::Deserialize;
-use std::{path::PathBuf, str::FromStr};
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr, time::Duration};
 use tracing::level_filters::LevelFilter;
-use wiki_api::{languages::Language, search, Endpoint};
+use wiki_api::{languages::Language, search, Client, Endpoint};
 
 pub const CACHE_ENV: &str = "WIKI_TUI_CACHE";
 pub const CONFIG_ENV: &str = "WIKI_TUI_CONFIG";
 
 pub const THEME_FILE_NAME: &str = "theme.toml";
 pub const CONFIG_FILE_NAME: &str = "config.toml";
+pub const THEMES_DIR_NAME: &str = "themes";
 
 pub fn project_dir() -> Option<ProjectDirs> {
     ProjectDirs::from("com", "builditluc", "wiki-tui")
@@ -110,6 +111,29 @@ struct UserLoggingConfigInner {
     level: Option<String>,
 }
 
+/// Reads only whether the language registry is enabled. The registry has to be installed before
+/// the rest of the config and the command line arguments are parsed, as both can contain
+/// languages from it
+pub fn load_language_registry_config() -> Result<bool> {
+    let user_config = toml::from_str::<UserLanguageRegistryConfig>(&get_user_config()?)
+        .context("failed loading the user language registry configuration")?;
+
+    Ok(user_config
+        .api
+        .and_then(|api| api.language_registry)
+        .unwrap_or(Config::default().api.language_registry))
+}
+
+#[derive(Deserialize)]
+struct UserLanguageRegistryConfig {
+    api: Option<UserLanguageRegistryConfigInner>,
+}
+
+#[derive(Deserialize)]
+struct UserLanguageRegistryConfigInner {
+    language_registry: Option<bool>,
+}
+
 pub fn load_config() -> Result<Config> {
     let mut default_config = Config::default();
     let user_config = load_user_config().context("failed loading the user config")?;
@@ -127,7 +151,7 @@ pub fn load_config() -> Result<Config> {
     }
 
     if let Some(user_ui_config) = user_config.ui {
-        override_ui_config(&mut default_config.ui, user_ui_config)
+        override_ui_config(&mut default_config.ui, user_ui_config)?
     }
 
     Ok(default_config)
@@ -147,6 +171,7 @@ fn override_page_config(config: &mut PageConfig, user_config: UserPageConfig) {
     }
 
     override_options!(config, user_config::padding);
+    override_options!(config, user_config::hint_characters);
 
     if let Some(user_zen) = user_config.zen_mode {
         override_options!(config, user_zen::{
@@ -157,6 +182,18 @@ fn override_page_config(config: &mut PageConfig, user_config: UserPageConfig) {
             vertical->zen_vertical
         });
     }
+
+    if let Some(user_prefetch) = user_config.prefetch {
+        override_options!(config.prefetch, user_prefetch::{
+            enabled,
+            mode,
+            delay,
+            links,
+
+            max_concurrent,
+            max_rate
+        });
+    }
 }
 
 fn override_bindings_config(config: &mut Keybindings, user_config: UserKeybindingsConfig) {
@@ -169,6 +206,7 @@ fn override_bindings_config(config: &mut Keybindings, user_config: UserKeybindin
             scroll_to_bottom,
 
             pop_popup,
+            cancel_request,
 
             half_down,
             half_up,
@@ -178,19 +216,33 @@ fn override_bindings_config(config: &mut Keybindings, user_config: UserKeybindin
             quit,
             enter_search_bar,
             exit_search_bar,
+            command_line,
 
             switch_context_search,
             switch_context_page,
 
+            split_vertical,
+            split_horizontal,
+            focus_other_pane,
+            close_pane,
+
             toggle_search_language_selection,
+            toggle_profile_selection,
             toggle_logger,
 
+            random_page,
+            show_feed,
+
             help
         });
     }
 
     if let Some(user_search_bindings) = user_config.search {
-        override_options!(config.search, user_search_bindings::continue_search);
+        override_options!(config.search, user_search_bindings::{
+            continue_search,
+            next_dashboard_section,
+            prev_dashboard_section
+        });
     }
 
     if let Some(user_page_bindings) = user_config.page {
@@ -204,36 +256,47 @@ fn override_bindings_config(config: &mut Keybindings, user_config: UserKeybindin
             open_link,
             toggle_page_language_selection,
             toggle_zen_mode,
-            toggle_toc
+            toggle_toc,
+            show_history,
+            show_changes,
+            compare_revision,
+            next_change,
+            prev_change,
+            show_categories,
+            show_backlinks,
+            show_summary,
+            link_hints,
+            next_section,
+            prev_section,
+            next_section_same_level,
+            prev_section_same_level,
+            toggle_fold,
+            fold_all,
+            unfold_all,
+            toggle_bookmark,
+            open_link_in_other_pane,
+            switch_variant
         });
     }
+
+    override_options!(config, user_config::sequence_timeout);
 }
 
-fn override_api_config(config: &mut ApiConfig, user_config: UserApiConfig) -> Result<()> {
-    // we need to manually build the endpoint
-    {
-        let pre_language = match user_config.pre_language {
-            Some(ref language) => language.as_str(),
-            None => "https://",
-        };
-        let language = &user_config.language.unwrap_or(config.language);
-        let post_language = match user_config.post_language {
-            Some(ref language) => language.as_str(),
-            None => ".wikipedia.org/w/api.php",
-        };
+/// Languages that can't be found are still deserialized (see [`Language`]), so the languages of
+/// the config have to be checked
+fn known_language(language: Language) -> Result<Language> {
+    match language {
+        Language::Other(code) => Ok(Language::from_str(code)?),
+        language => Ok(language),
+    }
+}
 
-        config.endpoint = Endpoint::parse(&format!(
-            "{}{}{}",
-            pre_language,
-            language.code(),
-            post_language,
-        ))
-        .context("failed parsing the endpoint url")?;
+fn override_api_config(config: &mut ApiConfig, user_config: UserApiConfig) -> Result<()> {
+    if let Some(language) = user_config.language {
+        config.language = known_language(language).context("failed loading the language")?;
     }
 
     override_options!(config, user_config::{
-        language,
-
         search_limit,
         search_info,
         search_type,
@@ -241,17 +304,98 @@ fn override_api_config(config: &mut ApiConfig, user_config: UserApiConfig) -> Re
         search_rewrites,
         search_sort_order,
 
-        page_redirects
+        page_redirects,
+        language_registry,
+
+        request_timeout,
+        request_retries,
+        proxy,
+        user_agent_contact
     });
 
+    // the default profile can still be changed with the pre_language and post_language options
+    if user_config.pre_language.is_some() || user_config.post_language.is_some() {
+        let pre_language = user_config.pre_language.as_deref().unwrap_or("https://");
+        let post_language = user_config
+            .post_language
+            .as_deref()
+            .unwrap_or(".wikipedia.org/w/api.php");
+
+        let endpoint = Endpoint::parse(&format!(
+            "{}{}{}",
+            pre_language,
+            config.language.code(),
+            post_language,
+        ))
+        .context("failed parsing the endpoint url")?
+        .with_url_template(format!("{}{{lang}}{}", pre_language, post_language))
+        .context("failed parsing the endpoint url")?;
+        config.profiles[0].endpoint = endpoint;
+    }
+
+    for (language, variant) in user_config.variants.unwrap_or_default() {
+        let language: Language = language
+            .parse()
+            .context("failed loading the language variants")?;
+        let variant = language
+            .variant(&variant)
+            .with_context(|| format!("'{}' is not a variant of {}", variant, language.name()))?;
+        config
+            .variants
+            .insert(language.code().to_string(), variant.code.to_string());
+    }
+
+    for (name, user_profile) in user_config.profiles.unwrap_or_default() {
+        let profile = WikiProfile {
+            endpoint: user_profile
+                .endpoint(&name)
+                .with_context(|| format!("failed loading the wiki profile '{}'", name))?,
+            name,
+        };
+        match config.profiles.iter_mut().find(|x| x.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => config.profiles.push(profile),
+        }
+    }
+
+    let profile_name = user_config
+        .profile
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+    let profile = config
+        .profile(&profile_name)
+        .with_context(|| format!("the wiki profile '{}' does not exist", profile_name))?;
+    config.endpoint = profile.endpoint.for_language(config.language);
+
+    config.client = config
+        .build_client()
+        .context("failed creating the http client")?;
+
     Ok(())
 }
 
-fn override_ui_config(config: &mut UiConfig, user_config: UserUiConfig) {
+fn override_ui_config(config: &mut UiConfig, user_config: UserUiConfig) -> Result<()> {
     override_options!(config, user_config::{
         popup_search_language_changed,
-        popup_page_language_changed
+        popup_page_language_changed,
+        mouse
     });
+
+    if let Some(pinned_languages) = user_config.pinned_languages {
+        config.pinned_languages = pinned_languages
+            .into_iter()
+            .map(known_language)
+            .collect::<Result<_>>()
+            .context("failed loading the pinned languages")?;
+    }
+
+    if let Some(user_dashboard) = user_config.dashboard {
+        override_options!(config.dashboard, user_dashboard::{
+            sections,
+            max_entries
+        });
+    }
+
+    Ok(())
 }
 
 fn get_user_config() -> Result<String> {
@@ -271,6 +415,7 @@ fn load_user_config() -> Result<UserConfig> {
     toml::from_str::<UserConfig>(&user_config_str).context("failed parsing the user config")
 }
 
+#[derive(Clone)]
 pub struct Config {
     pub page: PageConfig,
     pub bindings: Keybindings,
@@ -278,6 +423,7 @@ pub struct Config {
     pub ui: UiConfig,
 }
 
+#[derive(Clone)]
 pub struct PageConfig {
     pub toc: TocConfig,
     pub padding: Padding,
@@ -287,6 +433,10 @@ pub struct PageConfig {
 
     pub zen_horizontal: Constraint,
     pub zen_vertical: Constraint,
+
+    pub prefetch: PrefetchConfig,
+    /// Characters used for the labels of the link hints
+    pub hint_characters: String,
 }
 
 bitflags! {
@@ -327,19 +477,43 @@ impl Into<Padding> for PaddingConfig {
     }
 }
 
-#[derive(Deserialize, PartialEq, Eq)]
+#[derive(Deserialize, Clone, PartialEq, Eq)]
 pub enum TocConfigPosition {
     Left,
     Right,
 }
 
-#[derive(Deserialize)]
+/// Which links of a page are prefetched
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefetchMode {
+    /// The selected link, once it stayed selected for the delay
+    Selected,
+    /// The first links of the section at the top of the page
+    Section,
+}
+
+#[derive(Clone)]
+pub struct PrefetchConfig {
+    pub enabled: bool,
+    pub mode: PrefetchMode,
+    /// Time in milliseconds a link or section has to stay selected before it's prefetched
+    pub delay: u64,
+    /// Amount of links prefetched in the section mode
+    pub links: usize,
+
+    pub max_concurrent: usize,
+    /// Maximum average download rate of the prefetcher in KB/s, `0` disables the limit
+    pub max_rate: u64,
+}
+
+#[derive(Deserialize, Clone)]
 pub enum TocConfigTitle {
     Default,
     Article,
     Custom(String),
 }
 
+#[derive(Clone)]
 pub struct TocConfig {
     pub enabled: bool,
     pub width_percentage: u16,
@@ -350,12 +524,28 @@ pub struct TocConfig {
     pub enable_scrolling: bool,
 }
 
-#[derive(Deserialize)]
+/// A single key with its modifiers
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Binding {
     code: KeyCode,
     modifiers: KeyModifiers,
 }
 
+impl Binding {
+    pub fn key_event(&self) -> KeyEvent {
+        KeyEvent::new(self.code, self.modifiers)
+    }
+}
+
+impl From<KeyEvent> for Binding {
+    fn from(event: KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            modifiers: event.modifiers,
+        }
+    }
+}
+
 impl std::fmt::Display for Binding {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         macro_rules! vim_string {
@@ -432,9 +622,36 @@ impl std::fmt::Display for Binding {
     }
 }
 
-#[derive(Deserialize)]
+/// Keys that have to be typed after each other, e.g. `g g`. Most sequences are a single key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequence(Vec<Binding>);
+
+impl KeySequence {
+    /// Whether the event is the last key of the sequence and the pending keys are the ones
+    /// before it
+    fn matches(&self, pending: &[Binding], event: KeyEvent) -> bool {
+        match self.0.split_last() {
+            Some((last, first)) => first == pending && *last == Binding::from(event),
+            None => false,
+        }
+    }
+
+    /// Whether the sequence starts with the keys and is longer than them
+    fn continues(&self, keys: &[Binding]) -> bool {
+        self.0.len() > keys.len() && self.0.starts_with(keys)
+    }
+}
+
+impl std::fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let keys: Vec<String> = self.0.iter().map(|key| key.to_string()).collect();
+        write!(f, "{}", keys.join(" "))
+    }
+}
+
+#[derive(Clone)]
 pub struct Keybinding {
-    bindings: Vec<Binding>,
+    bindings: Vec<KeySequence>,
 }
 
 impl Keybinding {
@@ -445,78 +662,205 @@ impl Keybinding {
     }
 
     fn binding(mut self, code: KeyCode, modifiers: KeyModifiers) -> Self {
-        self.bindings.push(Binding { code, modifiers });
+        self.bindings
+            .push(KeySequence(vec![Binding { code, modifiers }]));
         self
     }
 
-    pub fn matches_event(&self, event: KeyEvent) -> bool {
-        self.bindings
-            .iter()
-            .any(|x| x.code == event.code && x.modifiers == event.modifiers)
+    fn sequence(mut self, codes: &[KeyCode]) -> Self {
+        self.bindings.push(KeySequence(
+            codes
+                .iter()
+                .map(|code| Binding {
+                    code: *code,
+                    // like in the config file, uppercase characters are typed with shift
+                    modifiers: match code {
+                        KeyCode::Char(c) if c.is_uppercase() => KeyModifiers::SHIFT,
+                        _ => KeyModifiers::NONE,
+                    },
+                })
+                .collect(),
+        ));
+        self
+    }
+
+    /// Whether the event, together with the pending keys typed before it, matches one of the
+    /// bindings
+    pub fn matches_event(&self, event: KeyEvent, pending: &[Binding]) -> bool {
+        self.bindings.iter().any(|x| x.matches(pending, event))
     }
 
-    pub fn bindings(&self) -> &[Binding] {
+    /// Whether the keys are the start of one of the key sequences
+    pub fn continues(&self, keys: &[Binding]) -> bool {
+        self.bindings.iter().any(|x| x.continues(keys))
+    }
+
+    pub fn bindings(&self) -> &[KeySequence] {
         &self.bindings
     }
 }
 
-pub struct GlobalKeybindings {
-    pub scroll_down: Keybinding,
-    pub scroll_up: Keybinding,
+/// Declares a group of bindings together with an iterator over all of them, so no binding is
+/// left out when the pending keys are checked
+macro_rules! keybindings {
+    ($(#[$meta:meta])* pub struct $name:ident { $(pub $field:ident: Keybinding,)+ }) => {
+        $(#[$meta])*
+        pub struct $name {
+            $(pub $field: Keybinding,)+
+        }
+
+        impl $name {
+            pub fn iter(&self) -> impl Iterator<Item = &Keybinding> {
+                IntoIterator::into_iter([$(&self.$field),+])
+            }
+        }
+    };
+}
+
+keybindings! {
+    #[derive(Clone)]
+    pub struct GlobalKeybindings {
+        pub scroll_down: Keybinding,
+        pub scroll_up: Keybinding,
+
+        pub scroll_to_top: Keybinding,
+        pub scroll_to_bottom: Keybinding,
+
+        pub pop_popup: Keybinding,
+        pub cancel_request: Keybinding,
 
-    pub scroll_to_top: Keybinding,
-    pub scroll_to_bottom: Keybinding,
+        pub half_down: Keybinding,
+        pub half_up: Keybinding,
+        pub unselect_scroll: Keybinding,
 
-    pub pop_popup: Keybinding,
+        pub submit: Keybinding,
+        pub quit: Keybinding,
+        pub enter_search_bar: Keybinding,
+        pub exit_search_bar: Keybinding,
+        pub command_line: Keybinding,
 
-    pub half_down: Keybinding,
-    pub half_up: Keybinding,
-    pub unselect_scroll: Keybinding,
+        pub switch_context_search: Keybinding,
+        pub switch_context_page: Keybinding,
 
-    pub submit: Keybinding,
-    pub quit: Keybinding,
-    pub enter_search_bar: Keybinding,
-    pub exit_search_bar: Keybinding,
+        pub split_vertical: Keybinding,
+        pub split_horizontal: Keybinding,
+        pub focus_other_pane: Keybinding,
+        pub close_pane: Keybinding,
 
-    pub switch_context_search: Keybinding,
-    pub switch_context_page: Keybinding,
+        pub toggle_search_language_selection: Keybinding,
+        pub toggle_profile_selection: Keybinding,
+        pub toggle_logger: Keybinding,
 
-    pub toggle_search_language_selection: Keybinding,
-    pub toggle_logger: Keybinding,
+        pub random_page: Keybinding,
+        pub show_feed: Keybinding,
 
-    pub help: Keybinding,
+        pub help: Keybinding,
+    }
 }
 
-pub struct SearchKeybindings {
-    pub continue_search: Keybinding,
+keybindings! {
+    #[derive(Clone)]
+    pub struct SearchKeybindings {
+        pub continue_search: Keybinding,
+
+        pub next_dashboard_section: Keybinding,
+        pub prev_dashboard_section: Keybinding,
+    }
 }
 
-pub struct PageKeybindings {
-    pub pop_page: Keybinding,
-    pub jump_to_header: Keybinding,
+keybindings! {
+    #[derive(Clone)]
+    pub struct PageKeybindings {
+        pub pop_page: Keybinding,
+        pub jump_to_header: Keybinding,
 
-    pub select_first_link: Keybinding,
-    pub select_last_link: Keybinding,
+        pub select_first_link: Keybinding,
+        pub select_last_link: Keybinding,
 
-    pub select_prev_link: Keybinding,
-    pub select_next_link: Keybinding,
+        pub select_prev_link: Keybinding,
+        pub select_next_link: Keybinding,
 
-    pub open_link: Keybinding,
+        pub open_link: Keybinding,
 
-    pub toggle_page_language_selection: Keybinding,
-    pub toggle_zen_mode: Keybinding,
-    pub toggle_toc: Keybinding,
+        pub toggle_page_language_selection: Keybinding,
+        pub toggle_zen_mode: Keybinding,
+        pub toggle_toc: Keybinding,
+
+        pub show_history: Keybinding,
+        pub show_changes: Keybinding,
+        pub compare_revision: Keybinding,
+
+        pub next_change: Keybinding,
+        pub prev_change: Keybinding,
+
+        pub show_categories: Keybinding,
+        pub show_backlinks: Keybinding,
+        pub show_summary: Keybinding,
+        pub link_hints: Keybinding,
+
+        pub next_section: Keybinding,
+        pub prev_section: Keybinding,
+        pub next_section_same_level: Keybinding,
+        pub prev_section_same_level: Keybinding,
+
+        pub toggle_fold: Keybinding,
+        pub fold_all: Keybinding,
+        pub unfold_all: Keybinding,
+
+        pub toggle_bookmark: Keybinding,
+        pub open_link_in_other_pane: Keybinding,
+        pub switch_variant: Keybinding,
+    }
 }
 
+#[derive(Clone)]
 pub struct Keybindings {
     pub global: GlobalKeybindings,
     pub search: SearchKeybindings,
     pub page: PageKeybindings,
+
+    /// Time in milliseconds to wait for the next key of a key sequence
+    pub sequence_timeout: u64,
+}
+
+impl Keybindings {
+    /// Whether the keys are the start of a key sequence of a global binding or a binding of the
+    /// context. The bindings of the other context can't run, so they are not waited for
+    pub fn continues(&self, keys: &[Binding], context: BindingContext) -> bool {
+        let continues = |binding: &Keybinding| binding.continues(keys);
+        self.global.iter().any(continues)
+            || match context {
+                BindingContext::Search => self.search.iter().any(continues),
+                BindingContext::Page => self.page.iter().any(continues),
+            }
+    }
+}
+
+/// The context whose bindings are used besides the global ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingContext {
+    Search,
+    Page,
 }
 
+/// Name of the built-in profile for Wikipedia
+pub const DEFAULT_PROFILE: &str = "wikipedia";
+/// Name of the built-in profile for Wiktionary
+pub const WIKTIONARY_PROFILE: &str = "wiktionary";
+
+/// A named wiki site that can be switched to at runtime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WikiProfile {
+    pub name: String,
+    pub endpoint: Endpoint,
+}
+
+#[derive(Clone)]
 pub struct ApiConfig {
+    /// Endpoint of the profile the program starts with, in the configured language
     pub endpoint: Endpoint,
     pub language: Language,
+    pub profiles: Vec<WikiProfile>,
 
     pub search_limit: usize,
     pub search_qiprofile: search::QiProfile,
@@ -526,11 +870,78 @@ pub struct ApiConfig {
     pub search_sort_order: search::SortOrder,
 
     pub page_redirects: bool,
+    /// Variant each language is converted to, by language code
+    pub variants: BTreeMap<String, String>,
+    /// Whether the list of languages is refreshed from the site matrix
+    pub language_registry: bool,
+
+    /// Timeout of a single request in seconds, `0` disables the timeout
+    pub request_timeout: u64,
+    pub request_retries: u32,
+    pub proxy: Option<String>,
+    pub user_agent_contact: Option<String>,
+    /// Http client shared by all requests, built from the options above
+    pub client: Client,
 }
 
+impl ApiConfig {
+    pub fn profile(&self, name: &str) -> Option<&WikiProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Returns the variant the content in the language is converted to
+    pub fn variant(&self, language: Language) -> Option<&str> {
+        self.variants
+            .get(language.code())
+            .map(|variant| variant.as_str())
+    }
+
+    fn build_client(&self) -> Result<Client> {
+        let mut builder = Client::builder()
+            .timeout(Some(Duration::from_secs(self.request_timeout)).filter(|x| !x.is_zero()))
+            .max_retries(self.request_retries);
+
+        if let Some(ref proxy) = self.proxy {
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(ref contact) = self.user_agent_contact {
+            builder = builder.contact(contact);
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+#[derive(Clone)]
 pub struct UiConfig {
     pub popup_search_language_changed: bool,
     pub popup_page_language_changed: bool,
+    pub mouse: bool,
+    pub dashboard: DashboardConfig,
+    /// Listed first in the language selection popups
+    pub pinned_languages: Vec<Language>,
+}
+
+/// A list of pages or searches on the dashboard, shown before anything was searched
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashboardSection {
+    /// Pages that were started but not read to the end
+    ContinueReading,
+    /// The most recently viewed pages
+    Recent,
+    Bookmarks,
+    SearchHistory,
+    /// The featured article of today, loaded when the program starts
+    Featured,
+}
+
+#[derive(Clone)]
+pub struct DashboardConfig {
+    /// The sections in the order they are shown
+    pub sections: Vec<DashboardSection>,
+    /// Entries shown at most in every section
+    pub max_entries: usize,
 }
 
 impl Config {
@@ -565,16 +976,30 @@ impl Config {
 
                 zen_horizontal: Constraint::Percentage(80),
                 zen_vertical: Constraint::Percentage(90),
+
+                prefetch: PrefetchConfig {
+                    enabled: false,
+                    mode: PrefetchMode::Selected,
+                    delay: 500,
+                    links: 3,
+
+                    max_concurrent: 2,
+                    max_rate: 0,
+                },
+                hint_characters: "sadfjklewcmpgh".to_string(),
             },
             bindings: Keybindings {
                 global: GlobalKeybindings {
                     scroll_down: keybinding!([KeyCode::Char('j');, KeyCode::Down;]),
                     scroll_up: keybinding!([KeyCode::Char('k');, KeyCode::Up;]),
 
-                    scroll_to_top: keybinding!([KeyCode::Char('g');, KeyCode::Home;]),
+                    scroll_to_top: Keybinding::new()
+                        .sequence(&[KeyCode::Char('g'), KeyCode::Char('g')])
+                        .binding(KeyCode::Home, KeyModifiers::NONE),
                     scroll_to_bottom: keybinding!([KeyCode::Char('G'); SHIFT, KeyCode::End;]),
 
                     pop_popup: keybinding!([KeyCode::Esc;]),
+                    cancel_request: keybinding!([KeyCode::Esc;]),
 
                     half_down: keybinding!([KeyCode::Char('d'); CONTROL, KeyCode::PageDown;]),
                     half_up: keybinding!([KeyCode::Char('u'); CONTROL, KeyCode::PageUp;]),
@@ -586,17 +1011,30 @@ impl Config {
 
                     enter_search_bar: keybinding!([KeyCode::Char('i');]),
                     exit_search_bar: keybinding!([KeyCode::Esc;]),
+                    command_line: keybinding!([KeyCode::Char(':');]),
 
                     switch_context_search: keybinding!([KeyCode::Char('s');]),
                     switch_context_page: keybinding!([KeyCode::Char('p');]),
 
+                    split_vertical: keybinding!([KeyCode::Char('|');]),
+                    split_horizontal: keybinding!([KeyCode::Char('-');]),
+                    focus_other_pane: keybinding!([KeyCode::Char('w'); CONTROL]),
+                    close_pane: keybinding!([KeyCode::Char('x');]),
+
                     toggle_search_language_selection: keybinding!([KeyCode::F(2);]),
+                    toggle_profile_selection: keybinding!([KeyCode::Char('w');]),
                     toggle_logger: keybinding!([KeyCode::Char('l');]),
 
+                    random_page: keybinding!([KeyCode::F(5);]),
+                    show_feed: keybinding!([KeyCode::F(6);]),
+
                     help: keybinding!([KeyCode::Char('?');]),
                 },
                 search: SearchKeybindings {
                     continue_search: keybinding!([KeyCode::Char('c');]),
+
+                    next_dashboard_section: keybinding!([KeyCode::Tab;]),
+                    prev_dashboard_section: keybinding!([KeyCode::BackTab; SHIFT]),
                 },
                 page: PageKeybindings {
                     pop_page: keybinding!([KeyCode::Esc;]),
@@ -609,12 +1047,53 @@ impl Config {
                     toggle_page_language_selection: keybinding!([KeyCode::F(3);]),
                     toggle_zen_mode: keybinding!([KeyCode::F(4);]),
                     toggle_toc: keybinding!([KeyCode::Tab;, KeyCode::BackTab;]),
+
+                    show_history: keybinding!([KeyCode::F(7);]),
+                    show_changes: keybinding!([KeyCode::F(8);]),
+                    compare_revision: keybinding!([KeyCode::Char('d');]),
+
+                    next_change: keybinding!([KeyCode::Char('n');]),
+                    prev_change: keybinding!([KeyCode::Char('N'); SHIFT]),
+
+                    show_categories: keybinding!([KeyCode::F(9);]),
+                    show_backlinks: keybinding!([KeyCode::F(10);]),
+                    show_summary: keybinding!([KeyCode::Char('K'); SHIFT]),
+                    link_hints: keybinding!([KeyCode::Char('f');]),
+
+                    next_section: Keybinding::new()
+                        .sequence(&[KeyCode::Char(']'), KeyCode::Char(']')]),
+                    prev_section: Keybinding::new()
+                        .sequence(&[KeyCode::Char('['), KeyCode::Char('[')]),
+                    next_section_same_level: Keybinding::new()
+                        .sequence(&[KeyCode::Char(']'), KeyCode::Char('s')]),
+                    prev_section_same_level: Keybinding::new()
+                        .sequence(&[KeyCode::Char('['), KeyCode::Char('s')]),
+
+                    toggle_fold: Keybinding::new()
+                        .sequence(&[KeyCode::Char('z'), KeyCode::Char('a')]),
+                    fold_all: Keybinding::new().sequence(&[KeyCode::Char('z'), KeyCode::Char('M')]),
+                    unfold_all: Keybinding::new()
+                        .sequence(&[KeyCode::Char('z'), KeyCode::Char('R')]),
+
+                    toggle_bookmark: keybinding!([KeyCode::Char('b');]),
+                    open_link_in_other_pane: keybinding!([KeyCode::Char('o');]),
+                    switch_variant: keybinding!([KeyCode::Char('v');]),
                 },
+                sequence_timeout: 1000,
             },
             api: ApiConfig {
-                endpoint: Endpoint::parse("https://en.wikipedia.org/w/api.php")
-                    .expect("Hardcoded links should work"),
+                endpoint: Endpoint::wikipedia(Language::English),
                 language: Language::English,
+                profiles: vec![
+                    WikiProfile {
+                        name: DEFAULT_PROFILE.to_string(),
+                        endpoint: Endpoint::wikipedia(Language::English),
+                    },
+                    WikiProfile {
+                        name: WIKTIONARY_PROFILE.to_string(),
+                        endpoint: Endpoint::wiktionary(Language::English),
+                    },
+                ],
 
                 search_limit: 10,
                 search_qiprofile: search::QiProfile::default(),
@@ -624,10 +1103,29 @@ impl Config {
                 search_sort_order: search::SortOrder::Relevance,
 
                 page_redirects: false,
+                variants: BTreeMap::new(),
+                language_registry: false,
+
+                request_timeout: 30,
+                request_retries: 3,
+                proxy: None,
+                user_agent_contact: None,
+                client: Client::default(),
             },
             ui: UiConfig {
                 popup_search_language_changed: true,
                 popup_page_language_changed: true,
+                mouse: true,
+                dashboard: DashboardConfig {
+                    sections: vec![
+                        DashboardSection::ContinueReading,
+                        DashboardSection::Recent,
+                        DashboardSection::Bookmarks,
+                        DashboardSection::SearchHistory,
+                    ],
+                    max_entries: 5,
+                },
+                pinned_languages: Vec::new(),
             },
         }
     }
@@ -650,6 +1148,73 @@ impl Default for Config {
     }
 }
 
+/// Options that can be changed while the program is running, named like in the config file
+pub const RUNTIME_OPTIONS: &[&str] = &[
+    "page.toc.enabled",
+    "page.toc.position",
+    "page.toc.width_percentage",
+    "page.toc.enable_scrolling",
+    "page.hint_characters",
+    "api.search_limit",
+    "ui.popup_search_language_changed",
+    "ui.popup_page_language_changed",
+];
+
+impl Config {
+    /// Changes one of the [`RUNTIME_OPTIONS`]. The `page.` of page options can be left out, e.g.
+    /// `toc.position`
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<()> {
+        fn parse<T>(name: &str, value: &str) -> Result<T>
+        where
+            T: FromStr,
+            T::Err: std::error::Error + Send + Sync + 'static,
+        {
+            value
+                .parse()
+                .with_context(|| format!("invalid value '{}' for '{}'", value, name))
+        }
+
+        let name = if RUNTIME_OPTIONS.contains(&name) {
+            name.to_string()
+        } else {
+            format!("page.{}", name)
+        };
+
+        match name.as_str() {
+            "page.toc.enabled" => self.page.toc.enabled = parse(&name, value)?,
+            "page.toc.position" => {
+                self.page.toc.position = match value.to_lowercase().as_str() {
+                    "left" => TocConfigPosition::Left,
+                    "right" => TocConfigPosition::Right,
+                    _ => bail!(
+                        "invalid value '{}' for '{}', use 'left' or 'right'",
+                        value,
+                        name
+                    ),
+                }
+            }
+            "page.toc.width_percentage" => {
+                let width: u16 = parse(&name, value)?;
+                if width > 100 {
+                    bail!("'{}' has to be between 0 and 100", name);
+                }
+                self.page.toc.width_percentage = width;
+            }
+            "page.toc.enable_scrolling" => self.page.toc.enable_scrolling = parse(&name, value)?,
+            "page.hint_characters" => self.page.hint_characters = value.to_string(),
+            "api.search_limit" => self.api.search_limit = parse(&name, value)?,
+            "ui.popup_search_language_changed" => {
+                self.ui.popup_search_language_changed = parse(&name, value)?
+            }
+            "ui.popup_page_language_changed" => {
+                self.ui.popup_page_language_changed = parse(&name, value)?
+            }
+            _ => bail!("the option '{}' doesn't exist or can't be changed", name),
+        }
+        Ok(())
+    }
+}
+
 #[derive(Deserialize)]
 struct UserConfig {
     page: Option<UserPageConfig>,
@@ -664,6 +1229,8 @@ struct UserPageConfig {
     padding: Option<PaddingConfig>,
 
     zen_mode: Option<UserZenModeConfig>,
+    prefetch: Option<UserPrefetchConfig>,
+    hint_characters: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -698,6 +1265,17 @@ struct UserZenModeConfig {
     vertical: Option<UserConstraint>,
 }
 
+#[derive(Deserialize)]
+struct UserPrefetchConfig {
+    enabled: Option<bool>,
+    mode: Option<PrefetchMode>,
+    delay: Option<u64>,
+    links: Option<usize>,
+
+    max_concurrent: Option<usize>,
+    max_rate: Option<u64>,
+}
+
 #[derive(Deserialize)]
 struct UserTocConfig {
     enabled: Option<bool>,
@@ -794,22 +1372,70 @@ enum UserBinding {
         code: UserKeyCode,
         modifiers: Option<KeyModifiers>,
     },
+    Sequence(UserKeySequence),
 }
 
 #[allow(clippy::from_over_into)]
-impl Into<Binding> for UserBinding {
-    fn into(self) -> Binding {
+impl Into<KeySequence> for UserBinding {
+    fn into(self) -> KeySequence {
         match self {
             UserBinding::CodeOnly(code) => UserBinding::Binding {
                 code,
                 modifiers: None,
             }
             .into(),
-            UserBinding::Binding { code, modifiers } => Binding {
+            UserBinding::Binding { code, modifiers } => KeySequence(vec![Binding {
                 code: code.into(),
                 modifiers: modifiers.unwrap_or(KeyModifiers::empty()),
-            },
+            }]),
+            UserBinding::Sequence(sequence) => KeySequence(sequence.0),
+        }
+    }
+}
+
+/// Keys separated by spaces, e.g. "g g" or "] ]"
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+struct UserKeySequence(Vec<Binding>);
+
+impl std::convert::TryFrom<String> for UserKeySequence {
+    type Error = String;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        use serde::de::{value::Error, IntoDeserializer};
+
+        let keys = value
+            .split_whitespace()
+            .map(|key| {
+                let mut chars = key.chars();
+                if let (Some(c), None) = (chars.next(), chars.next()) {
+                    // terminals send uppercase characters with the shift modifier
+                    let modifiers = if c.is_uppercase() {
+                        KeyModifiers::SHIFT
+                    } else {
+                        KeyModifiers::NONE
+                    };
+                    return Ok(Binding {
+                        code: KeyCode::Char(c),
+                        modifiers,
+                    });
+                }
+
+                let code = UserKeyCodeInner::deserialize(
+                    IntoDeserializer::<Error>::into_deserializer(key.to_lowercase().as_str()),
+                )
+                .map_err(|_| format!("unknown key '{}' in the key sequence '{}'", key, value))?;
+                Ok(Binding {
+                    code: UserKeyCode::NonChar(code).into(),
+                    modifiers: KeyModifiers::NONE,
+                })
+            })
+            .collect::<std::result::Result<Vec<Binding>, String>>()?;
+
+        if keys.is_empty() {
+            return Err("a key sequence needs at least one key".to_string());
         }
+        Ok(Self(keys))
     }
 }
 
@@ -850,6 +1476,7 @@ user_keybindings!(
     scroll_to_top,
     scroll_to_bottom,
     pop_popup,
+    cancel_request,
     half_down,
     half_up,
     unselect_scroll,
@@ -857,14 +1484,27 @@ user_keybindings!(
     quit,
     enter_search_bar,
     exit_search_bar,
+    command_line,
     switch_context_search,
     switch_context_page,
+    split_vertical,
+    split_horizontal,
+    focus_other_pane,
+    close_pane,
     toggle_search_language_selection,
+    toggle_profile_selection,
     toggle_logger,
+    random_page,
+    show_feed,
     help
 );
 
-user_keybindings!(UserSearchKeybindings, continue_search);
+user_keybindings!(
+    UserSearchKeybindings,
+    continue_search,
+    next_dashboard_section,
+    prev_dashboard_section
+);
 
 user_keybindings!(
     UserPageKeybindings,
@@ -877,7 +1517,26 @@ user_keybindings!(
     open_link,
     toggle_page_language_selection,
     toggle_zen_mode,
-    toggle_toc
+    toggle_toc,
+    show_history,
+    show_changes,
+    compare_revision,
+    next_change,
+    prev_change,
+    show_categories,
+    show_backlinks,
+    show_summary,
+    link_hints,
+    next_section,
+    prev_section,
+    next_section_same_level,
+    prev_section_same_level,
+    toggle_fold,
+    fold_all,
+    unfold_all,
+    toggle_bookmark,
+    open_link_in_other_pane,
+    switch_variant
 );
 
 #[derive(Deserialize)]
@@ -885,6 +1544,7 @@ struct UserKeybindingsConfig {
     global: Option<UserGlobalKeybindings>,
     search: Option<UserSearchKeybindings>,
     page: Option<UserPageKeybindings>,
+    sequence_timeout: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -893,6 +1553,9 @@ struct UserApiConfig {
     language: Option<Language>,
     post_language: Option<String>,
 
+    profile: Option<String>,
+    profiles: Option<BTreeMap<String, UserWikiProfile>>,
+
     search_limit: Option<usize>,
     search_qiprofile: Option<search::QiProfile>,
     search_type: Option<search::SearchType>,
@@ -901,17 +1564,104 @@ struct UserApiConfig {
     search_sort_order: Option<search::SortOrder>,
 
     page_redirects: Option<bool>,
+    variants: Option<BTreeMap<String, String>>,
+    language_registry: Option<bool>,
+
+    request_timeout: Option<u64>,
+    request_retries: Option<u32>,
+    proxy: Option<String>,
+    user_agent_contact: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UserWikiProfile {
+    url: String,
+    site_name: Option<String>,
+    url_template: Option<String>,
+    article_path: Option<String>,
+}
+
+impl UserWikiProfile {
+    fn endpoint(self, name: &str) -> Result<Endpoint> {
+        let mut endpoint = Endpoint::parse(&self.url)
+            .context("failed parsing the endpoint url")?
+            .with_site_name(self.site_name.unwrap_or_else(|| name.to_string()));
+
+        if let Some(template) = self.url_template {
+            endpoint = endpoint.with_url_template(template)?;
+        }
+
+        if let Some(article_path) = self.article_path {
+            endpoint = endpoint.with_article_path(article_path);
+        }
+
+        Ok(endpoint)
+    }
 }
 
 #[derive(Deserialize, Debug)]
 struct UserUiConfig {
     popup_search_language_changed: Option<bool>,
     popup_page_language_changed: Option<bool>,
+    mouse: Option<bool>,
+    dashboard: Option<UserDashboardConfig>,
+    pinned_languages: Option<Vec<Language>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct UserDashboardConfig {
+    sections: Option<Vec<DashboardSection>>,
+    max_entries: Option<usize>,
 }
 
 pub fn load_theme() -> Result<Theme> {
-    let mut default_theme = Theme::default();
     let user_theme = load_user_theme().context("failed loading the user theme")?;
+    Ok(theme_from_user(user_theme))
+}
+
+/// Name of the theme without any changes. Other themes are loaded from the themes directory
+pub const DEFAULT_THEME: &str = "default";
+
+/// Loads the theme `<name>.toml` from the themes directory in the config directory
+pub fn load_named_theme(name: &str) -> Result<Theme> {
+    if name == DEFAULT_THEME {
+        return Ok(Theme::default());
+    }
+
+    let path = themes_dir()?.join(format!("{}.toml", name));
+    if !path.exists() {
+        bail!("the theme '{}' doesn't exist", name);
+    }
+
+    let user_theme_str = std::fs::read_to_string(&path).context("failed reading the theme file")?;
+    let user_theme = toml::from_str::<UserTheme>(&user_theme_str)
+        .with_context(|| format!("failed parsing the theme '{}'", name))?;
+    Ok(theme_from_user(user_theme))
+}
+
+/// Names of the themes that can be loaded with [`load_named_theme`]
+pub fn theme_names() -> Vec<String> {
+    let mut names = vec![DEFAULT_THEME.to_string()];
+    if let Ok(entries) = themes_dir().and_then(|dir| Ok(std::fs::read_dir(dir)?)) {
+        names.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "toml" {
+                return None;
+            }
+            Some(path.file_stem()?.to_string_lossy().to_string())
+        }));
+    }
+    names
+}
+
+fn themes_dir() -> Result<PathBuf> {
+    Ok(config_dir()
+        .context("failed retrieving the config dir")?
+        .join(THEMES_DIR_NAME))
+}
+
+fn theme_from_user(user_theme: UserTheme) -> Theme {
+    let mut default_theme = Theme::default();
 
     override_options!(default_theme, user_theme::{
         bg,
@@ -938,10 +1688,19 @@ pub fn load_theme() -> Result<Theme> {
         search_title_fg,
 
         status_bar_fg,
-        status_bar_bg
+        status_bar_bg,
+
+        diff_inserted_fg,
+        diff_inserted_bg,
+        diff_deleted_fg,
+        diff_deleted_bg,
+
+        hint_fg,
+        hint_bg,
+        hint_typed_fg
     });
 
-    Ok(default_theme)
+    default_theme
 }
 
 fn load_user_theme() -> Result<UserTheme> {
@@ -986,6 +1745,15 @@ pub struct Theme {
 
     pub status_bar_fg: Color,
     pub status_bar_bg: Color,
+
+    pub diff_inserted_fg: Color,
+    pub diff_inserted_bg: Color,
+    pub diff_deleted_fg: Color,
+    pub diff_deleted_bg: Color,
+
+    pub hint_fg: Color,
+    pub hint_bg: Color,
+    pub hint_typed_fg: Color,
 }
 
 impl Theme {
@@ -1016,6 +1784,15 @@ impl Theme {
 
             status_bar_fg: Color::Reset,
             status_bar_bg: Color::DarkGray,
+
+            diff_inserted_fg: Color::Green,
+            diff_inserted_bg: Color::Reset,
+            diff_deleted_fg: Color::Red,
+            diff_deleted_bg: Color::Reset,
+
+            hint_fg: Color::Black,
+            hint_bg: Color::Yellow,
+            hint_typed_fg: Color::Red,
         }
     }
 
@@ -1093,4 +1870,13 @@ struct UserTheme {
 
     status_bar_fg: Option<Color>,
     status_bar_bg: Option<Color>,
+
+    diff_inserted_fg: Option<Color>,
+    diff_inserted_bg: Option<Color>,
+    diff_deleted_fg: Option<Color>,
+    diff_deleted_bg: Option<Color>,
+
+    hint_fg: Option<Color>,
+    hint_bg: Option<Color>,
+    hint_typed_fg: Option<Color>,
 }
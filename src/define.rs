@@ -0,0 +1,46 @@
+//! The `define` command, printing the definitions of a word from Wiktionary
+
+use anyhow::{bail, Context, Result};
+use wiki_api::{
+    dictionary::Dictionary,
+    languages::Language,
+    page::{Page, Property},
+    Endpoint,
+};
+
+use crate::{
+    config::{Config, WIKTIONARY_PROFILE},
+    export::{export_definitions, ExportFormat},
+};
+
+/// Fetches the Wiktionary page of the word and returns its definitions as text. The endpoint of
+/// the `wiktionary` profile is used, so it can be pointed to another site in the config
+pub async fn define(word: &str, language: Language, config: &Config) -> Result<String> {
+    let endpoint = config
+        .api
+        .profile(WIKTIONARY_PROFILE)
+        .map(|profile| profile.endpoint.for_language(language))
+        .unwrap_or_else(|| Endpoint::wiktionary(language));
+
+    let mut page_request = Page::builder()
+        .client(config.api.client.clone())
+        .page(word)
+        .properties(vec![Property::Text, Property::Sections])
+        .endpoint(endpoint)
+        .language(language)
+        .redirects(true);
+    if let Some(variant) = config.api.variant(language) {
+        page_request = page_request.variant(variant);
+    }
+
+    let page = page_request
+        .fetch()
+        .await
+        .with_context(|| format!("failed loading the definitions of '{}'", word))?;
+
+    let dictionary = Dictionary::from_document(&page.content);
+    if dictionary.is_empty() {
+        bail!("no definitions of '{}' were found", word);
+    }
+    Ok(export_definitions(&page, &dictionary, ExportFormat::Text))
+}
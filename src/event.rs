@@ -1,4 +1,6 @@
-use crossterm::event::{Event as CrosstermEvent, KeyEvent, KeyEventKind};
+use crossterm::event::{
+    Event as CrosstermEvent, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind,
+};
 use futures::FutureExt;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::StreamExt;
@@ -10,6 +12,7 @@ pub enum Event {
     Quit,
     RenderTick,
     Key(KeyEvent),
+    Mouse(MouseEvent),
     Resize(u16, u16),
 }
 
@@ -43,6 +46,10 @@ impl EventHandler {
                             CrosstermEvent::Key(key) => if key.kind == KeyEventKind::Press {
                                 event_tx.send(Event::Key(key)).unwrap();
                             },
+                            // plain mouse movements are not used and would only flood the channel
+                            CrosstermEvent::Mouse(mouse) => if mouse.kind != MouseEventKind::Moved {
+                                event_tx.send(Event::Mouse(mouse)).unwrap();
+                            },
                             CrosstermEvent::Resize(x, y) => {
                                 event_tx.send(Event::Resize(x, y)).unwrap();
                             },
@@ -0,0 +1,341 @@
+use std::str::FromStr;
+
+use wiki_api::{
+    dictionary::{Definition, Dictionary},
+    document::{Data, Document, Node},
+    page::{Link, Page},
+};
+
+/// File format a page can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Text,
+}
+
+impl ExportFormat {
+    pub const NAMES: &'static [&'static str] = &["md", "txt"];
+
+    /// File extension of the format, used when no path was given
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Text => "txt",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "txt" | "text" => Ok(ExportFormat::Text),
+            _ => Err(format!("unknown export format '{}', use 'md' or 'txt'", s)),
+        }
+    }
+}
+
+/// Converts the page into the format. Tables, images and other elements the page viewer can't
+/// display are left out
+pub fn export_page(page: &Page, format: ExportFormat) -> String {
+    let mut exporter = Exporter::new(format);
+    exporter.push_heading(&page.title, 1);
+
+    if let Some(root) = page.content.nth(0) {
+        exporter.export_node(root);
+    }
+
+    exporter.finish()
+}
+
+/// Converts the entries of a Wiktionary page into the format: the pronunciation, the numbered
+/// definitions of each part of speech with their examples and the etymology of every language
+pub fn export_definitions(page: &Page, dictionary: &Dictionary, format: ExportFormat) -> String {
+    let document = &page.content;
+    let mut exporter = Exporter::new(format);
+    exporter.push_heading(&page.title, 1);
+
+    for entry in dictionary.entries.iter() {
+        exporter.push_heading(&entry.language, 2);
+
+        for item in entry.pronunciation.iter().filter_map(|&x| document.nth(x)) {
+            exporter.end_line();
+            exporter.out.push_str("- ");
+            exporter.export_children(item);
+        }
+        exporter.end_block();
+
+        for part_of_speech in entry.parts_of_speech.iter() {
+            exporter.end_block();
+            exporter.push_markup("**");
+            exporter.out.push_str(&part_of_speech.name);
+            exporter.push_markup("**");
+            if let Some(headword) = part_of_speech.headword.and_then(|x| document.nth(x)) {
+                exporter.out.push_str(": ");
+                exporter.export_children(headword);
+            }
+            exporter.end_block();
+            exporter.export_definitions(document, &part_of_speech.definitions, 0);
+            exporter.end_block();
+        }
+
+        if !entry.etymology.is_empty() {
+            exporter.push_markup("_");
+            exporter.out.push_str("Etymology");
+            exporter.push_markup("_");
+            exporter.out.push_str(": ");
+            for paragraph in entry.etymology.iter().filter_map(|&x| document.nth(x)) {
+                exporter.export_children(paragraph);
+                exporter.push_text(" ");
+            }
+            exporter.end_block();
+        }
+    }
+
+    exporter.finish()
+}
+
+struct Exporter {
+    format: ExportFormat,
+    out: String,
+    list_depth: usize,
+}
+
+impl Exporter {
+    fn new(format: ExportFormat) -> Self {
+        Exporter {
+            format,
+            out: String::new(),
+            list_depth: 0,
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut out = self.out.trim_end().to_string();
+        out.push('\n');
+        out
+    }
+
+    fn is_markdown(&self) -> bool {
+        self.format == ExportFormat::Markdown
+    }
+
+    /// Adds text, collapsing whitespace like a browser does
+    fn push_text(&mut self, text: &str) {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !self.out.is_empty() && !self.out.ends_with(char::is_whitespace) {
+                    self.out.push(' ');
+                }
+            } else {
+                self.out.push(c);
+            }
+        }
+    }
+
+    /// Adds markup without collapsing it
+    fn push_markup(&mut self, markup: &str) {
+        if self.is_markdown() {
+            self.out.push_str(markup);
+        }
+    }
+
+    /// Ends the current line without leaving spaces at its end
+    fn end_line(&mut self) {
+        let len = self.out.trim_end_matches(' ').len();
+        self.out.truncate(len);
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    /// Ends the current block with an empty line
+    fn end_block(&mut self) {
+        self.end_line();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    /// Adds a heading of the level, the first level is only used for the title of the page
+    fn push_heading(&mut self, heading: &str, level: usize) {
+        self.end_block();
+        match self.format {
+            ExportFormat::Markdown => {
+                self.out
+                    .push_str(&format!("{} {}", "#".repeat(level), heading))
+            }
+            ExportFormat::Text => {
+                let underline = if level == 1 { "=" } else { "-" };
+                self.out.push_str(heading);
+                self.out.push('\n');
+                self.out
+                    .push_str(&underline.repeat(heading.chars().count()));
+            }
+        }
+        self.end_block();
+    }
+
+    /// Adds the definitions as a numbered list, the examples are indented below them
+    fn export_definitions(
+        &mut self,
+        document: &Document,
+        definitions: &[Definition],
+        depth: usize,
+    ) {
+        let indent = "   ".repeat(depth);
+        for (n, definition) in definitions.iter().enumerate() {
+            self.end_line();
+            self.out.push_str(&format!("{}{}. ", indent, n + 1));
+            for node in definition.content.iter().filter_map(|&x| document.nth(x)) {
+                self.export_node(node);
+            }
+
+            for example in definition.examples.iter().filter_map(|&x| document.nth(x)) {
+                self.end_line();
+                self.out.push_str(&indent);
+                self.out.push_str("   ");
+                self.push_markup("> ");
+                self.export_children(example);
+            }
+
+            self.export_definitions(document, &definition.subdefinitions, depth + 1);
+        }
+    }
+
+    fn export_children(&mut self, node: Node<'_>) {
+        for child in node.children() {
+            self.export_node(child);
+        }
+    }
+
+    fn export_node(&mut self, node: Node<'_>) {
+        match node.data() {
+            Data::Text { contents } => self.push_text(contents),
+            Data::Header { kind, .. } => {
+                self.end_block();
+                match self.format {
+                    // the title of the page is the only first level header
+                    ExportFormat::Markdown => {
+                        let level = (kind.clone() as usize + 1).min(6);
+                        self.out.push_str(&format!("{} ", "#".repeat(level)));
+                        self.export_children(node);
+                    }
+                    ExportFormat::Text => {
+                        let start = self.out.len();
+                        self.export_children(node);
+                        let width = self.out[start..].trim().chars().count();
+                        self.end_line();
+                        self.out.push_str(&"-".repeat(width));
+                    }
+                }
+                self.end_block();
+            }
+            Data::Paragraph
+            | Data::Division
+            | Data::Hatnote
+            | Data::RedirectMessage
+            | Data::Disambiguation => {
+                self.end_block();
+                self.export_children(node);
+                self.end_block();
+            }
+            Data::Blockquote => {
+                self.end_block();
+                let start = self.out.len();
+                self.export_children(node);
+                let quote = self.out.split_off(start);
+                let prefix = if self.is_markdown() { "> " } else { "    " };
+                for line in quote.trim().lines() {
+                    self.out.push_str(prefix);
+                    self.out.push_str(line);
+                    self.out.push('\n');
+                }
+                self.end_block();
+            }
+            Data::OrderedList | Data::UnorderedList => {
+                if self.list_depth == 0 {
+                    self.end_block();
+                }
+                self.list_depth += 1;
+                let is_ordered = matches!(node.data(), Data::OrderedList);
+                for (n, item) in node.children().enumerate() {
+                    self.end_line();
+                    self.out.push_str(&"  ".repeat(self.list_depth - 1));
+                    if is_ordered {
+                        self.out.push_str(&format!("{}. ", n + 1));
+                    } else {
+                        self.out.push_str("- ");
+                    }
+                    self.export_children(item);
+                }
+                self.list_depth -= 1;
+                if self.list_depth == 0 {
+                    self.end_block();
+                }
+            }
+            Data::DescriptionListTerm => {
+                self.end_line();
+                self.push_markup("**");
+                self.export_children(node);
+                self.push_markup("**");
+                self.end_line();
+            }
+            Data::DerscriptionListDescription => {
+                self.end_line();
+                self.out
+                    .push_str(if self.is_markdown() { ": " } else { "    " });
+                self.export_children(node);
+                self.end_line();
+            }
+            Data::DescriptionList => {
+                self.end_block();
+                self.export_children(node);
+                self.end_block();
+            }
+            Data::Bold => {
+                self.push_markup("**");
+                self.export_children(node);
+                self.push_markup("**");
+            }
+            Data::Italic => {
+                self.push_markup("_");
+                self.export_children(node);
+                self.push_markup("_");
+            }
+            Data::Linebreak => self.end_line(),
+            Data::Link(link) if self.is_markdown() => {
+                let url = match link {
+                    Link::Internal(link_data) => link_data
+                        .endpoint
+                        .article_url(&link_data.page)
+                        .map(|mut url| {
+                            if let Some(ref anchor) = link_data.anchor {
+                                url.set_fragment(Some(&anchor.anchor));
+                            }
+                            url.to_string()
+                        }),
+                    Link::Anchor(link_data) => Some(format!("#{}", link_data.anchor)),
+                    Link::External(link_data) => Some(link_data.url.to_string()),
+                    Link::RedLink(_) | Link::MediaLink(_) | Link::ExternalToInternal(_) => None,
+                };
+
+                match url {
+                    Some(url) => {
+                        self.out.push('[');
+                        self.export_children(node);
+                        self.out.push_str(&format!("]({})", url));
+                    }
+                    None => self.export_children(node),
+                }
+            }
+            // references and elements that can't be displayed are left out
+            Data::Reflink | Data::Unsupported(_) | Data::UnsupportedInline(_) => {}
+            Data::Section { .. } | Data::Span | Data::ListItem | Data::Link(_) | Data::Unknown => {
+                self.export_children(node)
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Fuzzy matching of short strings like command names or languages
+
+/// A successful fuzzy match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher scores are better matches
+    pub score: i64,
+    /// Positions of the matched characters in the text, counted in characters
+    pub indices: Vec<usize>,
+}
+
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '_' | '-' | '.' | '/' | '(' | ')')
+}
+
+/// Matches the pattern against the text, ignoring case. Every character of the pattern has to
+/// appear in the text in the same order. Matches at the start of the text or of a word and
+/// consecutive matches score higher, gaps between matches score lower
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let text: Vec<char> = text.chars().collect();
+    let mut indices = Vec::new();
+    let mut score = 0;
+    let mut start = 0;
+
+    for p in pattern.chars().flat_map(char::to_lowercase) {
+        let idx = (start..text.len()).find(|idx| text[*idx].to_lowercase().eq(Some(p)))?;
+
+        score += 1;
+        if idx == 0 {
+            score += 8;
+        } else if is_separator(text[idx - 1]) {
+            score += 6;
+        }
+        match indices.last() {
+            Some(last) if last + 1 == idx => score += 4,
+            Some(last) => score -= (idx - last - 1).min(5) as i64,
+            None => score -= idx.min(5) as i64,
+        }
+
+        indices.push(idx);
+        start = idx + 1;
+    }
+
+    // shorter texts are closer to what was typed
+    score -= (text.len() - indices.len()).min(10) as i64 / 2;
+    Some(FuzzyMatch { score, indices })
+}
+
+/// Keeps the items that match the pattern, the best matches first. Items with the same score keep
+/// their order. An empty pattern matches every item
+pub fn fuzzy_filter<T>(
+    pattern: &str,
+    items: impl IntoIterator<Item = T>,
+    text: impl Fn(&T) -> &str,
+) -> Vec<(T, FuzzyMatch)> {
+    let mut matches: Vec<(T, FuzzyMatch)> = items
+        .into_iter()
+        .filter_map(|item| {
+            let fuzzy_match = fuzzy_match(pattern, text(&item))?;
+            Some((item, fuzzy_match))
+        })
+        .collect();
+    matches.sort_by(|(_, a), (_, b)| b.score.cmp(&a.score));
+    matches
+}
@@ -0,0 +1,86 @@
+//! The list of languages fetched from the site matrix, saved in the cache directory
+
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use tracing::{debug, error, info};
+use wiki_api::{sitematrix::LanguageRegistry, Client, Endpoint};
+
+use crate::config::cache_dir;
+
+const REGISTRY_FILE_NAME: &str = "sitematrix.json";
+/// The site matrix is fetched again when the saved one is older than this
+const MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// Every Wikimedia wiki can return the site matrix
+const SITEMATRIX_ENDPOINT: &str = "https://meta.wikimedia.org/w/api.php";
+
+fn path() -> Result<PathBuf> {
+    Ok(cache_dir()?.join(REGISTRY_FILE_NAME))
+}
+
+/// Installs the saved languages, merged with the built in ones. Returns whether the saved
+/// languages are missing or too old and should be refreshed
+///
+/// This runs before the command line arguments are parsed, so `--language` can be one of the
+/// saved languages. It's only called when the language registry is enabled in the config
+pub fn install_cached() -> Result<bool> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(true);
+    }
+
+    let saved: LanguageRegistry = serde_json::from_str(
+        &std::fs::read_to_string(&path)
+            .with_context(|| format!("failed reading the languages from {:?}", path))?,
+    )
+    .with_context(|| format!("failed parsing the languages from {:?}", path))?;
+
+    let mut registry = LanguageRegistry::builtin();
+    registry.merge(saved);
+    registry.install();
+
+    let age = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .unwrap_or(MAX_AGE);
+    Ok(age >= MAX_AGE)
+}
+
+async fn fetch_and_save(client: Client) -> Result<()> {
+    let endpoint =
+        Endpoint::parse(SITEMATRIX_ENDPOINT).context("failed parsing the site matrix endpoint")?;
+    let fetched = LanguageRegistry::fetch(&client, endpoint)
+        .await
+        .context("failed fetching the site matrix")?;
+
+    let path = path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string(&fetched)?)
+        .with_context(|| format!("failed saving the languages to {:?}", path))?;
+    debug!("saved the languages to {:?}", path);
+
+    let mut registry = LanguageRegistry::builtin();
+    registry.merge(fetched);
+    info!(
+        "refreshed the list of languages, {} languages are known",
+        registry.languages().len()
+    );
+    registry.install();
+    Ok(())
+}
+
+/// Fetches the languages of the site matrix in the background, saves and installs them. Failures
+/// are only logged, the languages that are already installed stay in use
+pub fn refresh(client: Client) {
+    tokio::spawn(async move {
+        if let Err(error) = fetch_and_save(client).await {
+            error!("failed refreshing the list of languages: {:?}", error);
+        }
+    });
+}
@@ -1,13 +1,23 @@
 pub mod action;
 pub mod app;
+pub mod bookmarks;
 pub mod cli;
+pub mod commands;
 pub mod components;
 pub mod config;
+pub mod define;
 pub mod event;
+pub mod export;
+pub mod fuzzy;
 pub mod key_macros;
+pub mod language_registry;
 pub mod logging;
+pub mod page_cache;
 pub mod page_loader;
 pub mod panic_handler;
+pub mod pending_keys;
+pub mod prefetcher;
 pub mod renderer;
+pub mod request;
 pub mod terminal;
 pub mod ui;
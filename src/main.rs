@@ -2,14 +2,16 @@ use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use tokio::sync::{mpsc, Mutex};
-use tracing::warn;
+use tracing::{error, warn};
 use wiki_tui::{
     action::{Action, ActionPacket, ActionResult},
     app::AppComponent,
     cli::match_cli,
     components::Component,
-    config::{load_config, load_theme, Config, Theme},
+    config::{load_config, load_language_registry_config, load_theme, Config, Theme},
+    define::define,
     event::EventHandler,
+    language_registry,
     logging::initialize_logging,
     panic_handler::initialize_panic_handler,
     terminal::Tui,
@@ -18,6 +20,10 @@ use wiki_tui::{
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // a saved registry from an earlier run must not decide which languages are accepted when the
+    // registry is disabled
+    let registry_enabled = load_language_registry_config().unwrap_or_default();
+    let refresh_languages = registry_enabled.then(language_registry::install_cached);
     let results = match_cli();
     let mut actions = results.actions;
 
@@ -53,12 +59,29 @@ async fn main() -> Result<()> {
             Theme::default()
         });
 
+    if let Some(refresh_languages) = refresh_languages {
+        let refresh_languages = refresh_languages.unwrap_or_else(|err| {
+            error!("{:?}", err);
+            true
+        });
+        if refresh_languages {
+            language_registry::refresh(config.api.client.clone());
+        }
+    }
+
+    if let Some((word, language)) = results.define {
+        let language = language.unwrap_or(config.api.language);
+        print!("{}", define(&word, language, &config).await?);
+        return Ok(());
+    }
+
+    let mouse = config.ui.mouse;
     app_component
         .lock()
         .await
         .init(action_tx.clone(), Arc::new(config), Arc::new(theme))?;
 
-    let mut tui = Tui::new()?;
+    let mut tui = Tui::new(mouse)?;
     tui.enter()?;
 
     let _action_tx = action_tx.clone();
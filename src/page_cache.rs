@@ -0,0 +1,234 @@
+//! Pages that were opened before, saved in the cache directory. The cache is shared by the page
+//! viewers of both panes of the split view, so only one of them writes the file
+
+use std::{
+    collections::{HashMap, VecDeque},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use tracing::{debug, error};
+use uuid::Uuid;
+use wiki_api::{page::Page, Endpoint};
+
+use crate::{
+    components::page::PageComponent,
+    config::{Config, Theme},
+};
+
+/// Most prefetched pages that are kept, the oldest ones are dropped first
+const MAX_PREFETCHED_PAGES: usize = 50;
+
+/// The page cache of all panes
+pub type SharedPageCache = Arc<Mutex<PageCache>>;
+
+/// Identifies a page in the cache by its title, language code and the variant it was converted to
+pub type PageKey = (String, String, Option<String>);
+
+pub fn page_key(page: &Page) -> PageKey {
+    (
+        page.title.clone(),
+        page.language.code().to_string(),
+        page.variant.clone(),
+    )
+}
+
+/// Prefetched pages that weren't opened yet. They are only kept in memory, a page is added to the
+/// cache once it's displayed
+#[derive(Default)]
+struct PrefetchedPages {
+    /// The pages in the order they were prefetched, with the keys they are found by
+    pages: VecDeque<(Vec<PageKey>, Page)>,
+}
+
+impl PrefetchedPages {
+    fn get(&self, key: &PageKey) -> Option<&Page> {
+        self.pages
+            .iter()
+            .find(|(keys, _)| keys.contains(key))
+            .map(|(_, page)| page)
+    }
+
+    fn insert(&mut self, keys: Vec<PageKey>, page: Page) {
+        if self.pages.len() >= MAX_PREFETCHED_PAGES {
+            self.pages.pop_front();
+        }
+        self.pages.push_back((keys, page));
+    }
+
+    fn remove(&mut self, uuid: Uuid) {
+        self.pages.retain(|(_, page)| page.uuid != uuid);
+    }
+}
+
+#[derive(Default)]
+pub struct PageCache {
+    pages: HashMap<Uuid, PageComponent>,
+    /// Maps (title, language_code, variant) -> UUID for quick cache lookups
+    index: HashMap<PageKey, Uuid>,
+    prefetched: PrefetchedPages,
+}
+
+impl PageCache {
+    fn path() -> PathBuf {
+        let mut path = directories::ProjectDirs::from("com", "github", "wiki-tui")
+            .unwrap()
+            .cache_dir()
+            .to_path_buf();
+        path.push("page_cache.json");
+        path
+    }
+
+    pub fn load() -> Self {
+        let path = Self::path();
+        if !path.exists() {
+            debug!("no cache file found at {:?}", path);
+            return Self::default();
+        }
+
+        let file = match std::fs::File::open(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                error!("failed to open cache file at {:?}: {}", path, e);
+                return Self::default();
+            }
+        };
+
+        let reader = std::io::BufReader::new(file);
+        match serde_json::from_reader::<_, HashMap<Uuid, PageComponent>>(reader) {
+            Ok(mut pages) => {
+                debug!("successfully loaded cache from {:?}", path);
+                // pages cached before endpoints were stored are all from Wikipedia
+                for page_component in pages.values_mut() {
+                    let page = &mut page_component.page;
+                    if page.endpoint == Endpoint::default() {
+                        page.endpoint = Endpoint::wikipedia(page.language);
+                    }
+                }
+
+                let index = pages
+                    .iter()
+                    .map(|(uuid, page_component)| (page_key(&page_component.page), *uuid))
+                    .collect();
+                Self {
+                    pages,
+                    index,
+                    prefetched: PrefetchedPages::default(),
+                }
+            }
+            Err(e) => {
+                error!("failed to deserialize cache from {:?}: {}", path, e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let path = Self::path();
+        let file = match std::fs::File::create(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                error!("failed to create cache file at {:?}: {}", path, e);
+                return;
+            }
+        };
+
+        let writer = std::io::BufWriter::new(file);
+        match serde_json::to_writer(writer, &self.pages) {
+            Ok(_) => debug!("successfully saved cache to {:?}", path),
+            Err(e) => error!("failed to serialize and save cache to {:?}: {}", path, e),
+        }
+    }
+
+    /// Returns the cached or prefetched page with this key
+    pub fn get(&self, key: &PageKey) -> Option<Page> {
+        if let Some(uuid) = self.index.get(key) {
+            debug!("found uuid in index: {}", uuid);
+            if let Some(page_component) = self.pages.get(uuid) {
+                debug!("found page component in cache");
+                return Some(page_component.page.clone());
+            }
+        }
+        self.prefetched.get(key).cloned()
+    }
+
+    /// Caches an open page. When another version of the page is cached already, the one that was
+    /// read more recently is kept
+    pub fn insert(&mut self, page_component: PageComponent) {
+        let key = page_key(&page_component.page);
+        if let Some(&existing_uuid) = self.index.get(&key) {
+            let is_newer = self.pages.get(&existing_uuid).map_or(true, |existing| {
+                existing.read_at() <= page_component.read_at()
+            });
+            if !is_newer {
+                return;
+            }
+            self.pages.remove(&existing_uuid);
+        }
+
+        self.index.insert(key, page_component.page.uuid);
+        self.pages.insert(page_component.page.uuid, page_component);
+    }
+
+    /// Keeps a prefetched page until it's opened. It's also found by the requested title, as
+    /// links often use a different spelling than the title of the page
+    pub fn insert_prefetched(&mut self, page: Page, requested_title: String) {
+        let key = page_key(&page);
+        if self.index.contains_key(&key) || self.prefetched.get(&key).is_some() {
+            debug!("prefetched page '{}' is already cached", page.title);
+            return;
+        }
+
+        debug!("keeping prefetched page '{}'", page.title);
+        let requested_key = (requested_title, key.1.clone(), key.2.clone());
+        self.prefetched.insert(vec![key, requested_key], page);
+    }
+
+    /// Returns the component displaying the page. A cached version of the page keeps its reading
+    /// position, its content is replaced when the page was fetched again. New pages are cached
+    /// and the cache is saved
+    pub fn open(&mut self, page: Page, config: Arc<Config>, theme: Arc<Theme>) -> PageComponent {
+        // an opened page is cached like every other page
+        self.prefetched.remove(page.uuid);
+
+        if let Some(mut cached_page) = self.pages.get(&page.uuid).cloned() {
+            debug!("found page in cache by uuid, using cached version");
+            cached_page.rebuild(config, theme);
+            return cached_page;
+        }
+
+        let key = page_key(&page);
+        let existing = self
+            .index
+            .get(&key)
+            .copied()
+            .and_then(|existing_uuid| self.pages.remove(&existing_uuid));
+        let page_component = match existing {
+            Some(mut existing_page) => {
+                // the page was fetched again, its content could have changed
+                debug!(
+                    "found existing page in index with different uuid, updating uuid to {}",
+                    page.uuid
+                );
+                existing_page.page = page;
+                existing_page.rebuild(config, theme);
+                existing_page
+            }
+            None => {
+                debug!("page not in cache or index, creating new PageComponent");
+                PageComponent::new(page, config, theme)
+            }
+        };
+
+        self.index.insert(key, page_component.page.uuid);
+        self.pages
+            .insert(page_component.page.uuid, page_component.clone());
+        self.save();
+        page_component
+    }
+
+    /// All cached pages, without the prefetched ones
+    pub fn pages(&self) -> impl Iterator<Item = &PageComponent> {
+        self.pages.values()
+    }
+}
@@ -1,78 +1,776 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
+use chrono::Datelike;
 use tokio::sync::mpsc::UnboundedSender;
-use tracing::error;
+use tracing::{error, warn};
 use wiki_api::{
+    backlinks::Backlinks,
+    category::CategoryMembers,
+    diff::Diff,
+    feed::{Feed, FeedRequest},
     languages::Language,
     page::{LanguageLink, Link, Page, Property},
+    random::RandomPage,
+    revisions::Revisions,
     search::SearchResult,
-    Endpoint,
+    summary::Summary,
+    Endpoint, Error,
 };
 
 use crate::{
-    action::{Action, PageViewerAction},
+    action::{
+        Action, ActionPacket, BacklinksAction, CategoryAction, HistoryAction, PageFocus,
+        PageViewerAction, PaneSender, SearchAction, SummaryAction,
+    },
+    app::PaneId,
     config::Config,
+    request::{RequestHandle, RequestTracker},
 };
 
 /// Responsible for loading a page
 pub struct PageLoader {
     config: Arc<Config>,
     action_tx: UnboundedSender<Action>,
+
+    /// Requests that end up displaying a page, only the newest one of each pane is kept
+    page_requests: Mutex<HashMap<PaneId, RequestTracker>>,
+    /// Requests filling a popup, only the newest one of each kind is kept
+    feed_requests: RequestTracker,
+    history_requests: RequestTracker,
+    category_requests: RequestTracker,
+    backlinks_requests: RequestTracker,
+    summary_requests: RequestTracker,
+    diff_requests: RequestTracker,
+    /// Summaries loaded in this session, by (api url, title)
+    summaries: Arc<Mutex<HashMap<(String, String), Summary>>>,
 }
 
 impl PageLoader {
     pub fn new(config: Arc<Config>, action_tx: UnboundedSender<Action>) -> Self {
-        Self { config, action_tx }
+        Self {
+            config,
+            action_tx,
+            page_requests: Mutex::default(),
+            feed_requests: RequestTracker::default(),
+            history_requests: RequestTracker::default(),
+            category_requests: RequestTracker::default(),
+            backlinks_requests: RequestTracker::default(),
+            summary_requests: RequestTracker::default(),
+            diff_requests: RequestTracker::default(),
+            summaries: Arc::default(),
+        }
+    }
+
+    pub fn reconfigure(&mut self, config: Arc<Config>) {
+        self.config = config;
+    }
+
+    /// Returns the tracker of the requests displaying a page in the pane
+    fn page_requests(&self, pane: PaneId) -> RequestTracker {
+        self.page_requests
+            .lock()
+            .unwrap()
+            .entry(pane)
+            .or_default()
+            .clone()
     }
 
-    pub fn load_search_result(&self, result: SearchResult) {
-        self.load_page_custom(result.endpoint, result.language, result.title);
+    pub fn load_search_result(&self, result: SearchResult, pane: PaneId) -> RequestHandle {
+        self.load_page(result.title, result.language, result.endpoint, None, pane)
     }
 
-    pub fn load_link(&self, link: Link) {
+    /// Loads the page of an internal link. Other links aren't loaded and return `None`
+    pub fn load_link(&self, link: Link, pane: PaneId) -> Option<RequestHandle> {
         let link_data = match link {
             Link::Internal(data) => data,
-            _ => return,
+            _ => return None,
         };
 
-        self.load_page_custom(link_data.endpoint, link_data.language, link_data.page);
+        let focus = link_data
+            .anchor
+            .map(|anchor| PageFocus::Header(anchor.anchor));
+        Some(self.load_page(
+            link_data.page,
+            link_data.language,
+            link_data.endpoint,
+            focus,
+            pane,
+        ))
     }
 
-    pub fn load_language_link(&self, link: LanguageLink) {
-        self.load_page_custom(link.endpoint, link.language, link.title);
+    pub fn load_language_link(&self, link: LanguageLink, pane: PaneId) -> RequestHandle {
+        self.load_page(link.title, link.language, link.endpoint, None, pane)
     }
 
-    fn load_page_custom(&self, endpoint: Endpoint, language: Language, title: String) {
-        let page_request = Page::builder()
-            .page(title)
+    /// Loads a page and displays it in the pane, scrolled to the focus if one is given. A page
+    /// that is still loading is cancelled. The returned request has to be shown on the
+    /// processing screen of the pane before its result arrives
+    pub fn load_page(
+        &self,
+        title: String,
+        language: Language,
+        endpoint: Endpoint,
+        focus: Option<PageFocus>,
+        pane: PaneId,
+    ) -> RequestHandle {
+        let requests = self.page_requests(pane);
+        let retry = Action::TryLoadPage(title.clone(), language, endpoint.clone(), focus.clone());
+        let handle = requests.start(format!("Loading '{}'", title));
+
+        let mut page_request = Page::builder()
+            .client(handle.client(&self.config.api.client))
+            .page(title.clone())
             .properties(vec![
                 Property::Text,
                 Property::Sections,
                 Property::LangLinks,
+                Property::Categories,
             ])
             .endpoint(endpoint)
             .language(language)
             .redirects(self.config.api.page_redirects);
+        if let Some(variant) = self.config.api.variant(language) {
+            page_request = page_request.variant(variant);
+        }
+
+        let tx = PaneSender::new(self.action_tx.clone(), pane);
+        let request = handle.clone();
+        tokio::spawn(async move {
+            let result = request.run(page_request.fetch()).await;
+            requests.finish(&request);
+
+            match result {
+                Some(Ok(page)) => tx.send(Action::PageViewer(PageViewerAction::DisplayPage(
+                    Box::new(page),
+                    focus,
+                ))),
+                Some(Err(error)) => {
+                    tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                    tx.send(Self::page_failed(&title, &error, retry));
+                }
+                // a newer request or the user cancelled this one
+                None => tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request))),
+            };
+        });
+
+        handle
+    }
+
+    /// Loads a random page from the main namespace and displays it in the pane
+    pub fn load_random_page(
+        &self,
+        endpoint: Endpoint,
+        language: Language,
+        pane: PaneId,
+    ) -> RequestHandle {
+        let requests = self.page_requests(pane);
+        let handle = requests.start("Loading a random page");
+        let random_request = RandomPage::builder()
+            .client(handle.client(&self.config.api.client))
+            .endpoint(endpoint)
+            .language(language);
+
+        let tx = PaneSender::new(self.action_tx.clone(), pane);
+        let request = handle.clone();
+        tokio::spawn(async move {
+            let result = request.run(random_request.fetch()).await;
+            requests.finish(&request);
 
+            let Some(result) = result else {
+                tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                return;
+            };
+
+            match result.map(|pages| pages.into_iter().next()) {
+                Ok(Some(page)) => tx.send(Action::TryLoadPage(
+                    page.title,
+                    page.language,
+                    page.endpoint,
+                    None,
+                )),
+                Ok(None) => {
+                    tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                    tx.send(Action::PopupMessage(
+                        "Information".to_string(),
+                        "The server didn't return a random page".to_string(),
+                    ));
+                }
+                Err(error) => {
+                    tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                    tx.send(Self::request_failed(
+                        "Unable to fetch a random page",
+                        &error,
+                        Action::LoadRandomPage,
+                    ));
+                }
+            };
+        });
+
+        handle
+    }
+
+    /// Loads today's featured article and displays it in the pane
+    pub fn load_featured_page(
+        &self,
+        endpoint: Endpoint,
+        language: Language,
+        pane: PaneId,
+    ) -> RequestHandle {
+        let requests = self.page_requests(pane);
+        let handle = requests.start("Loading today's featured article");
+        let feed_request = self
+            .todays_feed(endpoint, language)
+            .client(handle.client(&self.config.api.client));
+
+        let tx = PaneSender::new(self.action_tx.clone(), pane);
+        let request = handle.clone();
+        tokio::spawn(async move {
+            let result = request.run(feed_request.fetch()).await;
+            requests.finish(&request);
+
+            let Some(result) = result else {
+                tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                return;
+            };
+
+            match result {
+                Ok(Feed {
+                    featured: Some(article),
+                    language,
+                    endpoint,
+                    ..
+                }) => tx.send(Action::TryLoadPage(article.title, language, endpoint, None)),
+                Ok(_) => {
+                    tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                    tx.send(Action::PopupMessage(
+                        "Information".to_string(),
+                        "There is no featured article for today".to_string(),
+                    ));
+                }
+                Err(error) => {
+                    tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                    tx.send(Self::request_failed(
+                        "Unable to fetch the featured article",
+                        &error,
+                        Action::LoadFeaturedPage,
+                    ));
+                }
+            };
+        });
+
+        handle
+    }
+
+    /// Loads today's feed and displays it once it's available
+    pub fn load_feed(&self, endpoint: Endpoint, language: Language) {
+        let handle = self.feed_requests.start("Loading today's feed");
+        let feed_request = self
+            .todays_feed(endpoint, language)
+            .client(handle.client(&self.config.api.client));
+
+        let requests = self.feed_requests.clone();
         let tx = self.action_tx.clone();
         tokio::spawn(async move {
-            tx.send(Action::SwitchContextPage).unwrap();
-            tx.send(Action::EnterProcessing).unwrap();
+            let result = handle.run(feed_request.fetch()).await;
+            requests.finish(&handle);
 
-            match page_request.fetch().await {
-                Ok(page) => tx
-                    .send(Action::PageViewer(PageViewerAction::DisplayPage(page)))
+            match result {
+                // a newer request cancelled this one
+                None => {}
+                Some(Ok(feed)) => tx.send(Action::ShowFeed(feed)).unwrap(),
+                Some(Err(error)) => tx
+                    .send(Self::request_failed(
+                        "Unable to fetch today's feed",
+                        &error,
+                        Action::LoadFeed,
+                    ))
                     .unwrap(),
-                Err(error) => {
-                    let error = error.context("Unable to fetch the page");
-                    tx.send(Action::PageViewer(PageViewerAction::ExitLoading))
+            }
+        });
+    }
+
+    /// Loads today's featured article for the dashboard. Without a connection the dashboard
+    /// just leaves it out, so failures are only logged
+    pub fn load_dashboard_featured(&self, endpoint: Endpoint, language: Language) {
+        let feed_request = self.todays_feed(endpoint, language);
+
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            match feed_request.fetch().await {
+                Ok(feed) => tx
+                    .send(Action::Search(SearchAction::ShowFeatured(feed)))
+                    .unwrap(),
+                Err(error) => warn!(
+                    "unable to fetch the featured article for the dashboard: {:?}",
+                    error
+                ),
+            }
+        });
+    }
+
+    /// Loads a batch of revisions of a page. When `continue_from` is set, the batch continues
+    /// where the previous one ended
+    pub fn load_history(
+        &self,
+        title: String,
+        language: Language,
+        endpoint: Endpoint,
+        continue_from: Option<String>,
+    ) {
+        let retry = Action::History(HistoryAction::LoadHistory(
+            title.clone(),
+            language,
+            endpoint.clone(),
+            continue_from.clone(),
+        ));
+
+        let handle = self
+            .history_requests
+            .start(format!("Loading the history of '{}'", title));
+        let mut revisions_request = Revisions::builder()
+            .client(handle.client(&self.config.api.client))
+            .title(title)
+            .endpoint(endpoint)
+            .language(language)
+            .limit(50);
+
+        if let Some(continue_from) = continue_from {
+            revisions_request = revisions_request.continue_from(continue_from);
+        }
+
+        let requests = self.history_requests.clone();
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            let result = handle.run(revisions_request.fetch()).await;
+            requests.finish(&handle);
+
+            match result {
+                None => {}
+                Some(Ok(revisions)) => tx
+                    .send(Action::History(HistoryAction::FinishHistory(revisions)))
+                    .unwrap(),
+                Some(Err(error)) => tx
+                    .send(Self::request_failed(
+                        "Unable to fetch the page history",
+                        &error,
+                        retry,
+                    ))
+                    .unwrap(),
+            }
+        });
+    }
+
+    /// Loads a batch of members of a category. When `continue_from` is set, the batch continues
+    /// where the previous one ended
+    pub fn load_category_members(
+        &self,
+        category: String,
+        language: Language,
+        endpoint: Endpoint,
+        continue_from: Option<String>,
+    ) {
+        let retry = Action::Category(CategoryAction::LoadMembers(
+            category.clone(),
+            language,
+            endpoint.clone(),
+            continue_from.clone(),
+        ));
+
+        let handle = self
+            .category_requests
+            .start(format!("Loading the members of '{}'", category));
+        let mut members_request = CategoryMembers::builder()
+            .client(handle.client(&self.config.api.client))
+            .category(category)
+            .endpoint(endpoint)
+            .language(language)
+            .limit(50);
+
+        if let Some(continue_from) = continue_from {
+            members_request = members_request.continue_from(continue_from);
+        }
+
+        let requests = self.category_requests.clone();
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            let result = handle.run(members_request.fetch()).await;
+            requests.finish(&handle);
+
+            match result {
+                None => {}
+                Some(Ok(members)) => tx
+                    .send(Action::Category(CategoryAction::FinishMembers(members)))
+                    .unwrap(),
+                Some(Err(error)) => tx
+                    .send(Self::request_failed(
+                        "Unable to fetch the category members",
+                        &error,
+                        retry,
+                    ))
+                    .unwrap(),
+            }
+        });
+    }
+
+    /// Loads a batch of pages linking to a page, including the pages linking through a redirect.
+    /// When `continue_from` is set, the batch continues where the previous one ended
+    pub fn load_backlinks(
+        &self,
+        title: String,
+        language: Language,
+        endpoint: Endpoint,
+        continue_from: Option<String>,
+    ) {
+        let retry = Action::Backlinks(BacklinksAction::LoadBacklinks(
+            title.clone(),
+            language,
+            endpoint.clone(),
+            continue_from.clone(),
+        ));
+
+        let handle = self
+            .backlinks_requests
+            .start(format!("Loading the backlinks of '{}'", title));
+        let mut backlinks_request = Backlinks::builder()
+            .client(handle.client(&self.config.api.client))
+            .title(title)
+            .endpoint(endpoint)
+            .language(language)
+            .redirects(true)
+            .limit(50);
+
+        if let Some(continue_from) = continue_from {
+            backlinks_request = backlinks_request.continue_from(continue_from);
+        }
+
+        let requests = self.backlinks_requests.clone();
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            let result = handle.run(backlinks_request.fetch()).await;
+            requests.finish(&handle);
+
+            match result {
+                None => {}
+                Some(Ok(backlinks)) => tx
+                    .send(Action::Backlinks(BacklinksAction::FinishBacklinks(
+                        backlinks,
+                    )))
+                    .unwrap(),
+                Some(Err(error)) => tx
+                    .send(Self::request_failed(
+                        "Unable to fetch the backlinks",
+                        &error,
+                        retry,
+                    ))
+                    .unwrap(),
+            }
+        });
+    }
+
+    /// Loads the summary of a page. Summaries are cached for the rest of the session
+    pub fn load_summary(&self, title: String, language: Language, endpoint: Endpoint) {
+        let key = (endpoint.api_url().to_string(), title.clone());
+        if let Some(summary) = self.summaries.lock().unwrap().get(&key) {
+            self.action_tx
+                .send(Action::Summary(SummaryAction::FinishSummary(
+                    summary.clone(),
+                )))
+                .unwrap();
+            return;
+        }
+
+        let retry = Action::Summary(SummaryAction::LoadSummary(
+            title.clone(),
+            language,
+            endpoint.clone(),
+        ));
+
+        let handle = self
+            .summary_requests
+            .start(format!("Loading the summary of '{}'", title));
+        let summary_request = Summary::builder()
+            .client(handle.client(&self.config.api.client))
+            .title(title.clone())
+            .endpoint(endpoint)
+            .language(language);
+
+        let requests = self.summary_requests.clone();
+        let summaries = self.summaries.clone();
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            let result = handle.run(summary_request.fetch()).await;
+            requests.finish(&handle);
+
+            match result {
+                None => {}
+                Some(Ok(summary)) => {
+                    summaries.lock().unwrap().insert(key, summary.clone());
+                    tx.send(Action::Summary(SummaryAction::FinishSummary(summary)))
+                        .unwrap();
+                }
+                Some(Err(error)) => {
+                    tx.send(Action::Summary(SummaryAction::FailSummary(title.clone())))
                         .unwrap();
-                    tx.send(Action::PopupError(error.to_string())).unwrap();
-                    error!("{:?}", error);
+                    tx.send(Self::request_failed(
+                        &format!("Unable to fetch the summary of '{}'", title),
+                        &error,
+                        retry,
+                    ))
+                    .unwrap();
+                }
+            }
+        });
+    }
+
+    /// Loads an old revision of a page and displays it in the pane. Old revisions are displayed
+    /// read-only and never cached
+    pub fn load_revision(
+        &self,
+        title: String,
+        revision: usize,
+        language: Language,
+        endpoint: Endpoint,
+        pane: PaneId,
+    ) -> RequestHandle {
+        let requests = self.page_requests(pane);
+        let retry = Action::LoadRevision(title.clone(), revision, language, endpoint.clone());
+        let handle = requests.start(format!("Loading revision {} of '{}'", revision, title));
+
+        let page_request = Page::builder()
+            .client(handle.client(&self.config.api.client))
+            .page(title)
+            .old_revision(revision)
+            .properties(vec![
+                Property::Text,
+                Property::Sections,
+                Property::Categories,
+            ])
+            .endpoint(endpoint)
+            .language(language);
+
+        let tx = PaneSender::new(self.action_tx.clone(), pane);
+        let request = handle.clone();
+        tokio::spawn(async move {
+            let result = request.run(page_request.fetch()).await;
+            requests.finish(&request);
+
+            match result {
+                Some(Ok(page)) => tx.send(Action::PageViewer(PageViewerAction::DisplayPage(
+                    Box::new(page),
+                    None,
+                ))),
+                Some(Err(error)) => {
+                    tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                    tx.send(Self::request_failed(
+                        &format!("Unable to fetch revision {}", revision),
+                        &error,
+                        retry,
+                    ));
+                }
+                None => tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request))),
+            };
+        });
+
+        handle
+    }
+
+    /// Fetches the current version of the page and compares it with the given version. When the
+    /// page has changed, the current version is displayed in the pane together with the changes.
+    /// Like [`PageLoader::load_page`], the returned request has to be shown on the processing
+    /// screen of the pane
+    pub fn load_changes(&self, page: Page, pane: PaneId) -> RequestHandle {
+        let requests = self.page_requests(pane);
+        let handle = requests.start(format!("Loading the changes to '{}'", page.title));
+
+        let mut page_request = Page::builder()
+            .client(handle.client(&self.config.api.client))
+            .page(page.title.clone())
+            .properties(vec![
+                Property::Text,
+                Property::Sections,
+                Property::LangLinks,
+                Property::Categories,
+            ])
+            .endpoint(page.endpoint.clone())
+            .language(page.language)
+            .redirects(self.config.api.page_redirects);
+        if let Some(ref variant) = page.variant {
+            page_request = page_request.variant(variant.clone());
+        }
+
+        let tx = PaneSender::new(self.action_tx.clone(), pane);
+        let request = handle.clone();
+        tokio::spawn(async move {
+            let result = request.run(page_request.fetch()).await;
+            requests.finish(&request);
+
+            let current = match result {
+                Some(Ok(current)) if current.revision_id != page.revision_id => current,
+                Some(Ok(_)) => {
+                    tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                    tx.send(Action::PopupMessage(
+                        "Information".to_string(),
+                        format!("'{}' hasn't changed since you last read it", page.title),
+                    ));
+                    return;
+                }
+                Some(Err(error)) => {
+                    tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                    tx.send(Self::request_failed(
+                        &format!("Unable to fetch the current version of '{}'", page.title),
+                        &error,
+                        Action::PageViewer(PageViewerAction::ShowChanges),
+                    ));
+                    return;
+                }
+                // a newer request or the user cancelled this one
+                None => {
+                    tx.send(Action::PageViewer(PageViewerAction::ExitLoading(request)));
+                    return;
                 }
             };
 
-            tx.send(Action::EnterNormal).unwrap();
+            let diff = Diff::between(&page.content, &current.content);
+            let title = format!("Changes to '{}'", page.title);
+            tx.send(Action::PageViewer(PageViewerAction::DisplayPage(
+                Box::new(current),
+                None,
+            )));
+            tx.send(Action::ShowDiff(title, diff));
+        });
+
+        handle
+    }
+
+    /// Loads the diff between two revisions and displays it once it's available
+    pub fn load_diff(&self, title: String, from: usize, to: usize, endpoint: Endpoint) {
+        let retry = Action::CompareRevisions(title.clone(), from, to, endpoint.clone());
+
+        let handle = self
+            .diff_requests
+            .start(format!("Comparing revision {} to {}", from, to));
+        let compare_request = Diff::compare()
+            .client(handle.client(&self.config.api.client))
+            .from_revision(from)
+            .to_revision(to)
+            .endpoint(endpoint);
+
+        let requests = self.diff_requests.clone();
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            let result = handle.run(compare_request.fetch()).await;
+            requests.finish(&handle);
+
+            match result {
+                None => {}
+                Some(Ok(diff)) => tx
+                    .send(Action::ShowDiff(
+                        format!("{} (revision {} to {})", title, from, to),
+                        diff,
+                    ))
+                    .unwrap(),
+                Some(Err(error)) => tx
+                    .send(Self::request_failed(
+                        "Unable to compare the revisions",
+                        &error,
+                        retry,
+                    ))
+                    .unwrap(),
+            }
         });
     }
+
+    /// Builds the popup telling the user that a request failed. Requests that failed because of
+    /// the network or the server can be retried by sending `retry` again
+    pub fn request_failed(context: &str, error: &Error, retry: Action) -> Action {
+        error!("{}: {:?}", context, error);
+
+        let message = match error {
+            Error::Request { .. } => {
+                "Could not reach the server, check your internet connection".to_string()
+            }
+            Error::RateLimited {
+                retry_after: Some(seconds),
+            } => format!(
+                "The server is rate limiting the requests, wait {} seconds before trying again",
+                seconds
+            ),
+            Error::RateLimited { retry_after: None } => {
+                "The server is rate limiting the requests, wait a moment before trying again"
+                    .to_string()
+            }
+            Error::Status { status } if status.is_server_error() => {
+                format!("The server is currently unavailable ({})", status)
+            }
+            Error::Status { status } => format!("The server rejected the request ({})", status),
+            Error::Api { code, info } => format!("The wiki returned an error: {} ({})", info, code),
+            Error::MissingPage { title } => format!("The page '{}' doesn't exist", title),
+            Error::Unsupported { what, site } => {
+                format!(
+                    "The {} is only available on Wikipedia, not on {}",
+                    what, site
+                )
+            }
+            Error::InvalidUrl { .. } => {
+                format!(
+                    "The url of the wiki is invalid, check your config ({})",
+                    error
+                )
+            }
+            Error::Client { .. } => format!(
+                "The http client could not be created, check your config ({})",
+                error
+            ),
+            Error::Json { .. } | Error::MissingField { .. } | Error::Deserialize { .. } => {
+                format!("The response of the server could not be read ({})", error)
+            }
+        };
+
+        if error.is_retryable() {
+            return Action::PopupDialog(
+                "Error".to_string(),
+                format!("{}\n{}\n\nTry again?", context, message),
+                Box::new(ActionPacket::single(retry)),
+            );
+        }
+
+        Action::PopupError(format!("{}\n{}", context, message))
+    }
+
+    /// Like [`PageLoader::request_failed`], but offers to search for the title instead when the
+    /// page doesn't exist
+    pub fn page_failed(title: &str, error: &Error, retry: Action) -> Action {
+        if !error.is_missing_page() {
+            return Self::request_failed(
+                &format!("Unable to fetch the page '{}'", title),
+                error,
+                retry,
+            );
+        }
+
+        error!("the page '{}' doesn't exist: {:?}", title, error);
+        Action::PopupDialog(
+            "Page not found".to_string(),
+            format!(
+                "The page '{}' doesn't exist\n\nSearch for '{}' instead?",
+                title, title
+            ),
+            Box::new(
+                ActionPacket::single(Action::SwitchContextSearch)
+                    .action(Action::Search(SearchAction::StartSearch(title.to_string()))),
+            ),
+        )
+    }
+
+    fn todays_feed(&self, endpoint: Endpoint, language: Language) -> FeedRequest {
+        let today = chrono::Local::now().date_naive();
+        Feed::builder()
+            .client(self.config.api.client.clone())
+            .date(today.year() as u16, today.month() as u8, today.day() as u8)
+            .endpoint(endpoint)
+            .language(language)
+    }
 }
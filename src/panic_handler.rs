@@ -12,7 +12,7 @@ pub fn initialize_panic_handler() -> Result<()> {
     eyre_hook.install()?;
 
     std::panic::set_hook(Box::new(move |panic_info| {
-        match Tui::new() {
+        match Tui::new(false) {
             Ok(mut tui) => {
                 if let Err(error) = tui.exit() {
                     error!("unable to exit terminal: {error:?}");
@@ -0,0 +1,66 @@
+//! Keys typed for a binding that is not complete yet: the count before a binding (the `5` in
+//! `5 j`) and the first keys of a key sequence (the first `g` of `g g`)
+
+use std::time::{Duration, Instant};
+
+use crate::config::Binding;
+
+/// Highest count that can be typed, larger counts are cut off
+pub const MAX_COUNT: u16 = 999;
+
+#[derive(Default)]
+pub struct PendingKeys {
+    count: Option<u16>,
+    keys: Vec<Binding>,
+    /// When the last key of the sequence was typed
+    typed: Option<Instant>,
+}
+
+impl PendingKeys {
+    /// The first keys of the key sequence that is being typed
+    pub fn keys(&self) -> &[Binding] {
+        &self.keys
+    }
+
+    pub fn set_keys(&mut self, keys: Vec<Binding>) {
+        self.keys = keys;
+        self.typed = Some(Instant::now());
+    }
+
+    /// Adds the digit to the count. Returns false when the character doesn't belong to a count:
+    /// counts can't start with `0` and can't be typed in the middle of a key sequence
+    pub fn push_digit(&mut self, c: char) -> bool {
+        let digit = match c.to_digit(10) {
+            Some(0) if self.count.is_none() => return false,
+            Some(digit) if self.keys.is_empty() => digit as u16,
+            _ => return false,
+        };
+
+        self.count = Some((self.count.unwrap_or_default() * 10 + digit).min(MAX_COUNT));
+        true
+    }
+
+    /// Removes the keys and returns the count
+    pub fn finish(&mut self) -> Option<u16> {
+        self.keys.clear();
+        self.typed = None;
+        self.count.take()
+    }
+
+    /// Whether a key sequence is being typed and no key was typed for longer than the timeout
+    pub fn is_expired(&self, timeout: Duration) -> bool {
+        !self.keys.is_empty() && self.typed.is_some_and(|typed| typed.elapsed() >= timeout)
+    }
+
+    /// Formats the count and keys for the status bar, e.g. "5 g"
+    pub fn indicator(&self) -> Option<String> {
+        let keys = self
+            .count
+            .iter()
+            .map(|count| count.to_string())
+            .chain(self.keys.iter().map(|key| key.to_string()))
+            .collect::<Vec<String>>();
+
+        (!keys.is_empty()).then(|| keys.join(" "))
+    }
+}
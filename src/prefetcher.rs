@@ -0,0 +1,146 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{sync::Semaphore, time::Instant};
+use tracing::debug;
+use wiki_api::{
+    page::{link_data::InternalData, Page, Property},
+    Progress,
+};
+
+use crate::{
+    action::{Action, PageViewerAction, PaneSender},
+    config::Config,
+    request::RequestTracker,
+};
+
+/// Loads pages in the background so they are already cached when they are opened. Prefetching
+/// is best effort, failed requests are only logged
+pub struct Prefetcher {
+    config: Arc<Config>,
+    /// Prefetched pages are cached by the page viewer that asked for them
+    action_tx: PaneSender,
+
+    /// Prefetches of the current link or section, prefetching other links cancels them. All pages
+    /// of one call share a handle, so it's kept until the next call instead of being finished
+    requests: RequestTracker,
+    permits: Arc<Semaphore>,
+    rate_limit: Arc<RateLimit>,
+}
+
+impl Prefetcher {
+    pub fn new(config: Arc<Config>, action_tx: PaneSender) -> Self {
+        let prefetch = &config.page.prefetch;
+        Self {
+            permits: Arc::new(Semaphore::new(prefetch.max_concurrent.max(1))),
+            rate_limit: Arc::new(RateLimit::new(prefetch.max_rate * 1024)),
+            requests: RequestTracker::default(),
+
+            config,
+            action_tx,
+        }
+    }
+
+    /// Uses the changed config for the next prefetches. The limits keep their values
+    pub fn reconfigure(&mut self, config: Arc<Config>) {
+        self.config = config;
+    }
+
+    /// Prefetches the pages once the configured delay has passed. Pages of an earlier call that
+    /// are still waiting or loading are cancelled
+    pub fn prefetch(&self, links: Vec<InternalData>) {
+        if links.is_empty() {
+            self.requests.cancel();
+            return;
+        }
+
+        let handle = self
+            .requests
+            .start(format!("Prefetching {} pages", links.len()));
+        let delay = Duration::from_millis(self.config.page.prefetch.delay);
+
+        for link in links {
+            let progress = Progress::default();
+            let mut page_request = Page::builder()
+                .client(self.config.api.client.with_progress(progress.clone()))
+                .page(link.page.clone())
+                .properties(vec![
+                    Property::Text,
+                    Property::Sections,
+                    Property::LangLinks,
+                    Property::Categories,
+                ])
+                .endpoint(link.endpoint.clone())
+                .language(link.language)
+                .redirects(self.config.api.page_redirects);
+            if let Some(variant) = self.config.api.variant(link.language) {
+                page_request = page_request.variant(variant);
+            }
+
+            let handle = handle.clone();
+            let permits = self.permits.clone();
+            let rate_limit = self.rate_limit.clone();
+            let tx = self.action_tx.clone();
+
+            tokio::spawn(async move {
+                let result = handle
+                    .run(async {
+                        tokio::time::sleep(delay).await;
+                        let _permit = permits.acquire().await.unwrap();
+                        rate_limit.wait().await;
+
+                        let result = page_request.fetch().await;
+                        rate_limit.consume(progress.bytes_received());
+                        result
+                    })
+                    .await;
+
+                match result {
+                    None => debug!("cancelled prefetching '{}'", link.page),
+                    Some(Ok(page)) => {
+                        debug!("prefetched '{}'", link.page);
+                        tx.send(Action::PageViewer(PageViewerAction::CachePage(
+                            Box::new(page),
+                            link.page,
+                        )));
+                    }
+                    Some(Err(error)) => debug!("failed prefetching '{}': {}", link.page, error),
+                }
+            });
+        }
+    }
+}
+
+/// Spreads the received bytes over time so the average download rate stays below the limit
+struct RateLimit {
+    /// Bytes per second, `0` disables the limit
+    rate: u64,
+    /// When the next request may start
+    next: Mutex<Instant>,
+}
+
+impl RateLimit {
+    fn new(rate: u64) -> Self {
+        Self {
+            rate,
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    async fn wait(&self) {
+        let next = *self.next.lock().unwrap();
+        tokio::time::sleep_until(next).await;
+    }
+
+    fn consume(&self, bytes: u64) {
+        if self.rate == 0 {
+            return;
+        }
+
+        let mut next = self.next.lock().unwrap();
+        *next =
+            (*next).max(Instant::now()) + Duration::from_secs_f64(bytes as f64 / self.rate as f64);
+    }
+}
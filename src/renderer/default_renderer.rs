@@ -1,8 +1,12 @@
+use std::collections::HashSet;
+
 use ratatui::style::{Color, Modifier, Style};
 use textwrap::wrap_algorithms::{wrap_optimal_fit, Penalties};
 use tracing::warn;
 use wiki_api::{
+    dictionary::{Definition, Dictionary},
     document::{Data, Document, HeaderKind, Node, UnsupportedElement},
+    languages::Direction,
     page::Link,
 };
 
@@ -18,7 +22,10 @@ const BLOCKQUOTE_PADDING: u8 = 4;
 const LIST_PADDING: u8 = 1;
 const LIST_PREFIX: char = '-';
 
-struct Renderer {
+const DEFINITION_PADDING: u8 = 2;
+const EXAMPLE_PREFIX: char = '>';
+
+struct Renderer<'a> {
     rendered_lines: Vec<Vec<Word>>,
     links: Vec<(usize, usize)>,
 
@@ -29,10 +36,18 @@ struct Renderer {
 
     left_padding: u8,
     prefix: Option<char>,
+
+    /// Anchors of the headers whose sections are folded
+    folded: &'a HashSet<String>,
 }
 
-impl<'a> Renderer {
-    fn render_document(document: &'a Document, width: u16) -> RenderedDocument {
+impl<'a> Renderer<'a> {
+    fn render_document(
+        document: &'a Document,
+        width: u16,
+        folded: &'a HashSet<String>,
+        direction: Direction,
+    ) -> RenderedDocument {
         if document.nodes.is_empty() {
             warn!("document contains no nodes, aborting the render");
             return RenderedDocument {
@@ -41,7 +56,13 @@ impl<'a> Renderer {
             };
         }
 
-        let mut renderer = Renderer {
+        let mut renderer = Renderer::new(width, folded);
+        renderer.render_node(document.nth(0).unwrap());
+        renderer.finish(direction)
+    }
+
+    fn new(width: u16, folded: &'a HashSet<String>) -> Self {
+        Renderer {
             rendered_lines: Vec::new(),
             links: Vec::new(),
 
@@ -52,16 +73,130 @@ impl<'a> Renderer {
 
             left_padding: 0,
             prefix: None,
-        };
 
-        renderer.render_node(document.nth(0).unwrap());
+            folded,
+        }
+    }
+
+    fn finish(mut self, direction: Direction) -> RenderedDocument {
+        self.clear_line();
+        let width = self.width;
+        let lines = match direction {
+            Direction::LeftToRight => self.rendered_lines,
+            Direction::RightToLeft => self
+                .rendered_lines
+                .into_iter()
+                .map(|line| mirror_line(line, width))
+                .collect(),
+        };
 
         RenderedDocument {
-            lines: renderer.rendered_lines,
-            links: renderer.links,
+            lines,
+            links: self.links,
+        }
+    }
+
+    /// Renders the entries of the dictionary compactly: the language, the pronunciation, the
+    /// numbered definitions of each part of speech with their examples and the etymology
+    fn render_dictionary(&mut self, document: &'a Document, dictionary: &Dictionary) {
+        let nodes = |indices: &[usize]| -> Vec<Node<'a>> {
+            indices.iter().filter_map(|&x| document.nth(x)).collect()
+        };
+
+        for entry in dictionary.entries.iter() {
+            if let Some(header) = document.nth(entry.header) {
+                self.render_header(header);
+            }
+
+            for item in nodes(&entry.pronunciation) {
+                self.render_marker(&LIST_PREFIX.to_string());
+                self.render_children(item);
+                self.clear_line();
+            }
+            if !entry.pronunciation.is_empty() {
+                self.ensure_empty_line();
+            }
+
+            for part_of_speech in entry.parts_of_speech.iter() {
+                self.add_modifier(Modifier::BOLD);
+                self.set_text_fg(Color::Red);
+                self.render_string(&part_of_speech.name, part_of_speech.header);
+                self.reset_text_fg();
+                self.remove_modifier(Modifier::BOLD);
+                self.add_whitespace();
+                if let Some(headword) = part_of_speech.headword.and_then(|x| document.nth(x)) {
+                    self.render_children(headword);
+                }
+                self.clear_line();
+
+                self.add_n_padding(DEFINITION_PADDING);
+                for (n, definition) in part_of_speech.definitions.iter().enumerate() {
+                    self.render_definition(document, definition, &format!("{}.", n + 1));
+                }
+                self.remove_n_padding(DEFINITION_PADDING);
+                self.ensure_empty_line();
+            }
+
+            if !entry.etymology.is_empty() {
+                self.add_modifier(Modifier::ITALIC);
+                self.set_text_fg(Color::DarkGray);
+                self.render_string("Etymology", entry.header);
+                self.reset_text_fg();
+                self.remove_modifier(Modifier::ITALIC);
+                self.clear_line();
+
+                self.add_n_padding(DEFINITION_PADDING);
+                for paragraph in nodes(&entry.etymology) {
+                    self.render_children(paragraph);
+                    self.clear_line();
+                }
+                self.remove_n_padding(DEFINITION_PADDING);
+                self.ensure_empty_line();
+            }
         }
     }
 
+    fn render_definition(&mut self, document: &'a Document, definition: &Definition, marker: &str) {
+        let padding = self.render_marker(marker);
+        self.add_n_padding(padding);
+
+        for node in definition.content.iter().filter_map(|&x| document.nth(x)) {
+            self.render_node(node);
+        }
+        self.clear_line();
+
+        self.set_prefix(EXAMPLE_PREFIX);
+        self.set_text_fg(Color::DarkGray);
+        for example in definition.examples.iter().filter_map(|&x| document.nth(x)) {
+            self.render_children(example);
+            self.clear_line();
+        }
+        self.reset_text_fg();
+        self.reset_prefix();
+
+        for (n, subdefinition) in definition.subdefinitions.iter().enumerate() {
+            let letter = (b'a' + (n % 26) as u8) as char;
+            self.render_definition(document, subdefinition, &format!("{}.", letter));
+        }
+
+        self.remove_n_padding(padding);
+    }
+
+    /// Starts a new line with the marker of a list item. Returns the padding of the wrapped lines
+    fn render_marker(&mut self, marker: &str) -> u8 {
+        self.clear_line();
+        let content = format!("{}{}", " ".repeat(self.left_padding as usize), marker);
+        self.current_line.push(Word {
+            index: usize::MAX,
+            width: content.chars().count() as f64,
+            content,
+            style: Style::default(),
+            whitespace_width: 1.0,
+            penalty_width: 0.0,
+        });
+        marker.chars().count() as u8 + 1
+    }
+
     /// Returns whether the last word of the current line is a whitespace
     fn is_last_whitespace(&self) -> bool {
         self.current_line
@@ -289,11 +424,49 @@ impl<'a> Renderer {
 
         self.ensure_empty_line();
 
-        self.render_children(node);
+        match section_header(node) {
+            Some((header, anchor)) if self.folded.contains(anchor) => {
+                self.render_folded_section(node, header)
+            }
+            _ => self.render_children(node),
+        }
 
         self.ensure_empty_line();
     }
 
+    /// Renders the header of the section and the amount of lines that are folded instead of the
+    /// content. Subsections are folded together with the section
+    fn render_folded_section(&mut self, node: Node<'a>, header: Node<'a>) {
+        let mut children = node.children();
+        for child in children.by_ref() {
+            self.render_node(child);
+            // the header can be wrapped in a division
+            if is_ancestor_or_self(child, header) {
+                break;
+            }
+        }
+
+        // the content is rendered to count its lines and then thrown away
+        self.clear_line();
+        let (n_lines, n_links) = (self.rendered_lines.len(), self.links.len());
+        for child in children {
+            self.render_node(child);
+        }
+        self.clear_line();
+        let folded_lines = self.rendered_lines.len() - n_lines;
+        self.rendered_lines.truncate(n_lines);
+        self.links.truncate(n_links);
+
+        self.ensure_empty_line();
+        self.add_modifier(Modifier::ITALIC);
+        self.set_text_fg(Color::DarkGray);
+        // the marker belongs to the header, so it's found together with it
+        self.render_string(&format!("+ {} lines folded", folded_lines), header.index());
+        self.reset_text_fg();
+        self.remove_modifier(Modifier::ITALIC);
+        self.clear_line();
+    }
+
     fn render_header(&mut self, node: Node<'a>) {
         let Data::Header { kind, .. } = node.data() else {
             warn!("expected header data, got other data");
@@ -602,6 +775,367 @@ impl<'a> Renderer {
     }
 }
 
-pub fn render_document(document: &Document, width: u16) -> RenderedDocument {
-    Renderer::render_document(document, width)
+fn is_ancestor_or_self(node: Node<'_>, descendant: Node<'_>) -> bool {
+    let mut current = Some(descendant);
+    while let Some(parent) = current {
+        if parent.index() == node.index() {
+            return true;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Returns the header of the section and its anchor. The headers of subsections don't count, so
+/// sections without a header (like the introduction) have none
+pub fn section_header(section: Node<'_>) -> Option<(Node<'_>, &String)> {
+    let header = section
+        .descendants()
+        .find(|node| matches!(node.data(), Data::Header { .. }))?;
+
+    // the nearest section around the header has to be this one
+    let mut parent = header.parent();
+    while let Some(node) = parent {
+        if matches!(node.data(), Data::Section { .. }) {
+            break;
+        }
+        parent = node.parent();
+    }
+    if parent.map(|node| node.index()) != Some(section.index()) {
+        return None;
+    }
+
+    match header.data() {
+        Data::Header { id, .. } => Some((header, id)),
+        _ => None,
+    }
+}
+
+/// Whether a word is written left-to-right, right-to-left or has no letters at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WordDirection {
+    LeftToRight,
+    RightToLeft,
+    Neutral,
+}
+
+/// Returns whether the character belongs to a right-to-left script (Hebrew, Arabic, Syriac,
+/// Thaana, N'Ko and their presentation forms)
+fn is_rtl_char(c: char) -> bool {
+    matches!(c,
+        '\u{0590}'..='\u{08FF}'
+        | '\u{FB1D}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}'
+        | '\u{10800}'..='\u{10FFF}'
+        | '\u{1E800}'..='\u{1EFFF}')
+}
+
+/// Returns whether the character is a combining mark that belongs to the character before it,
+/// like the vowel points of Hebrew and Arabic
+fn is_mark(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'
+        | '\u{0591}'..='\u{05BD}'
+        | '\u{05BF}'
+        | '\u{05C1}'..='\u{05C2}'
+        | '\u{05C4}'..='\u{05C5}'
+        | '\u{05C7}'
+        | '\u{0610}'..='\u{061A}'
+        | '\u{064B}'..='\u{065F}'
+        | '\u{0670}'
+        | '\u{06D6}'..='\u{06DC}'
+        | '\u{06DF}'..='\u{06E4}'
+        | '\u{06E7}'..='\u{06E8}'
+        | '\u{06EA}'..='\u{06ED}'
+        | '\u{200C}'..='\u{200D}')
+}
+
+/// The direction of a word is the one of its first letter
+fn word_direction(content: &str) -> WordDirection {
+    content
+        .chars()
+        .find_map(|c| {
+            if is_rtl_char(c) {
+                Some(WordDirection::RightToLeft)
+            } else if c.is_alphabetic() {
+                Some(WordDirection::LeftToRight)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(WordDirection::Neutral)
+}
+
+/// Returns the bracket facing the other way, brackets are mirrored in right-to-left text
+fn mirror_char(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '«' => '»',
+        '»' => '«',
+        _ => c,
+    }
+}
+
+/// Reverses right-to-left text to be displayed from left to right. Marks stay after their
+/// character and runs of numbers and left-to-right letters keep their order
+fn reverse_rtl(content: &str) -> String {
+    // split the text into characters together with their marks
+    let mut clusters: Vec<String> = Vec::new();
+    for c in content.chars() {
+        match clusters.last_mut() {
+            Some(cluster) if is_mark(c) => cluster.push(c),
+            _ => clusters.push(c.to_string()),
+        }
+    }
+
+    let first_char = |idx: usize| clusters.get(idx).and_then(|cluster| cluster.chars().next());
+    let is_digit = |idx: usize| first_char(idx).is_some_and(|c| c.is_ascii_digit());
+    let keeps_order = |idx: usize| match first_char(idx) {
+        // separators inside a number, like in 1.5 or 10,000
+        Some('.' | ',' | ':') => idx > 0 && is_digit(idx - 1) && is_digit(idx + 1),
+        Some(c) => c.is_alphanumeric() && !is_rtl_char(c),
+        None => false,
+    };
+
+    let mut reversed = String::with_capacity(content.len());
+    let mut run: Vec<&String> = Vec::new();
+    for (idx, cluster) in clusters.iter().enumerate().rev() {
+        if keeps_order(idx) {
+            run.push(cluster);
+            continue;
+        }
+
+        reversed.extend(run.drain(..).rev().map(|cluster| cluster.as_str()));
+        let mut chars = cluster.chars();
+        if let Some(c) = chars.next() {
+            reversed.push(mirror_char(c));
+        }
+        reversed.extend(chars);
+    }
+    reversed.extend(run.drain(..).rev().map(|cluster| cluster.as_str()));
+    reversed
+}
+
+/// Reorders the words of a line of a right-to-left page to be displayed from left to right and
+/// aligns the line to the right. The indentation and list prefixes end up on the right side
+///
+/// This is a simplified version of the Unicode bidirectional algorithm: Runs of left-to-right
+/// words keep their order, words without letters between them belong to the run
+fn mirror_line(line: Vec<Word>, width: u16) -> Vec<Word> {
+    if line.is_empty() {
+        return line;
+    }
+
+    let directions: Vec<WordDirection> = line
+        .iter()
+        .map(|word| word_direction(&word.content))
+        .collect();
+    let is_strong = |direction: &&WordDirection| **direction != WordDirection::Neutral;
+    let is_ltr: Vec<bool> = (0..line.len())
+        .map(|idx| match directions[idx] {
+            WordDirection::LeftToRight => true,
+            WordDirection::RightToLeft => false,
+            WordDirection::Neutral => {
+                directions[..idx].iter().rev().find(is_strong) == Some(&WordDirection::LeftToRight)
+                    && directions[idx + 1..].iter().find(is_strong)
+                        == Some(&WordDirection::LeftToRight)
+            }
+        })
+        .collect();
+
+    // reverse the line and restore the order of the left-to-right runs
+    let mut order: Vec<usize> = (0..line.len()).rev().collect();
+    let mut start = 0;
+    while start < order.len() {
+        let end = order[start..]
+            .iter()
+            .position(|idx| !is_ltr[*idx])
+            .map(|len| start + len)
+            .unwrap_or(order.len());
+        order[start..end].reverse();
+        start = end + 1;
+    }
+
+    let mut mirrored: Vec<Word> = order
+        .iter()
+        .enumerate()
+        .map(|(pos, idx)| {
+            let mut word = line[*idx].clone();
+            if !is_ltr[*idx] {
+                word.content = reverse_rtl(&word.content);
+            }
+            // the space between two words follows the one of them that comes first in the text
+            word.whitespace_width = order
+                .get(pos + 1)
+                .map(|next| line[*idx.min(next)].whitespace_width)
+                .unwrap_or_default();
+            word
+        })
+        .collect();
+
+    let line_width: f64 = mirrored
+        .iter()
+        .map(|word| word.width + word.whitespace_width)
+        .sum();
+    mirrored.insert(
+        0,
+        Word {
+            index: usize::MAX,
+            content: String::new(),
+            style: Style::default(),
+            width: 0.0,
+            whitespace_width: (width as f64 - line_width).max(0.0),
+            penalty_width: 0.0,
+        },
+    );
+    mirrored
+}
+
+/// Renders the document to lines of the width. The sections of the headers in `folded` only show
+/// their header and how many lines are folded. Right-to-left documents are mirrored
+pub fn render_document(
+    document: &Document,
+    width: u16,
+    folded: &HashSet<String>,
+    direction: Direction,
+) -> RenderedDocument {
+    Renderer::render_document(document, width, folded, direction)
+}
+
+/// Renders the entries of a Wiktionary page in a compact dictionary layout. Folding sections is
+/// not supported in this layout
+pub fn render_dictionary(
+    document: &Document,
+    dictionary: &Dictionary,
+    width: u16,
+    direction: Direction,
+) -> RenderedDocument {
+    let folded = HashSet::new();
+    let mut renderer = Renderer::new(width, &folded);
+    renderer.render_dictionary(document, dictionary);
+    renderer.finish(direction)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Style;
+
+    use super::{mirror_line, reverse_rtl, word_direction, WordDirection};
+    use crate::renderer::Word;
+
+    /// Words of a line, each as wide as its characters and followed by a single space
+    fn line(contents: &[&str]) -> Vec<Word> {
+        contents
+            .iter()
+            .enumerate()
+            .map(|(index, content)| Word {
+                index,
+                content: content.to_string(),
+                style: Style::default(),
+                width: content.chars().count() as f64,
+                whitespace_width: 1.0,
+                penalty_width: 0.0,
+            })
+            .collect()
+    }
+
+    /// The contents of a mirrored line, without the padding word
+    fn contents(line: &[Word]) -> Vec<&str> {
+        line.iter()
+            .skip(1)
+            .map(|word| word.content.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_word_direction() {
+        assert_eq!(word_direction("Rust"), WordDirection::LeftToRight);
+        assert_eq!(word_direction("שלום"), WordDirection::RightToLeft);
+        assert_eq!(word_direction("(سلام)"), WordDirection::RightToLeft);
+        assert_eq!(word_direction("2024"), WordDirection::Neutral);
+        assert_eq!(word_direction("-"), WordDirection::Neutral);
+    }
+
+    #[test]
+    fn test_reverse_marks() {
+        // shin with qamatz and shin dot, vav with holam
+        assert_eq!(
+            reverse_rtl("\u{05E9}\u{05B8}\u{05C1}\u{05DC}\u{05D5}\u{05B9}\u{05DD}"),
+            "\u{05DD}\u{05D5}\u{05B9}\u{05DC}\u{05E9}\u{05B8}\u{05C1}"
+        );
+        // every letter carries a fatha
+        assert_eq!(
+            reverse_rtl("\u{0643}\u{064E}\u{062A}\u{064E}\u{0628}\u{064E}"),
+            "\u{0628}\u{064E}\u{062A}\u{064E}\u{0643}\u{064E}"
+        );
+    }
+
+    #[test]
+    fn test_reverse_keeps_ltr_runs() {
+        assert_eq!(reverse_rtl("שנת1948"), "1948תנש");
+        assert_eq!(reverse_rtl("גרסה1.5"), "1.5הסרג");
+        assert_eq!(reverse_rtl("10,000"), "10,000");
+        assert_eq!(reverse_rtl("עםHTML"), "HTMLםע");
+        // a dot at the end of a number is punctuation
+        assert_eq!(reverse_rtl("שנת5."), ".5תנש");
+    }
+
+    #[test]
+    fn test_reverse_brackets() {
+        assert_eq!(reverse_rtl("(שלום)"), "(םולש)");
+        assert_eq!(reverse_rtl("[א]"), "[א]");
+        assert_eq!(reverse_rtl("«كتب»"), "«بتك»");
+    }
+
+    #[test]
+    fn test_mirror_ltr_inside_rtl() {
+        let mirrored = mirror_line(line(&["שלום", "Rust", "and", "Go", "עולם"]), 40);
+        assert_eq!(
+            contents(&mirrored),
+            vec!["םלוע", "Rust", "and", "Go", "םולש"]
+        );
+
+        let mirrored = mirror_line(line(&["בשנת", "2024", "היה"]), 40);
+        assert_eq!(contents(&mirrored), vec!["היה", "2024", "תנשב"]);
+    }
+
+    #[test]
+    fn test_mirror_neutral_words() {
+        // between an ltr and an rtl run, the neutral word is part of the rtl text
+        let mirrored = mirror_line(line(&["Rust", "-", "שפה"]), 40);
+        assert_eq!(contents(&mirrored), vec!["הפש", "-", "Rust"]);
+
+        // between two ltr words, it's part of the ltr run
+        let mirrored = mirror_line(line(&["שפת", "C", "-", "Rust"]), 40);
+        assert_eq!(contents(&mirrored), vec!["C", "-", "Rust", "תפש"]);
+    }
+
+    #[test]
+    fn test_mirror_brackets() {
+        let mirrored = mirror_line(line(&["ראו", "(עמוד)"]), 40);
+        assert_eq!(contents(&mirrored), vec!["(דומע)", "ואר"]);
+    }
+
+    #[test]
+    fn test_mirror_padding() {
+        assert!(mirror_line(Vec::new(), 40).is_empty());
+
+        // two words of two characters and the space between them
+        let mirrored = mirror_line(line(&["אב", "גד"]), 20);
+        assert_eq!(mirrored[0].content, "");
+        assert_eq!(mirrored[0].whitespace_width, 15.0);
+        assert_eq!(mirrored[1].whitespace_width, 1.0);
+        assert_eq!(mirrored[2].whitespace_width, 0.0);
+
+        let mirrored = mirror_line(line(&["אב", "גד"]), 3);
+        assert_eq!(mirrored[0].whitespace_width, 0.0);
+        assert_eq!(mirrored.len(), 3);
+    }
 }
@@ -0,0 +1,132 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio_util::sync::CancellationToken;
+use wiki_api::{Client, Progress};
+
+/// Ids of the requests, unique across all trackers so handles of different panes never match
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A running request. Cancelling the request stops it and drops its result, clones of the handle
+/// share the same cancellation token and progress
+#[derive(Debug, Clone)]
+pub struct RequestHandle {
+    id: u64,
+    description: String,
+    started: Instant,
+    progress: Progress,
+    token: CancellationToken,
+}
+
+impl RequestHandle {
+    /// What the request is doing, e.g. "Loading 'Rust'"
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn bytes_received(&self) -> u64 {
+        self.progress.bytes_received()
+    }
+
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Returns a client that reports the received bytes to this request
+    pub fn client(&self, client: &Client) -> Client {
+        client.with_progress(self.progress.clone())
+    }
+
+    /// Runs the future until it completes. Returns `None` when the request was cancelled before
+    /// the future completed
+    pub async fn run<F: Future>(&self, future: F) -> Option<F::Output> {
+        tokio::select! {
+            biased;
+            _ = self.token.cancelled() => None,
+            output = future => Some(output),
+        }
+    }
+
+    /// Formats the elapsed time and received bytes, e.g. "1.2s · 34.5 KB received"
+    pub fn status(&self) -> String {
+        let bytes = self.bytes_received() as f64;
+        let received = if bytes < 1024.0 {
+            format!("{} B", bytes)
+        } else if bytes < 1024.0 * 1024.0 {
+            format!("{:.1} KB", bytes / 1024.0)
+        } else {
+            format!("{:.1} MB", bytes / (1024.0 * 1024.0))
+        };
+
+        format!(
+            "{:.1}s · {} received",
+            self.elapsed().as_secs_f32(),
+            received
+        )
+    }
+}
+
+// handles are compared by their request, so they can be sent in actions
+impl PartialEq for RequestHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for RequestHandle {}
+
+/// Tracks the running request of one kind, e.g. loading a page. Starting a new request cancels
+/// the one that is still running, so the newest request always wins
+#[derive(Debug, Clone, Default)]
+pub struct RequestTracker {
+    current: Arc<Mutex<Option<RequestHandle>>>,
+}
+
+impl RequestTracker {
+    /// Starts tracking a new request and cancels the previous one
+    pub fn start(&self, description: impl Into<String>) -> RequestHandle {
+        let mut current = self.current.lock().unwrap();
+        if let Some(previous) = current.take() {
+            previous.cancel();
+        }
+
+        let handle = RequestHandle {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            description: description.into(),
+            started: Instant::now(),
+            progress: Progress::default(),
+            token: CancellationToken::new(),
+        };
+        *current = Some(handle.clone());
+        handle
+    }
+
+    /// Cancels the running request. Returns whether a request was running
+    pub fn cancel(&self) -> bool {
+        match self.current.lock().unwrap().take() {
+            Some(handle) => {
+                handle.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stops tracking the request once it's done. Newer requests are not affected
+    pub fn finish(&self, handle: &RequestHandle) {
+        let mut current = self.current.lock().unwrap();
+        if current.as_ref() == Some(handle) {
+            *current = None;
+        }
+    }
+}
@@ -10,23 +10,24 @@ pub type Frame<'a> = ratatui::Frame<'a>;
 
 pub struct Tui {
     pub terminal: ratatui::Terminal<Backend<std::io::Stdout>>,
+    /// Whether mouse events are captured. Without capturing them, the terminal can still be used
+    /// to select text
+    mouse: bool,
 }
 
 impl Tui {
-    pub fn new() -> Result<Self> {
+    pub fn new(mouse: bool) -> Result<Self> {
         let terminal = ratatui::Terminal::new(Backend::new(std::io::stdout()))
             .context("unable to create terminal")?;
-        Ok(Self { terminal })
+        Ok(Self { terminal, mouse })
     }
 
     pub fn enter(&mut self) -> Result<()> {
         crossterm::terminal::enable_raw_mode()?;
-        crossterm::execute!(
-            std::io::stdout(),
-            EnterAlternateScreen,
-            EnableMouseCapture,
-            cursor::Hide
-        )?;
+        crossterm::execute!(std::io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        if self.mouse {
+            crossterm::execute!(std::io::stdout(), EnableMouseCapture)?;
+        }
         self.terminal.clear()?;
         Ok(())
     }
@@ -0,0 +1,134 @@
+use ratatui::{
+    style::{Modifier, Stylize},
+    text::{Line, Span},
+    widgets::ListItem,
+};
+use wiki_api::languages::Language;
+
+use crate::{config::Theme, fuzzy::fuzzy_match};
+
+/// The text of a language that matched what was typed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LanguageField {
+    Name,
+    Autonym,
+    Code,
+}
+
+/// A language in a language selection popup
+#[derive(Debug, Clone)]
+pub struct LanguageItem<T> {
+    pub value: T,
+    pub language: Language,
+    /// Name of the language in the language itself
+    pub autonym: String,
+    /// Shown after the names, e.g. the title of the page in this language
+    pub detail: Option<String>,
+    /// Position in the pinned languages of the config
+    pinned: Option<usize>,
+    /// The text that matched and the positions of the matched characters in it
+    matched: Option<(LanguageField, Vec<usize>)>,
+}
+
+impl<T> LanguageItem<T> {
+    pub fn new(value: T, language: Language, autonym: impl Into<String>) -> Self {
+        LanguageItem {
+            value,
+            language,
+            autonym: autonym.into(),
+            detail: None,
+            pinned: None,
+            matched: None,
+        }
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn pin(mut self, pinned: &[Language]) -> Self {
+        self.pinned = pinned.iter().position(|x| *x == self.language);
+        self
+    }
+
+    /// Matches the query against the English name, the autonym and the code and keeps the best
+    /// match
+    fn fuzzy_match(&self, query: &str) -> Option<(i64, LanguageField, Vec<usize>)> {
+        vec![
+            (LanguageField::Name, self.language.name()),
+            (LanguageField::Autonym, self.autonym.as_str()),
+            (LanguageField::Code, self.language.code()),
+        ]
+        .into_iter()
+        .filter_map(|(field, text)| {
+            fuzzy_match(query, text)
+                .map(|fuzzy_match| (fuzzy_match.score, field, fuzzy_match.indices))
+        })
+        .max_by_key(|(score, ..)| *score)
+    }
+
+    /// Turns the item into a list entry, the matched characters are highlighted
+    pub fn to_list_item(&self, theme: &Theme) -> ListItem<'static> {
+        let highlighted = |field: LanguageField, text: &str| -> Vec<Span<'static>> {
+            let indices = match self.matched {
+                Some((matched, ref indices)) if matched == field => indices.as_slice(),
+                _ => &[],
+            };
+            text.chars()
+                .enumerate()
+                .map(|(idx, c)| {
+                    let span = Span::raw(c.to_string());
+                    if indices.contains(&idx) {
+                        span.fg(theme.highlight_fg).add_modifier(Modifier::BOLD)
+                    } else {
+                        span
+                    }
+                })
+                .collect()
+        };
+
+        let mut spans = vec![Span::raw(if self.pinned.is_some() { "* " } else { "  " })];
+        spans.extend(highlighted(LanguageField::Name, self.language.name()));
+        if self.autonym != self.language.name() {
+            spans.push(Span::raw(" "));
+            spans.extend(
+                highlighted(LanguageField::Autonym, &self.autonym)
+                    .into_iter()
+                    .map(|span| span.fg(theme.inactive_fg)),
+            );
+        }
+        spans.push(Span::raw(" ("));
+        spans.extend(highlighted(LanguageField::Code, self.language.code()));
+        spans.push(Span::raw(")"));
+        if let Some(ref detail) = self.detail {
+            spans.push(Span::raw(format!(" | {}", detail)).fg(theme.search_title_fg));
+        }
+
+        ListItem::new(Line::from(spans)).fg(theme.fg)
+    }
+}
+
+/// Keeps the languages that match the query, the pinned ones first and then the best matches.
+/// Without a query, every language is kept in its order after the pinned ones
+pub fn filter_languages<T: Clone>(query: &str, items: &[LanguageItem<T>]) -> Vec<LanguageItem<T>> {
+    let mut matches: Vec<(i64, LanguageItem<T>)> = items
+        .iter()
+        .filter_map(|item| {
+            let mut item = item.clone();
+            if query.is_empty() {
+                item.matched = None;
+                return Some((0, item));
+            }
+
+            let (score, field, indices) = item.fuzzy_match(query)?;
+            item.matched = Some((field, indices));
+            Some((score, item))
+        })
+        .collect();
+
+    matches.sort_by_key(|(score, item)| {
+        (item.pinned.unwrap_or(usize::MAX), std::cmp::Reverse(*score))
+    });
+    matches.into_iter().map(|(_, item)| item).collect()
+}
@@ -1,8 +1,10 @@
 mod centered_rect;
+mod language_list;
 mod padded_rect;
 mod stateful_list;
 
 pub use centered_rect::centered_rect;
+pub use language_list::{filter_languages, LanguageItem};
 pub use padded_rect::padded_rect;
 
 pub use stateful_list::{ScrollBehaviour, StatefulList};
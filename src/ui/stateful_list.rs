@@ -37,6 +37,10 @@ impl<T> StatefulList<T> {
         &mut self.items
     }
 
+    pub fn get_state(&self) -> &ListState {
+        &self.state
+    }
+
     pub fn get_state_mut(&mut self) -> &mut ListState {
         &mut self.state
     }
@@ -56,6 +56,7 @@ pub fn parse_languages(input: TokenStream) -> TokenStream {
     let mut variants = quote!();
     let mut language_data_arms = quote!();
     let mut from_str_arms = quote!();
+    let mut direction_arms = quote!();
     let mut array_def = quote!();
     for (_key, value) in languages {
         let ident = value.identifier.clone().unwrap();
@@ -75,6 +76,12 @@ pub fn parse_languages(input: TokenStream) -> TokenStream {
             #language_data_arms
             Language::#ident => (#en_name, #lang_name, #lang_code),
         };
+        if value.dir == "rtl" {
+            direction_arms = quote! {
+                #direction_arms
+                Language::#ident => Direction::RightToLeft,
+            };
+        }
         from_str_arms = quote! {
             #from_str_arms
             #lang_code_lowercase | #lang_name_lowercase | #en_name_lowercase => Some(Language::#ident),
@@ -86,15 +93,17 @@ pub fn parse_languages(input: TokenStream) -> TokenStream {
     }
 
     let expanded = quote! {
-        use serde::{Serialize, Deserialize};
+        use serde::{Serialize, Serializer, Deserialize, Deserializer};
         use std::str::FromStr;
         use std::convert::TryFrom;
 
-        #[derive(Copy, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-        #[serde(try_from = "String")]
+        #[derive(Copy, Debug, Clone, PartialEq, Eq)]
         pub enum Language{
             Unknown,
             #variants
+            /// A language that isn't built in, but was found in the installed
+            /// [`LanguageRegistry`](crate::sitematrix::LanguageRegistry). Contains its code
+            Other(&'static str),
         }
 
         impl Language{
@@ -104,6 +113,7 @@ pub fn parse_languages(input: TokenStream) -> TokenStream {
                 match self {
                     Language::Unknown => ("UNKNOWN", "UNKNOWN", "UNKNOWN"),
                     #language_data_arms
+                    Language::Other(code) => crate::sitematrix::language_data(code),
                 }
             }
             /// Returns the English name of the language
@@ -120,15 +130,64 @@ pub fn parse_languages(input: TokenStream) -> TokenStream {
             pub fn code(&self) -> &str {
                 self.language_data().2
             }
+
+            /// Returns the direction the language is written in
+            pub fn direction(&self) -> Direction {
+                match self {
+                    #direction_arms
+                    Language::Other(code) => crate::sitematrix::direction(code),
+                    _ => Direction::LeftToRight,
+                }
+            }
         }
 
-        impl FromStr for Language {
-            type Err = ParseLanguageError;
-            fn from_str(from: &str) -> Result<Self, Self::Err> {
+        /// The direction a language is written in
+        #[derive(Copy, Debug, Clone, PartialEq, Eq, Default)]
+        pub enum Direction {
+            #[default]
+            LeftToRight,
+            RightToLeft,
+        }
+
+        impl Language {
+            /// Finds the built in language with this code, English name or local name
+            pub(crate) fn builtin(from: &str) -> Option<Self> {
                 match from.to_lowercase().as_ref() {
                     #from_str_arms
                     _ => None,
-                }.ok_or(ParseLanguageError(from.to_string()))
+                }
+            }
+        }
+
+        impl FromStr for Language {
+            type Err = ParseLanguageError;
+            /// Looks the language up in the installed
+            /// [`LanguageRegistry`](crate::sitematrix::LanguageRegistry) first, then in the
+            /// built in languages
+            fn from_str(from: &str) -> Result<Self, Self::Err> {
+                crate::sitematrix::find_language(from)
+                    .or_else(|| Self::builtin(from))
+                    .ok_or(ParseLanguageError(from.to_string()))
+            }
+        }
+
+        /// Languages that can't be found are deserialized as [`Language::Other`], so pages saved
+        /// in a language of a [`LanguageRegistry`](crate::sitematrix::LanguageRegistry) can still be
+        /// loaded after it was uninstalled
+        impl<'de> Deserialize<'de> for Language {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                if value == "UNKNOWN" {
+                    return Ok(Language::Unknown);
+                }
+                Ok(Self::from_str(&value)
+                    .unwrap_or_else(|_| Language::Other(crate::sitematrix::intern(&value))))
+            }
+        }
+
+        impl Serialize for Language {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.code())
             }
         }
 
@@ -0,0 +1,415 @@
+use reqwest::Response;
+use serde::Deserialize;
+use snafu::{OptionExt, ResultExt};
+
+use crate::{
+    error::{DeserializeSnafu, MissingFieldSnafu, Result},
+    languages::Language,
+    search::Namespace,
+    Client, Endpoint,
+};
+
+/// A batch of pages that link to a page
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Backlinks {
+    /// Title of the page the backlinks point to
+    pub title: String,
+    /// The backlinks in this batch. Pages that link to the page through a redirect follow the
+    /// redirect they link through
+    pub links: Vec<Backlink>,
+    /// If more backlinks are available, use this value to continue listing them
+    pub continue_from: Option<String>,
+
+    /// Language of the page
+    pub language: Language,
+    /// API endpoint of the MediaWiki site the page belongs to
+    pub endpoint: Endpoint,
+}
+
+impl Backlinks {
+    /// Creates a [`BacklinksBuilder`] to configure and fetch the backlinks of a page
+    ///
+    /// [`BacklinksBuilder`]: BacklinksBuilder
+    pub fn builder() -> BacklinksBuilder<NoTitle, NoEndpoint, NoLanguage> {
+        BacklinksBuilder::default()
+    }
+
+    /// If available, returns the data necessary for continuing to list the backlinks
+    ///
+    /// When more backlinks are available, which can be checked via the
+    /// `Backlinks::continue_from` field, creates a [`BacklinksContinue`] data struct that contains
+    /// all of the necessary information to fetch the next batch
+    ///
+    /// [`BacklinksContinue`]: BacklinksContinue
+    pub fn continue_data(&self) -> Option<BacklinksContinue> {
+        self.continue_from
+            .as_ref()
+            .map(|continue_from| BacklinksContinue {
+                title: self.title.clone(),
+                endpoint: self.endpoint.clone(),
+                language: self.language,
+                continue_from: continue_from.clone(),
+            })
+    }
+}
+
+/// Contains the necessary data for continuing to list the backlinks of a page. This data can be
+/// extracted from already fetched backlinks with [`Backlinks::continue_data`]
+///
+/// [`Backlinks::continue_data`]: Backlinks::continue_data
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BacklinksContinue {
+    /// Title of the page the backlinks point to
+    pub title: String,
+    /// API endpoint of the MediaWiki site the page belongs to
+    pub endpoint: Endpoint,
+    /// Language of the page
+    pub language: Language,
+    /// Value where the listing will continue
+    pub continue_from: String,
+}
+
+/// A page that links to another page
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Backlink {
+    /// Namespace of the linking page
+    pub namespace: Namespace,
+    /// Title of the linking page
+    pub title: String,
+    /// PageId of the linking page
+    pub pageid: usize,
+    /// Whether the linking page is a redirect to the page
+    pub is_redirect: bool,
+    /// If the page links to the page through a redirect, the title of that redirect
+    pub via_redirect: Option<String>,
+}
+
+/// Which kind of pages to list as backlinks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectFilter {
+    /// List redirects and regular pages
+    All,
+    /// Only list redirects
+    Redirects,
+    /// Only list pages that are not redirects
+    NonRedirects,
+}
+
+impl std::fmt::Display for RedirectFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RedirectFilter::All => write!(f, "all"),
+            RedirectFilter::Redirects => write!(f, "redirects"),
+            RedirectFilter::NonRedirects => write!(f, "nonredirects"),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct WithTitle(String);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoTitle;
+
+#[doc(hidden)]
+pub struct WithEndpoint(Endpoint);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoEndpoint;
+
+#[doc(hidden)]
+pub struct WithLanguage(Language);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoLanguage;
+
+/// A fully configured `BacklinksBuilder` that can be used to fetch the backlinks. This is a
+/// convenience type
+pub type BacklinksRequest = BacklinksBuilder<WithTitle, WithEndpoint, WithLanguage>;
+
+/// A `BacklinksBuilder` can be used to configure and fetch the pages linking to a page
+#[derive(Default)]
+pub struct BacklinksBuilder<T, E, L> {
+    title: T,
+    endpoint: E,
+    language: L,
+    namespaces: Option<Vec<Namespace>>,
+    redirects: Option<bool>,
+    filter_redirects: Option<RedirectFilter>,
+    limit: Option<usize>,
+    continue_from: Option<String>,
+    client: Client,
+}
+
+impl<E, L> BacklinksBuilder<NoTitle, E, L> {
+    /// Title of the page to list the backlinks of
+    pub fn title(self, title: impl Into<String>) -> BacklinksBuilder<WithTitle, E, L> {
+        BacklinksBuilder {
+            title: WithTitle(title.into()),
+            endpoint: self.endpoint,
+            language: self.language,
+            namespaces: self.namespaces,
+            redirects: self.redirects,
+            filter_redirects: self.filter_redirects,
+            limit: self.limit,
+            continue_from: self.continue_from,
+            client: self.client,
+        }
+    }
+}
+
+impl<T, L> BacklinksBuilder<T, NoEndpoint, L> {
+    /// API endpoint for the MediaWiki site the page belongs to
+    pub fn endpoint(self, endpoint: Endpoint) -> BacklinksBuilder<T, WithEndpoint, L> {
+        BacklinksBuilder {
+            title: self.title,
+            endpoint: WithEndpoint(endpoint),
+            language: self.language,
+            namespaces: self.namespaces,
+            redirects: self.redirects,
+            filter_redirects: self.filter_redirects,
+            limit: self.limit,
+            continue_from: self.continue_from,
+            client: self.client,
+        }
+    }
+}
+
+impl<T, E> BacklinksBuilder<T, E, NoLanguage> {
+    /// Language of the page
+    pub fn language(self, language: Language) -> BacklinksBuilder<T, E, WithLanguage> {
+        BacklinksBuilder {
+            title: self.title,
+            endpoint: self.endpoint,
+            language: WithLanguage(language),
+            namespaces: self.namespaces,
+            redirects: self.redirects,
+            filter_redirects: self.filter_redirects,
+            limit: self.limit,
+            continue_from: self.continue_from,
+            client: self.client,
+        }
+    }
+}
+
+impl<T, E, L> BacklinksBuilder<T, E, L> {
+    /// Http client used to send the request
+    ///
+    /// Default: a shared [`Client`] with the default configuration
+    ///
+    /// [`Client`]: Client
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Only list backlinks from pages in these namespaces
+    ///
+    /// Default: [`Namespace::Main`]
+    ///
+    /// [`Namespace::Main`]: Namespace::Main
+    pub fn namespaces(mut self, namespaces: Vec<Namespace>) -> Self {
+        self.namespaces = Some(namespaces);
+        self
+    }
+
+    /// If a linking page is a redirect, also list the pages that link to the page through that
+    /// redirect
+    pub fn redirects(mut self, redirects: bool) -> Self {
+        self.redirects = Some(redirects);
+        self
+    }
+
+    /// Which kind of linking pages to list
+    ///
+    /// Default: [`RedirectFilter::All`]
+    ///
+    /// [`RedirectFilter::All`]: RedirectFilter::All
+    pub fn filter_redirects(mut self, filter: RedirectFilter) -> Self {
+        self.filter_redirects = Some(filter);
+        self
+    }
+
+    /// How many backlinks to return. The value must be between 1 and 500
+    ///
+    /// Default: `10`
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// When more backlinks are available, use this to continue (See
+    /// [`Backlinks::continue_from`])
+    ///
+    /// [`Backlinks::continue_from`]: Backlinks::continue_from
+    pub fn continue_from(mut self, continue_from: impl Into<String>) -> Self {
+        self.continue_from = Some(continue_from.into());
+        self
+    }
+}
+
+impl BacklinksBuilder<WithTitle, WithEndpoint, WithLanguage> {
+    /// Fetches the pages linking to the page
+    ///
+    /// # Error
+    ///
+    /// This function returns an error when one of the following things happens:
+    /// - The request to the server could not be made
+    /// - The server returned an error
+    /// - The returned result could not interpreted as `Backlinks`
+    pub async fn fetch(self) -> Result<Backlinks> {
+        async fn action_query(
+            client: &Client,
+            params: Vec<(&str, String)>,
+            endpoint: Endpoint,
+        ) -> Result<Response> {
+            let request = client
+                .get(endpoint.api_url().clone())
+                .query(&[
+                    ("action", "query"),
+                    ("format", "json"),
+                    ("formatversion", "2"),
+                ])
+                .query(&params);
+            client.send(request).await
+        }
+
+        let namespaces = self
+            .namespaces
+            .unwrap_or_else(|| vec![Namespace::Main])
+            .into_iter()
+            .map(|namespace| (namespace as usize).to_string())
+            .collect::<Vec<String>>()
+            .join("|");
+
+        let mut params = vec![
+            ("list", "backlinks".to_string()),
+            ("bltitle", self.title.0.clone()),
+            ("blnamespace", namespaces),
+        ];
+
+        if let Some(true) = self.redirects {
+            params.push(("blredirect", "1".to_string()));
+        }
+
+        if let Some(filter) = self.filter_redirects {
+            params.push(("blfilterredir", filter.to_string()));
+        }
+
+        if let Some(limit) = self.limit {
+            params.push(("bllimit", limit.to_string()));
+        }
+
+        if let Some(continue_from) = self.continue_from {
+            params.push(("blcontinue", continue_from));
+        }
+
+        let response = action_query(&self.client, params, self.endpoint.0.clone()).await?;
+        let res_json = self.client.response_json(response).await?;
+
+        backlinks_from_json(res_json, self.title.0, self.endpoint.0, self.language.0)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawBacklink {
+    pageid: usize,
+    ns: Namespace,
+    title: String,
+    #[serde(default)]
+    redirect: bool,
+    #[serde(default)]
+    redirlinks: Vec<RawBacklink>,
+}
+
+fn backlinks_from_json(
+    res_json: serde_json::Value,
+    title: String,
+    endpoint: Endpoint,
+    language: Language,
+) -> Result<Backlinks> {
+    let continue_from = res_json
+        .get("continue")
+        .and_then(|x| x.get("blcontinue"))
+        .and_then(|x| x.as_str())
+        .map(|x| x.to_string());
+
+    let backlinks_json = res_json
+        .get("query")
+        .and_then(|x| x.get("backlinks"))
+        .context(MissingFieldSnafu { what: "backlinks" })?
+        .to_owned();
+
+    let raw_backlinks: Vec<RawBacklink> =
+        serde_json::from_value(backlinks_json).context(DeserializeSnafu { what: "backlinks" })?;
+
+    let mut links = Vec::new();
+    for raw in raw_backlinks {
+        let redirect_title = raw.title.clone();
+        links.push(Backlink {
+            namespace: raw.ns,
+            title: raw.title,
+            pageid: raw.pageid,
+            is_redirect: raw.redirect,
+            via_redirect: None,
+        });
+        links.extend(raw.redirlinks.into_iter().map(|link| Backlink {
+            namespace: link.ns,
+            title: link.title,
+            pageid: link.pageid,
+            is_redirect: link.redirect,
+            via_redirect: Some(redirect_title.clone()),
+        }));
+    }
+
+    Ok(Backlinks {
+        title,
+        links,
+        continue_from,
+        language,
+        endpoint,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::backlinks_from_json;
+    use crate::{languages::Language, Endpoint};
+
+    #[test]
+    fn test_backlinks_from_fixture() {
+        let res_json = serde_json::from_str(include_str!("../tests/fixtures/query_backlinks.json"))
+            .expect("the fixture should be valid json");
+        let endpoint = Endpoint::parse("https://en.wikipedia.org/w/api.php").unwrap();
+
+        let backlinks = backlinks_from_json(
+            res_json,
+            "Rust (programming language)".to_string(),
+            endpoint,
+            Language::English,
+        )
+        .unwrap();
+        assert_eq!(backlinks.continue_from.as_deref(), Some("0|1148745"));
+        assert_eq!(
+            backlinks.continue_data().map(|data| data.continue_from),
+            Some("0|1148745".to_string())
+        );
+        assert_eq!(backlinks.links.len(), 5);
+
+        assert!(!backlinks.links[0].is_redirect);
+        assert!(backlinks.links[1].is_redirect);
+        assert_eq!(backlinks.links[1].title, "Rust language");
+
+        // pages linking through the redirect follow it
+        assert_eq!(backlinks.links[2].title, "Mozilla");
+        assert_eq!(
+            backlinks.links[2].via_redirect.as_deref(),
+            Some("Rust language")
+        );
+        assert_eq!(backlinks.links[4].title, "Servo (software)");
+        assert_eq!(backlinks.links[4].via_redirect, None);
+    }
+}
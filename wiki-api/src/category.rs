@@ -0,0 +1,302 @@
+use reqwest::Response;
+use serde::Deserialize;
+use snafu::{OptionExt, ResultExt};
+
+use crate::{
+    error::{DeserializeSnafu, MissingFieldSnafu, Result},
+    languages::Language,
+    search::Namespace,
+    Client, Endpoint,
+};
+
+/// A batch of pages and subcategories that are members of a category
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CategoryMembers {
+    /// Title of the category, including the namespace prefix (e.g. `Category:Physics`)
+    pub category: String,
+    /// The members in this batch
+    pub members: Vec<CategoryMember>,
+    /// If more members are available, use this value to continue listing them
+    pub continue_from: Option<String>,
+
+    /// Language of the category
+    pub language: Language,
+    /// API endpoint of the MediaWiki site the category belongs to
+    pub endpoint: Endpoint,
+}
+
+impl CategoryMembers {
+    /// Creates a [`CategoryMembersBuilder`] to configure and fetch the members of a category
+    ///
+    /// [`CategoryMembersBuilder`]: CategoryMembersBuilder
+    pub fn builder() -> CategoryMembersBuilder<NoCategory, NoEndpoint, NoLanguage> {
+        CategoryMembersBuilder::default()
+    }
+}
+
+/// A page or subcategory in a category
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CategoryMember {
+    /// Namespace of the member. Either [`Namespace::Main`] or [`Namespace::Category`]
+    ///
+    /// [`Namespace::Main`]: Namespace::Main
+    /// [`Namespace::Category`]: Namespace::Category
+    pub namespace: Namespace,
+    /// Title of the member
+    pub title: String,
+    /// PageId of the member
+    pub pageid: usize,
+}
+
+impl CategoryMember {
+    /// Whether this member is a subcategory
+    pub fn is_subcategory(&self) -> bool {
+        self.namespace == Namespace::Category
+    }
+}
+
+#[doc(hidden)]
+pub struct WithCategory(String);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoCategory;
+
+#[doc(hidden)]
+pub struct WithEndpoint(Endpoint);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoEndpoint;
+
+#[doc(hidden)]
+pub struct WithLanguage(Language);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoLanguage;
+
+/// A fully configured `CategoryMembersBuilder` that can be used to fetch the members. This is a
+/// convenience type
+pub type CategoryMembersRequest = CategoryMembersBuilder<WithCategory, WithEndpoint, WithLanguage>;
+
+/// A `CategoryMembersBuilder` can be used to configure and fetch the members of a category
+#[derive(Default)]
+pub struct CategoryMembersBuilder<C, E, L> {
+    category: C,
+    endpoint: E,
+    language: L,
+    limit: Option<usize>,
+    continue_from: Option<String>,
+    client: Client,
+}
+
+impl<E, L> CategoryMembersBuilder<NoCategory, E, L> {
+    /// Title of the category, including the namespace prefix (e.g. `Category:Physics`)
+    pub fn category(
+        self,
+        category: impl Into<String>,
+    ) -> CategoryMembersBuilder<WithCategory, E, L> {
+        CategoryMembersBuilder {
+            category: WithCategory(category.into()),
+            endpoint: self.endpoint,
+            language: self.language,
+            limit: self.limit,
+            continue_from: self.continue_from,
+            client: self.client,
+        }
+    }
+}
+
+impl<C, L> CategoryMembersBuilder<C, NoEndpoint, L> {
+    /// API endpoint for the MediaWiki site the category belongs to
+    pub fn endpoint(self, endpoint: Endpoint) -> CategoryMembersBuilder<C, WithEndpoint, L> {
+        CategoryMembersBuilder {
+            category: self.category,
+            endpoint: WithEndpoint(endpoint),
+            language: self.language,
+            limit: self.limit,
+            continue_from: self.continue_from,
+            client: self.client,
+        }
+    }
+}
+
+impl<C, E> CategoryMembersBuilder<C, E, NoLanguage> {
+    /// Language of the category
+    pub fn language(self, language: Language) -> CategoryMembersBuilder<C, E, WithLanguage> {
+        CategoryMembersBuilder {
+            category: self.category,
+            endpoint: self.endpoint,
+            language: WithLanguage(language),
+            limit: self.limit,
+            continue_from: self.continue_from,
+            client: self.client,
+        }
+    }
+}
+
+impl<C, E, L> CategoryMembersBuilder<C, E, L> {
+    /// Http client used to send the request
+    ///
+    /// Default: a shared [`Client`] with the default configuration
+    ///
+    /// [`Client`]: Client
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// How many members to return. The value must be between 1 and 500
+    ///
+    /// Default: `10`
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// When more members are available, use this to continue (See
+    /// [`CategoryMembers::continue_from`])
+    ///
+    /// [`CategoryMembers::continue_from`]: CategoryMembers::continue_from
+    pub fn continue_from(mut self, continue_from: impl Into<String>) -> Self {
+        self.continue_from = Some(continue_from.into());
+        self
+    }
+}
+
+impl CategoryMembersBuilder<WithCategory, WithEndpoint, WithLanguage> {
+    /// Fetches the members of the category. Only articles and subcategories are returned,
+    /// subcategories are listed first
+    ///
+    /// # Error
+    ///
+    /// This function returns an error when one of the following things happens:
+    /// - The request to the server could not be made
+    /// - The server returned an error
+    /// - The returned result could not interpreted as `CategoryMembers`
+    pub async fn fetch(self) -> Result<CategoryMembers> {
+        async fn action_query(
+            client: &Client,
+            params: Vec<(&str, String)>,
+            endpoint: Endpoint,
+        ) -> Result<Response> {
+            let request = client
+                .get(endpoint.api_url().clone())
+                .query(&[
+                    ("action", "query"),
+                    ("format", "json"),
+                    ("formatversion", "2"),
+                ])
+                .query(&params);
+            client.send(request).await
+        }
+
+        let mut params = vec![
+            ("list", "categorymembers".to_string()),
+            ("cmtitle", self.category.0.clone()),
+            ("cmprop", "ids|title".to_string()),
+            ("cmtype", "subcat|page".to_string()),
+            (
+                "cmnamespace",
+                format!(
+                    "{}|{}",
+                    Namespace::Main as usize,
+                    Namespace::Category as usize
+                ),
+            ),
+            ("cmsort", "sortkey".to_string()),
+        ];
+
+        if let Some(limit) = self.limit {
+            params.push(("cmlimit", limit.to_string()));
+        }
+
+        if let Some(continue_from) = self.continue_from {
+            params.push(("cmcontinue", continue_from));
+        }
+
+        let response = action_query(&self.client, params, self.endpoint.0.clone()).await?;
+        let res_json = self.client.response_json(response).await?;
+
+        category_members_from_json(res_json, self.category.0, self.endpoint.0, self.language.0)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawCategoryMember {
+    pageid: usize,
+    ns: Namespace,
+    title: String,
+}
+
+fn category_members_from_json(
+    res_json: serde_json::Value,
+    category: String,
+    endpoint: Endpoint,
+    language: Language,
+) -> Result<CategoryMembers> {
+    let continue_from = res_json
+        .get("continue")
+        .and_then(|x| x.get("cmcontinue"))
+        .and_then(|x| x.as_str())
+        .map(|x| x.to_string());
+
+    let members_json = res_json
+        .get("query")
+        .and_then(|x| x.get("categorymembers"))
+        .context(MissingFieldSnafu {
+            what: "category members",
+        })?
+        .to_owned();
+
+    let raw_members: Vec<RawCategoryMember> =
+        serde_json::from_value(members_json).context(DeserializeSnafu {
+            what: "category members",
+        })?;
+
+    Ok(CategoryMembers {
+        category,
+        members: raw_members
+            .into_iter()
+            .map(|raw| CategoryMember {
+                namespace: raw.ns,
+                title: raw.title,
+                pageid: raw.pageid,
+            })
+            .collect(),
+        continue_from,
+        language,
+        endpoint,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::category_members_from_json;
+    use crate::{languages::Language, Endpoint};
+
+    #[test]
+    fn test_category_members_from_fixture() {
+        let res_json =
+            serde_json::from_str(include_str!("../tests/fixtures/query_categorymembers.json"))
+                .expect("the fixture should be valid json");
+        let endpoint = Endpoint::parse("https://en.wikipedia.org/w/api.php").unwrap();
+
+        let members = category_members_from_json(
+            res_json,
+            "Category:Physics".to_string(),
+            endpoint,
+            Language::English,
+        )
+        .unwrap();
+        assert_eq!(members.category, "Category:Physics");
+        assert_eq!(members.continue_from.as_deref(), Some("page|4c4f4e47|123"));
+        assert_eq!(members.members.len(), 3);
+
+        assert!(members.members[0].is_subcategory());
+        assert_eq!(members.members[0].title, "Category:Concepts in physics");
+        assert!(!members.members[2].is_subcategory());
+        assert_eq!(members.members[2].pageid, 22939);
+    }
+}
@@ -0,0 +1,502 @@
+use reqwest::{header::RETRY_AFTER, RequestBuilder, Response, StatusCode};
+use snafu::ResultExt;
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock,
+    },
+    time::Duration,
+};
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::error::{
+    api_error, ClientSnafu, JsonSnafu, RateLimitedSnafu, RequestSnafu, Result, StatusSnafu,
+};
+
+const DEFAULT_CONTACT: &str = "https://github.com/Builditluc/wiki-tui";
+
+/// The http client used for all requests of this crate. It holds a single connection pool that
+/// is reused between requests, so it should be created once and passed to every builder
+///
+/// Failed requests are retried with an exponential backoff when the server responds with `429
+/// Too Many Requests` or an error of the server itself. A `Retry-After` header of the response
+/// is respected, responses asking to wait longer than the maximum backoff are returned as they
+/// are
+///
+/// Cloning a `Client` is cheap, the clones share the same connection pool
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use wiki_api::{languages::Language, search::Search, Client, Endpoint};
+/// # async fn example() -> wiki_api::Result<()> {
+/// let client = Client::builder()
+///     .timeout(Duration::from_secs(10))
+///     .max_retries(2)
+///     .contact("mail@example.org")
+///     .build()?;
+///
+/// let search = Search::builder()
+///     .client(client.clone())
+///     .query("meaning")
+///     .endpoint(Endpoint::wikipedia(Language::English))
+///     .language(Language::English)
+///     .search()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: reqwest::Client,
+    user_agent: String,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    progress: Option<Progress>,
+}
+
+/// Counts the bytes of the responses received by a [`Client`]. The counter is shared between
+/// clones, so it can be read while the request is still running
+///
+/// [`Client`]: Client
+#[derive(Debug, Clone, Default)]
+pub struct Progress {
+    bytes: Arc<AtomicU64>,
+}
+
+impl Progress {
+    /// Amount of bytes of the response bodies received so far
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    fn add(&self, bytes: usize) {
+        self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+impl Client {
+    /// Creates a [`ClientBuilder`] to configure a `Client`
+    ///
+    /// [`ClientBuilder`]: ClientBuilder
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// The value of the `User-Agent` header sent with every request
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Returns a client sharing the connection pool of this one that reports the received bytes
+    /// to `progress`
+    pub fn with_progress(&self, progress: Progress) -> Client {
+        Client {
+            progress: Some(progress),
+            ..self.clone()
+        }
+    }
+
+    /// Starts a GET request to the url with the `User-Agent` header of this client
+    pub(crate) fn get(&self, url: Url) -> RequestBuilder {
+        self.inner
+            .get(url)
+            .header(reqwest::header::USER_AGENT, &self.user_agent)
+    }
+
+    /// Sends the request, retrying it with an exponential backoff when the server is rate
+    /// limiting the requests, the server failed or the connection could not be made
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            // requests without a body can always be cloned
+            let result = request
+                .try_clone()
+                .expect("a GET request can be cloned")
+                .send()
+                .await;
+
+            let delay = match result {
+                Ok(response) => {
+                    let status = response.status();
+                    if attempt >= self.max_retries || !Self::is_retryable_status(status) {
+                        return Ok(response);
+                    }
+
+                    let delay = match Self::retry_after(&response) {
+                        Some(retry_after) if retry_after > self.max_backoff => {
+                            debug!("the server asked to wait {:?}, not retrying", retry_after);
+                            return Ok(response);
+                        }
+                        Some(retry_after) => retry_after,
+                        None => self.backoff(attempt),
+                    };
+                    warn!(
+                        "the server responded with '{}', retrying in {:?}",
+                        status, delay
+                    );
+                    delay
+                }
+                Err(error) => {
+                    if attempt >= self.max_retries || !(error.is_connect() || error.is_timeout()) {
+                        return Err(error).context(RequestSnafu);
+                    }
+
+                    let delay = self.backoff(attempt);
+                    warn!("the request failed: {}, retrying in {:?}", error, delay);
+                    delay
+                }
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Checks the status of the response and interprets its body as json. Errors reported by the
+    /// MediaWiki api in the body are returned as an `Error::Api`
+    pub(crate) async fn response_json(&self, mut response: Response) -> Result<serde_json::Value> {
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = Self::retry_after(&response).map(|x| x.as_secs());
+            return RateLimitedSnafu { retry_after }.fail();
+        }
+
+        if status.is_client_error() || status.is_server_error() {
+            return StatusSnafu { status }.fail();
+        }
+
+        // the body is read in chunks so the progress can be followed while it arrives
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.context(RequestSnafu)? {
+            if let Some(ref progress) = self.progress {
+                progress.add(chunk.len());
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        let res_json: serde_json::Value = serde_json::from_slice(&body).context(JsonSnafu)?;
+        match api_error(&res_json) {
+            Some(error) => Err(error),
+            None => Ok(res_json),
+        }
+    }
+
+    fn is_retryable_status(status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+
+    /// The delay before retrying for the given attempt, doubling with every attempt
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+
+    /// The delay the server asked for in the `Retry-After` header. Only the delay in seconds is
+    /// supported, not a http date
+    fn retry_after(response: &Response) -> Option<Duration> {
+        response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.trim().parse().ok())
+            .map(Duration::from_secs)
+    }
+}
+
+impl Default for Client {
+    /// Returns a shared client with the default configuration
+    fn default() -> Self {
+        static DEFAULT: OnceLock<Client> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| {
+                Client::builder()
+                    .build()
+                    .expect("failed creating the default http client")
+            })
+            .clone()
+    }
+}
+
+/// A `ClientBuilder` can be used to configure a [`Client`]
+///
+/// [`Client`]: Client
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    proxy: Option<String>,
+    contact: Option<String>,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self {
+            timeout: Some(Duration::from_secs(30)),
+            connect_timeout: Some(Duration::from_secs(10)),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            proxy: None,
+            contact: None,
+        }
+    }
+}
+
+impl ClientBuilder {
+    /// Total time a single request may take, from connecting until the response is read.
+    /// `None` disables the timeout
+    ///
+    /// Default: `30s`
+    pub fn timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    /// Time a connection to the server may take to be established. `None` disables the timeout
+    ///
+    /// Default: `10s`
+    pub fn connect_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.connect_timeout = timeout.into();
+        self
+    }
+
+    /// How often a failed request is retried. `0` disables retrying
+    ///
+    /// Default: `3`
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Delay before the first retry, the delay doubles with every following retry
+    ///
+    /// Default: `500ms`
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Longest delay between two retries. When the server asks to wait longer than this, the
+    /// request is not retried
+    ///
+    /// Default: `30s`
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Sends all requests through this proxy. Supported are `http://`, `https://`, `socks5://`
+    /// and `socks5h://` urls
+    ///
+    /// Default: the proxy set in the environment (`HTTP_PROXY`, `HTTPS_PROXY`, ...)
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Contact information (e.g. an email address or a website) added to the `User-Agent`
+    /// header. Wikimedia asks clients that make many requests to identify themselves this way
+    ///
+    /// Default: the url of the wiki-tui repository
+    pub fn contact(mut self, contact: impl Into<String>) -> Self {
+        self.contact = Some(contact.into());
+        self
+    }
+
+    /// Builds the `Client`
+    ///
+    /// # Error
+    ///
+    /// This function returns an error when the proxy url is invalid or the underlying http client
+    /// could not be created
+    pub fn build(self) -> Result<Client> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(ref proxy) = self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy).context(ClientSnafu)?);
+        }
+
+        let user_agent = format!(
+            "wiki-tui/{} ({})",
+            env!("CARGO_PKG_VERSION"),
+            self.contact.as_deref().unwrap_or(DEFAULT_CONTACT)
+        );
+
+        Ok(Client {
+            inner: builder.build().context(ClientSnafu)?,
+            user_agent,
+            max_retries: self.max_retries,
+            initial_backoff: self.initial_backoff,
+            max_backoff: self.max_backoff,
+            progress: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Starts a http server on a random local port that answers the requests with the given
+    /// responses in order and returns its url and the number of received requests
+    async fn mock_server(responses: Vec<&'static str>) -> (Url, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!(
+            "http://{}/w/api.php",
+            listener.local_addr().unwrap()
+        ))
+        .unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buffer = [0; 4096];
+                let _ = stream.read(&mut buffer).await.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+
+    fn test_client(max_retries: u32) -> Client {
+        Client::builder()
+            .max_retries(max_retries)
+            .initial_backoff(Duration::from_millis(10))
+            .max_backoff(Duration::from_secs(2))
+            .build()
+            .unwrap()
+    }
+
+    const OK: &str =
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}";
+    const UNAVAILABLE: &str =
+        "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    const RATE_LIMITED: &str =
+        "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    const RATE_LIMITED_LONG: &str =
+        "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 120\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+    #[tokio::test]
+    async fn test_retries_server_errors() {
+        let (url, requests) = mock_server(vec![UNAVAILABLE, UNAVAILABLE, OK]).await;
+        let client = test_client(3);
+
+        let response = client.send(client.get(url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let (url, requests) = mock_server(vec![UNAVAILABLE, UNAVAILABLE]).await;
+        let client = test_client(1);
+
+        let response = client.send(client.get(url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_respects_retry_after() {
+        let (url, requests) = mock_server(vec![RATE_LIMITED, OK]).await;
+        let client = test_client(3);
+
+        let start = std::time::Instant::now();
+        let response = client.send(client.get(url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_wait_longer_than_max_backoff() {
+        let (url, requests) = mock_server(vec![RATE_LIMITED_LONG]).await;
+        let client = test_client(3);
+
+        let response = client.send(client.get(url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sends_the_user_agent() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 4096];
+            let read = stream.read(&mut buffer).await.unwrap();
+            stream.write_all(OK.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&buffer[..read]).to_lowercase()
+        });
+
+        let client = Client::builder()
+            .contact("mail@example.org")
+            .build()
+            .unwrap();
+        client.send(client.get(url)).await.unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.contains(&format!(
+            "user-agent: wiki-tui/{} (mail@example.org)",
+            env!("CARGO_PKG_VERSION")
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_reports_the_received_bytes() {
+        let (url, _) = mock_server(vec![OK]).await;
+        let progress = Progress::default();
+        let client = test_client(0).with_progress(progress.clone());
+
+        let response = client.send(client.get(url)).await.unwrap();
+        client.response_json(response).await.unwrap();
+        assert_eq!(progress.bytes_received(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reports_rate_limiting() {
+        let (url, _) = mock_server(vec![RATE_LIMITED_LONG]).await;
+        let client = test_client(0);
+
+        let response = client.send(client.get(url)).await.unwrap();
+        let error = client.response_json(response).await.unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::RateLimited {
+                retry_after: Some(120)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_rejects_invalid_proxies() {
+        assert!(Client::builder().proxy("not a proxy").build().is_err());
+    }
+}
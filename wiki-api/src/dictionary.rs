@@ -0,0 +1,342 @@
+//! Structured definitions of a Wiktionary entry
+//!
+//! Wiktionary pages are parsed like every other page. The sections of the [`Document`] are then
+//! interpreted by their headers: every language has its own section, containing the etymology,
+//! the pronunciation and a section for each part of speech with an ordered list of definitions.
+//! The headers are recognized in the layout of the English Wiktionary, entries of other editions
+//! may not be found
+//!
+//! The parts of an entry are stored as indices of the nodes in the document, so they can be
+//! rendered and selected like the rest of the page
+
+use crate::document::{Data, Document, HeaderKind, Node};
+
+/// Headers of the sections containing the definitions of a part of speech
+const PARTS_OF_SPEECH: &[&str] = &[
+    "abbreviation",
+    "acronym",
+    "adjective",
+    "adverb",
+    "affix",
+    "article",
+    "circumfix",
+    "classifier",
+    "conjunction",
+    "contraction",
+    "counter",
+    "determiner",
+    "han character",
+    "idiom",
+    "infix",
+    "initialism",
+    "interfix",
+    "interjection",
+    "letter",
+    "noun",
+    "number",
+    "numeral",
+    "participle",
+    "particle",
+    "phrase",
+    "postposition",
+    "prefix",
+    "preposition",
+    "prepositional phrase",
+    "pronoun",
+    "proper noun",
+    "proverb",
+    "root",
+    "suffix",
+    "symbol",
+    "syllable",
+    "verb",
+];
+
+/// The entries of a Wiktionary page, one for each language the word exists in
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dictionary {
+    pub entries: Vec<Entry>,
+}
+
+/// The entry of a word in one language
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Name of the language, as written in the header of its section
+    pub language: String,
+    /// Index of the header of the language section
+    pub header: usize,
+    /// Indices of the paragraphs describing the origin of the word. Words with multiple
+    /// etymologies have the paragraphs of all of them
+    pub etymology: Vec<usize>,
+    /// Indices of the list items with the pronunciation
+    pub pronunciation: Vec<usize>,
+    pub parts_of_speech: Vec<PartOfSpeech>,
+}
+
+/// The definitions of a word used as one part of speech
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartOfSpeech {
+    /// Name of the part of speech, as written in the header of its section
+    pub name: String,
+    /// Index of the header of the section
+    pub header: usize,
+    /// Index of the paragraph with the headword line (the word and its inflections)
+    pub headword: Option<usize>,
+    pub definitions: Vec<Definition>,
+}
+
+/// One sense of a word
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    /// Indices of the nodes making up the definition, without its examples and subsenses
+    pub content: Vec<usize>,
+    /// Indices of the usage examples
+    pub examples: Vec<usize>,
+    /// More specific senses of this definition
+    pub subdefinitions: Vec<Definition>,
+}
+
+impl Dictionary {
+    /// Finds the entries in a parsed Wiktionary page
+    pub fn from_document(document: &Document) -> Dictionary {
+        let entries = document
+            .nth(0)
+            .map(|root| {
+                root.descendants()
+                    .filter(|node| matches!(node.data(), Data::Section { .. }))
+                    .filter_map(|section| {
+                        let header = section_header(section)?;
+                        match header.data() {
+                            Data::Header {
+                                kind: HeaderKind::Sub,
+                                ..
+                            } => Some(Entry::from_section(section, header)),
+                            _ => None,
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Dictionary { entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Entry {
+    fn from_section(section: Node<'_>, header: Node<'_>) -> Entry {
+        let mut entry = Entry {
+            language: text(header),
+            header: header.index(),
+            etymology: Vec::new(),
+            pronunciation: Vec::new(),
+            parts_of_speech: Vec::new(),
+        };
+        entry.add_subsections(section);
+        entry
+    }
+
+    /// Sorts the subsections by their headers. The parts of speech of words with multiple
+    /// etymologies are nested in the etymology sections
+    fn add_subsections(&mut self, section: Node<'_>) {
+        for subsection in section
+            .children()
+            .filter(|node| matches!(node.data(), Data::Section { .. }))
+        {
+            if let Some(header) = section_header(subsection) {
+                let name = text(header);
+                let lowercase = name.to_lowercase();
+                if lowercase.starts_with("etymology") {
+                    self.etymology.extend(
+                        content(subsection)
+                            .filter(|node| matches!(node.data(), Data::Paragraph))
+                            .map(|node| node.index()),
+                    );
+                } else if lowercase.starts_with("pronunciation") {
+                    self.pronunciation.extend(
+                        content(subsection)
+                            .filter(|node| matches!(node.data(), Data::UnorderedList))
+                            .flat_map(|list| list.children())
+                            .filter(|node| matches!(node.data(), Data::ListItem))
+                            .map(|node| node.index()),
+                    );
+                } else if PARTS_OF_SPEECH.contains(&lowercase.as_str()) {
+                    self.parts_of_speech
+                        .push(PartOfSpeech::from_section(subsection, header, name));
+                }
+            }
+
+            self.add_subsections(subsection);
+        }
+    }
+}
+
+impl PartOfSpeech {
+    fn from_section(section: Node<'_>, header: Node<'_>, name: String) -> PartOfSpeech {
+        PartOfSpeech {
+            name,
+            header: header.index(),
+            headword: content(section)
+                .find(|node| matches!(node.data(), Data::Paragraph))
+                .map(|node| node.index()),
+            definitions: content(section)
+                .find(|node| matches!(node.data(), Data::OrderedList))
+                .map(definitions)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Returns the definitions of the items in an ordered list
+fn definitions(list: Node<'_>) -> Vec<Definition> {
+    list.children()
+        .filter(|node| matches!(node.data(), Data::ListItem))
+        .filter(|item| item.children().any(|node| !text(node).is_empty()))
+        .map(|item| {
+            let mut definition = Definition {
+                content: Vec::new(),
+                examples: Vec::new(),
+                subdefinitions: Vec::new(),
+            };
+            for child in item.children() {
+                match child.data() {
+                    Data::OrderedList => definition.subdefinitions.extend(definitions(child)),
+                    Data::DescriptionList => definition.examples.extend(
+                        child
+                            .children()
+                            .filter(|node| matches!(node.data(), Data::DerscriptionListDescription))
+                            .map(|node| node.index()),
+                    ),
+                    // quotations are left out to keep the definitions short
+                    Data::UnorderedList => {}
+                    _ => definition.content.push(child.index()),
+                }
+            }
+            definition
+        })
+        .collect()
+}
+
+/// Returns the header of the section. Headers of subsections don't count
+fn section_header(section: Node<'_>) -> Option<Node<'_>> {
+    let header = section
+        .descendants()
+        .find(|node| matches!(node.data(), Data::Header { .. }))?;
+
+    // the nearest section around the header has to be this one
+    let mut parent = header.parent();
+    while let Some(node) = parent {
+        if matches!(node.data(), Data::Section { .. }) {
+            break;
+        }
+        parent = node.parent();
+    }
+    (parent.map(|node| node.index()) == Some(section.index())).then_some(header)
+}
+
+/// Returns the children of the section that are neither its header nor a subsection
+fn content(section: Node<'_>) -> impl Iterator<Item = Node<'_>> {
+    section.children().filter(|child| {
+        !matches!(child.data(), Data::Section { .. } | Data::Header { .. })
+            && !child
+                .descendants()
+                .any(|node| matches!(node.data(), Data::Header { .. }))
+    })
+}
+
+/// Returns the text of the node and its descendants, with the whitespace collapsed
+pub fn text(node: Node<'_>) -> String {
+    let mut text = String::new();
+    for node in std::iter::once(node).chain(node.descendants()) {
+        if let Data::Text { contents } = node.data() {
+            text.push_str(contents);
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{text, Dictionary};
+    use crate::{
+        document::Document,
+        languages::Language,
+        parser::{Parser, WikipediaParser},
+        Endpoint,
+    };
+
+    fn document() -> Document {
+        let nodes = WikipediaParser::parse_document(
+            include_str!("../tests/fixtures/wiktionary_entry.html"),
+            Endpoint::wiktionary(Language::English),
+            Language::English,
+        )
+        .nodes();
+        Document { nodes }
+    }
+
+    #[test]
+    fn test_entries_by_language() {
+        let document = document();
+        let dictionary = Dictionary::from_document(&document);
+
+        let languages: Vec<&str> = dictionary
+            .entries
+            .iter()
+            .map(|entry| entry.language.as_str())
+            .collect();
+        assert_eq!(languages, vec!["English", "Dutch"]);
+    }
+
+    #[test]
+    fn test_english_entry() {
+        let document = document();
+        let dictionary = Dictionary::from_document(&document);
+        let english = &dictionary.entries[0];
+        let nth_text = |index: usize| text(document.nth(index).unwrap());
+
+        // both etymologies, the parts of speech are nested in them
+        assert_eq!(english.etymology.len(), 2);
+        assert!(nth_text(english.etymology[0]).starts_with("From Middle English"));
+        assert_eq!(english.pronunciation.len(), 1);
+        assert_eq!(nth_text(english.pronunciation[0]), "IPA: /bæŋk/");
+
+        let names: Vec<&str> = english
+            .parts_of_speech
+            .iter()
+            .map(|pos| pos.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Noun", "Verb"]);
+
+        let noun = &english.parts_of_speech[0];
+        assert_eq!(nth_text(noun.headword.unwrap()), "bank (plural banks)");
+        assert_eq!(noun.definitions.len(), 2);
+
+        let institution = &noun.definitions[0];
+        let content: Vec<String> = institution.content.iter().map(|x| nth_text(*x)).collect();
+        assert!(content
+            .join(" ")
+            .starts_with("An institution where one can place and borrow money"));
+        assert_eq!(institution.examples.len(), 1);
+        assert_eq!(
+            nth_text(institution.examples[0]),
+            "She went to the bank to deposit her paycheck."
+        );
+        assert_eq!(institution.subdefinitions.len(), 1);
+        assert!(noun.definitions[1].examples.is_empty());
+    }
+
+    #[test]
+    fn test_not_a_dictionary() {
+        let nodes = WikipediaParser::parse_document(
+            "<section data-mw-section-id=\"0\"><p>Just an article</p></section>",
+            Endpoint::wikipedia(Language::English),
+            Language::English,
+        )
+        .nodes();
+        assert!(Dictionary::from_document(&Document { nodes }).is_empty());
+    }
+}
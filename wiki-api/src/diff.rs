@@ -0,0 +1,631 @@
+use reqwest::Response;
+use scraper::{ElementRef, Html, Selector};
+use snafu::OptionExt;
+
+use crate::{
+    document::{Data, Document},
+    error::{MissingFieldSnafu, Result},
+    Client, Endpoint,
+};
+
+/// When two sequences differ in more places than this, they are treated as completely different
+/// instead of searching for the shortest edit script. This bounds the memory used by the diff
+const MAX_EDIT_DISTANCE: usize = 1000;
+
+/// A word-level diff between two versions of a page. The diff is a sequence of paragraphs, each
+/// containing the unchanged, inserted and deleted words in reading order
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Diff {
+    pub paragraphs: Vec<DiffParagraph>,
+}
+
+/// A paragraph in a [`Diff`]
+///
+/// [`Diff`]: Diff
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DiffParagraph {
+    pub spans: Vec<DiffSpan>,
+}
+
+/// A run of words that share the same [`ChangeKind`]
+///
+/// [`ChangeKind`]: ChangeKind
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DiffSpan {
+    pub kind: ChangeKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ChangeKind {
+    Unchanged,
+    Inserted,
+    Deleted,
+}
+
+impl DiffParagraph {
+    fn with_kind(kind: ChangeKind, text: impl Into<String>) -> Self {
+        DiffParagraph {
+            spans: vec![DiffSpan {
+                kind,
+                text: text.into(),
+            }],
+        }
+    }
+
+    /// Whether the paragraph contains inserted or deleted words
+    pub fn is_changed(&self) -> bool {
+        self.spans
+            .iter()
+            .any(|span| span.kind != ChangeKind::Unchanged)
+    }
+}
+
+impl Diff {
+    /// Creates a [`CompareBuilder`] to fetch the diff between two revisions from the server
+    ///
+    /// [`CompareBuilder`]: CompareBuilder
+    pub fn compare() -> CompareBuilder<NoFrom, NoTo, NoEndpoint> {
+        CompareBuilder::default()
+    }
+
+    /// Computes the diff between the text of two documents
+    pub fn between(old: &Document, new: &Document) -> Diff {
+        Diff::from_paragraphs(&paragraphs(old), &paragraphs(new))
+    }
+
+    /// Computes the diff between two lists of paragraphs. Paragraphs are compared as a whole
+    /// first, changed paragraphs are then compared word by word
+    pub fn from_paragraphs(old: &[String], new: &[String]) -> Diff {
+        let mut diff = Diff::default();
+        let (mut deleted, mut inserted) = (Vec::new(), Vec::new());
+        let (mut old_i, mut new_i) = (0, 0);
+
+        for edit in shortest_edit(old, new) {
+            match edit {
+                Edit::Equal => {
+                    diff.push_hunk(&mut deleted, &mut inserted);
+                    diff.paragraphs.push(DiffParagraph::with_kind(
+                        ChangeKind::Unchanged,
+                        old[old_i].clone(),
+                    ));
+                    old_i += 1;
+                    new_i += 1;
+                }
+                Edit::Delete => {
+                    deleted.push(old[old_i].as_str());
+                    old_i += 1;
+                }
+                Edit::Insert => {
+                    inserted.push(new[new_i].as_str());
+                    new_i += 1;
+                }
+            }
+        }
+        diff.push_hunk(&mut deleted, &mut inserted);
+
+        diff
+    }
+
+    /// Adds a block of replaced paragraphs. Deleted and inserted paragraphs are paired up in
+    /// order and compared word by word, the rest is added as a whole
+    fn push_hunk(&mut self, deleted: &mut Vec<&str>, inserted: &mut Vec<&str>) {
+        let paired = deleted.len().min(inserted.len());
+        for (old, new) in deleted.iter().zip(inserted.iter()) {
+            self.paragraphs.push(diff_words(old, new));
+        }
+        for old in &deleted[paired..] {
+            self.paragraphs
+                .push(DiffParagraph::with_kind(ChangeKind::Deleted, *old));
+        }
+        for new in &inserted[paired..] {
+            self.paragraphs
+                .push(DiffParagraph::with_kind(ChangeKind::Inserted, *new));
+        }
+        deleted.clear();
+        inserted.clear();
+    }
+
+    /// Returns the indices of all paragraphs that contain changes
+    pub fn changes(&self) -> Vec<usize> {
+        self.paragraphs
+            .iter()
+            .enumerate()
+            .filter(|(_, paragraph)| paragraph.is_changed())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Whether both versions are the same
+    pub fn is_empty(&self) -> bool {
+        !self.paragraphs.iter().any(DiffParagraph::is_changed)
+    }
+}
+
+/// Compares two paragraphs word by word
+fn diff_words(old: &str, new: &str) -> DiffParagraph {
+    let old_words: Vec<&str> = old.split_whitespace().collect();
+    let new_words: Vec<&str> = new.split_whitespace().collect();
+    let (mut old_i, mut new_i) = (0, 0);
+
+    let mut paragraph = DiffParagraph::default();
+    for edit in shortest_edit(&old_words, &new_words) {
+        let (kind, word) = match edit {
+            Edit::Equal => {
+                new_i += 1;
+                old_i += 1;
+                (ChangeKind::Unchanged, old_words[old_i - 1])
+            }
+            Edit::Delete => {
+                old_i += 1;
+                (ChangeKind::Deleted, old_words[old_i - 1])
+            }
+            Edit::Insert => {
+                new_i += 1;
+                (ChangeKind::Inserted, new_words[new_i - 1])
+            }
+        };
+
+        let is_first = paragraph.spans.is_empty();
+        match paragraph.spans.last_mut() {
+            Some(span) if span.kind == kind => {
+                span.text.push(' ');
+                span.text.push_str(word);
+            }
+            _ => paragraph.spans.push(DiffSpan {
+                kind,
+                text: if is_first {
+                    word.to_string()
+                } else {
+                    format!(" {}", word)
+                },
+            }),
+        }
+    }
+    paragraph
+}
+
+/// Extracts the text of a document, split into paragraphs with normalized whitespace
+fn paragraphs(document: &Document) -> Vec<String> {
+    fn flush(paragraphs: &mut Vec<String>, current: &mut String) {
+        let text = current.split_whitespace().collect::<Vec<&str>>().join(" ");
+        if !text.is_empty() {
+            paragraphs.push(text);
+        }
+        current.clear();
+    }
+
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    let root = match document.nth(0) {
+        Some(root) => root,
+        None => return paragraphs,
+    };
+
+    for node in root.descendants() {
+        match node.data() {
+            Data::Text { contents } => current.push_str(contents),
+            Data::Linebreak => current.push(' '),
+            Data::Section { .. }
+            | Data::Header { .. }
+            | Data::Division
+            | Data::Paragraph
+            | Data::Hatnote
+            | Data::Blockquote
+            | Data::ListItem
+            | Data::DescriptionListTerm
+            | Data::DerscriptionListDescription => flush(&mut paragraphs, &mut current),
+            _ => {}
+        }
+    }
+    flush(&mut paragraphs, &mut current);
+
+    paragraphs
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Finds the shortest edit script that turns `old` into `new` using Myers' algorithm
+fn shortest_edit<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    // the common prefix and suffix don't need to go through the algorithm
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut edits = vec![Edit::Equal; prefix];
+    edits.extend(myers(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    edits.extend(std::iter::repeat(Edit::Equal).take(suffix));
+    edits
+}
+
+fn myers<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    let replace_all = || {
+        let mut edits = vec![Edit::Delete; old.len()];
+        edits.extend(std::iter::repeat(Edit::Insert).take(new.len()));
+        edits
+    };
+
+    if old.is_empty() || new.is_empty() {
+        return replace_all();
+    }
+
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = n + m;
+    let offset = max + 1;
+    let mut v = vec![0_isize; 2 * max as usize + 3];
+    // for every d, the part of v that is needed for backtracking (diagonals -d..=d)
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max {
+        if d as usize > MAX_EDIT_DISTANCE {
+            return replace_all();
+        }
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+
+        for k in (-d..=d).step_by(2) {
+            let idx = |k: isize| (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[idx(k - 1)] < v[idx(k + 1)]) {
+                v[idx(k + 1)]
+            } else {
+                v[idx(k - 1)] + 1
+            };
+            let mut y = x - k;
+
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx(k)] = x;
+
+            if x >= n && y >= m {
+                return backtrack(&trace, n, m);
+            }
+        }
+    }
+
+    unreachable!("the edit script can't be longer than both sequences combined")
+}
+
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<Edit> {
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        if d == 0 {
+            edits.extend(std::iter::repeat(Edit::Equal).take(x as usize));
+            break;
+        }
+
+        let get = |k: isize| v[(k + d) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && get(k - 1) < get(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = get(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            edits.push(Edit::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        edits.push(if x == prev_x {
+            Edit::Insert
+        } else {
+            Edit::Delete
+        });
+        (x, y) = (prev_x, prev_y);
+    }
+
+    edits.reverse();
+    edits
+}
+
+#[doc(hidden)]
+pub struct WithFrom(usize);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoFrom;
+
+#[doc(hidden)]
+pub struct WithTo(usize);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoTo;
+
+#[doc(hidden)]
+pub struct WithEndpoint(Endpoint);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoEndpoint;
+
+/// A fully configured `CompareBuilder` that can be used to fetch the diff. This is a convenience
+/// type
+pub type CompareRequest = CompareBuilder<WithFrom, WithTo, WithEndpoint>;
+
+/// A `CompareBuilder` can be used to fetch the diff between two revisions using `action=compare`
+///
+/// Read more in the [MediaWiki API docs](https://www.mediawiki.org/wiki/API:Compare)
+#[derive(Default)]
+pub struct CompareBuilder<F, T, E> {
+    from: F,
+    to: T,
+    endpoint: E,
+    client: Client,
+}
+
+impl<T, E> CompareBuilder<NoFrom, T, E> {
+    /// The older revision
+    pub fn from_revision(self, revid: usize) -> CompareBuilder<WithFrom, T, E> {
+        CompareBuilder {
+            from: WithFrom(revid),
+            to: self.to,
+            endpoint: self.endpoint,
+            client: self.client,
+        }
+    }
+}
+
+impl<F, E> CompareBuilder<F, NoTo, E> {
+    /// The newer revision
+    pub fn to_revision(self, revid: usize) -> CompareBuilder<F, WithTo, E> {
+        CompareBuilder {
+            from: self.from,
+            to: WithTo(revid),
+            endpoint: self.endpoint,
+            client: self.client,
+        }
+    }
+}
+
+impl<F, T> CompareBuilder<F, T, NoEndpoint> {
+    /// API endpoint for the MediaWiki site the revisions belong to
+    pub fn endpoint(self, endpoint: Endpoint) -> CompareBuilder<F, T, WithEndpoint> {
+        CompareBuilder {
+            from: self.from,
+            to: self.to,
+            endpoint: WithEndpoint(endpoint),
+            client: self.client,
+        }
+    }
+}
+
+impl<F, T, E> CompareBuilder<F, T, E> {
+    /// Http client used to send the request
+    ///
+    /// Default: a shared [`Client`] with the default configuration
+    ///
+    /// [`Client`]: Client
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+impl CompareBuilder<WithFrom, WithTo, WithEndpoint> {
+    /// Fetches the diff between the two revisions. The server compares the wikitext of the
+    /// revisions, changed lines are then compared word by word
+    ///
+    /// # Error
+    ///
+    /// This function returns an error when one of the following things happens:
+    /// - The request to the server could not be made
+    /// - The server returned an error
+    /// - The returned result could not interpreted as a `Diff`
+    pub async fn fetch(self) -> Result<Diff> {
+        async fn action_compare(
+            client: &Client,
+            params: Vec<(&str, String)>,
+            endpoint: Endpoint,
+        ) -> Result<Response> {
+            let request = client
+                .get(endpoint.api_url().clone())
+                .query(&[
+                    ("action", "compare"),
+                    ("format", "json"),
+                    ("formatversion", "2"),
+                    ("prop", "diff|ids"),
+                ])
+                .query(&params);
+            client.send(request).await
+        }
+
+        let params = vec![
+            ("fromrev", self.from.0.to_string()),
+            ("torev", self.to.0.to_string()),
+        ];
+
+        let response = action_compare(&self.client, params, self.endpoint.0).await?;
+        let res_json = self.client.response_json(response).await?;
+
+        diff_from_json(res_json)
+    }
+}
+
+fn diff_from_json(res_json: serde_json::Value) -> Result<Diff> {
+    let body = res_json
+        .get("compare")
+        .and_then(|x| x.get("body"))
+        .and_then(|x| x.as_str())
+        .context(MissingFieldSnafu { what: "diff" })?;
+
+    Ok(diff_from_html(body))
+}
+
+/// Interprets the table rows returned by `action=compare`
+fn diff_from_html(body: &str) -> Diff {
+    fn has_class(element: &ElementRef, class: &str) -> bool {
+        element.value().classes().any(|x| x == class)
+    }
+
+    fn cell_text(element: &ElementRef) -> String {
+        element
+            .text()
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    // the rows need a table around them, otherwise they are dropped by the parser
+    let fragment = Html::parse_fragment(&format!("<table>{}</table>", body));
+    let row_selector = Selector::parse("tr").unwrap();
+    let cell_selector = Selector::parse("td").unwrap();
+
+    let mut diff = Diff::default();
+    for row in fragment.select(&row_selector) {
+        let (mut context, mut deleted, mut inserted) = (None, None, None);
+        let mut is_lineno = false;
+
+        for cell in row.select(&cell_selector) {
+            if has_class(&cell, "diff-lineno") {
+                is_lineno = true;
+            } else if has_class(&cell, "diff-context") {
+                context.get_or_insert_with(|| cell_text(&cell));
+            } else if has_class(&cell, "diff-deletedline") {
+                deleted = Some(cell_text(&cell));
+            } else if has_class(&cell, "diff-addedline") {
+                inserted = Some(cell_text(&cell));
+            }
+        }
+
+        // line numbers mark skipped, unchanged parts of the page
+        if is_lineno {
+            if !diff.paragraphs.is_empty() {
+                diff.paragraphs
+                    .push(DiffParagraph::with_kind(ChangeKind::Unchanged, "..."));
+            }
+            continue;
+        }
+
+        let paragraph = match (context, deleted, inserted) {
+            (_, Some(old), Some(new)) => diff_words(&old, &new),
+            (_, Some(old), None) => DiffParagraph::with_kind(ChangeKind::Deleted, old),
+            (_, None, Some(new)) => DiffParagraph::with_kind(ChangeKind::Inserted, new),
+            (Some(text), None, None) => DiffParagraph::with_kind(ChangeKind::Unchanged, text),
+            (None, None, None) => continue,
+        };
+
+        if paragraph.spans.iter().all(|span| span.text.is_empty()) {
+            continue;
+        }
+        diff.paragraphs.push(paragraph);
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_from_json, diff_words, shortest_edit, ChangeKind, Diff, Edit};
+
+    #[test]
+    fn test_shortest_edit() {
+        let old: Vec<char> = "ABCABBA".chars().collect();
+        let new: Vec<char> = "CBABAC".chars().collect();
+        let edits = shortest_edit(&old, &new);
+
+        // the shortest edit script between these two sequences has a length of 5
+        let changes = edits.iter().filter(|x| **x != Edit::Equal).count();
+        assert_eq!(changes, 5);
+
+        // applying the edits to the old sequence must result in the new sequence
+        let (mut old_i, mut result) = (0, Vec::new());
+        let mut new_i = 0;
+        for edit in edits {
+            match edit {
+                Edit::Equal => {
+                    result.push(old[old_i]);
+                    old_i += 1;
+                    new_i += 1;
+                }
+                Edit::Delete => old_i += 1,
+                Edit::Insert => {
+                    result.push(new[new_i]);
+                    new_i += 1;
+                }
+            }
+        }
+        assert_eq!(result, new);
+    }
+
+    #[test]
+    fn test_diff_words() {
+        let paragraph = diff_words("the quick brown fox", "the slow brown fox jumps");
+        let spans: Vec<(ChangeKind, &str)> = paragraph
+            .spans
+            .iter()
+            .map(|x| (x.kind, x.text.as_str()))
+            .collect();
+
+        assert_eq!(
+            spans,
+            vec![
+                (ChangeKind::Unchanged, "the"),
+                (ChangeKind::Deleted, " quick"),
+                (ChangeKind::Inserted, " slow"),
+                (ChangeKind::Unchanged, " brown fox"),
+                (ChangeKind::Inserted, " jumps"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_paragraphs() {
+        let old = ["Intro".to_string(), "Some text".to_string()];
+        let new = [
+            "Intro".to_string(),
+            "Some other text".to_string(),
+            "A new paragraph".to_string(),
+        ];
+
+        let diff = Diff::from_paragraphs(&old, &new);
+        assert_eq!(diff.paragraphs.len(), 3);
+        assert_eq!(diff.changes(), vec![1, 2]);
+        assert!(!diff.paragraphs[0].is_changed());
+        assert_eq!(diff.paragraphs[2].spans[0].kind, ChangeKind::Inserted);
+
+        assert!(Diff::from_paragraphs(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_diff_from_fixture() {
+        let res_json = serde_json::from_str(include_str!("../tests/fixtures/compare.json"))
+            .expect("the fixture should be valid json");
+
+        let diff = diff_from_json(res_json).unwrap();
+        assert_eq!(diff.changes(), vec![1, 3, 4]);
+        assert_eq!(diff.paragraphs[2].spans[0].text, "...");
+
+        let changed = &diff.paragraphs[1];
+        assert!(changed
+            .spans
+            .iter()
+            .any(|x| x.kind == ChangeKind::Deleted && x.text == " red"));
+        assert!(changed
+            .spans
+            .iter()
+            .any(|x| x.kind == ChangeKind::Inserted && x.text == " blue"));
+
+        assert_eq!(diff.paragraphs[3].spans[0].kind, ChangeKind::Deleted);
+        assert_eq!(diff.paragraphs[4].spans[0].kind, ChangeKind::Inserted);
+    }
+}
@@ -0,0 +1,449 @@
+use std::{fmt::Display, str::FromStr, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use url::Url;
+
+use crate::languages::Language;
+
+const LANGUAGE_PLACEHOLDER: &str = "{lang}";
+const TITLE_PLACEHOLDER: &str = "$1";
+const DEFAULT_ARTICLE_PATH: &str = "/wiki/$1";
+
+/// The kind of wiki farm a site belongs to. This is used to guess the layout of a site when only
+/// its api url is known
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Project {
+    /// One of the language editions of Wikipedia (`{lang}.wikipedia.org`)
+    #[default]
+    Wikipedia,
+    /// One of the language editions of Wiktionary (`{lang}.wiktionary.org`)
+    Wiktionary,
+    /// A wiki hosted on Fandom (`{name}.fandom.com`)
+    Fandom,
+    /// Any other MediaWiki site, e.g. a self-hosted one
+    MediaWiki,
+}
+
+impl Display for Project {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Project::Wikipedia => write!(f, "Wikipedia"),
+            Project::Wiktionary => write!(f, "Wiktionary"),
+            Project::Fandom => write!(f, "Fandom"),
+            Project::MediaWiki => write!(f, "MediaWiki"),
+        }
+    }
+}
+
+/// The API endpoint of a MediaWiki site
+///
+/// Besides the url of the `api.php` of the site, an endpoint knows the name of the site, the
+/// project it belongs to, where its articles can be found and, for sites with multiple language
+/// editions, how the api url changes with the language
+///
+/// # Example
+///
+/// ```
+/// use wiki_api::{endpoint::Endpoint, languages::Language};
+///
+/// let endpoint = Endpoint::wikipedia(Language::English);
+/// let german = endpoint.for_language(Language::German);
+/// assert_eq!(german.api_url().as_str(), "https://de.wikipedia.org/w/api.php");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Endpoint {
+    // every link in a page holds an endpoint, so it is shared instead of copied
+    inner: Arc<EndpointInner>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+struct EndpointInner {
+    site_name: String,
+    project: Project,
+    url: Url,
+    url_template: Option<String>,
+    article_path: String,
+}
+
+impl Endpoint {
+    /// Creates an endpoint for a site with a single language edition. The article path defaults
+    /// to `/wiki/$1`
+    pub fn new(site_name: impl Into<String>, project: Project, url: Url) -> Self {
+        EndpointInner {
+            site_name: site_name.into(),
+            project,
+            url,
+            url_template: None,
+            article_path: DEFAULT_ARTICLE_PATH.to_string(),
+        }
+        .into()
+    }
+
+    /// The endpoint of the Wikipedia in the given language
+    pub fn wikipedia(language: Language) -> Self {
+        Endpoint::new(
+            "Wikipedia",
+            Project::Wikipedia,
+            Url::parse("https://en.wikipedia.org/w/api.php").expect("Hardcoded links should work"),
+        )
+        .with_url_template("https://{lang}.wikipedia.org/w/api.php")
+        .expect("Hardcoded links should work")
+        .for_language(language)
+    }
+
+    /// The endpoint of the Wiktionary in the given language
+    pub fn wiktionary(language: Language) -> Self {
+        Endpoint::new(
+            "Wiktionary",
+            Project::Wiktionary,
+            Url::parse("https://en.wiktionary.org/w/api.php").expect("Hardcoded links should work"),
+        )
+        .with_url_template("https://{lang}.wiktionary.org/w/api.php")
+        .expect("Hardcoded links should work")
+        .for_language(language)
+    }
+
+    /// Parses the api url of a site and guesses the rest of the endpoint from it (See
+    /// [`Endpoint::from_url`])
+    ///
+    /// [`Endpoint::from_url`]: Endpoint::from_url
+    pub fn parse(url: &str) -> Result<Self, url::ParseError> {
+        Ok(Endpoint::from_url(Url::parse(url)?))
+    }
+
+    /// Creates an endpoint from the api url of a site. Wikipedia, Wiktionary and Fandom urls are
+    /// recognized, every other url is treated as a self-hosted MediaWiki site with a single
+    /// language
+    pub fn from_url(url: Url) -> Self {
+        let host = url.host_str().unwrap_or_default().to_string();
+
+        let language_project = match host.split_once('.') {
+            Some((language, domain @ "wikipedia.org")) => {
+                Some((language, domain, Project::Wikipedia))
+            }
+            Some((language, domain @ "wiktionary.org")) => {
+                Some((language, domain, Project::Wiktionary))
+            }
+            _ => None,
+        };
+        if let Some((language, domain, project)) = language_project {
+            if Language::from_str(language).is_ok() {
+                let template = format!(
+                    "{}://{}.{}{}",
+                    url.scheme(),
+                    LANGUAGE_PLACEHOLDER,
+                    domain,
+                    url.path()
+                );
+                return EndpointInner {
+                    site_name: project.to_string(),
+                    project,
+                    url,
+                    url_template: Some(template),
+                    article_path: DEFAULT_ARTICLE_PATH.to_string(),
+                }
+                .into();
+            }
+        }
+
+        if let Some((name, "fandom.com")) = host.split_once('.') {
+            return Endpoint::new(name.to_string(), Project::Fandom, url);
+        }
+
+        Endpoint::new(host, Project::MediaWiki, url)
+    }
+
+    /// Sets the template used for building the api url of a language edition. `{lang}` is
+    /// replaced with the code of the language
+    ///
+    /// # Error
+    ///
+    /// Returns an error when the template doesn't contain `{lang}` or does not result in a valid
+    /// url
+    pub fn with_url_template(mut self, template: impl Into<String>) -> Result<Self, EndpointError> {
+        let template = template.into();
+        if !template.contains(LANGUAGE_PLACEHOLDER) {
+            return Err(EndpointError::MissingPlaceholder(template));
+        }
+        Url::parse(&template.replace(LANGUAGE_PLACEHOLDER, Language::default().code()))
+            .map_err(|error| EndpointError::InvalidUrl(template.clone(), error))?;
+
+        Arc::make_mut(&mut self.inner).url_template = Some(template);
+        Ok(self)
+    }
+
+    /// Sets the path of the articles on the site. `$1` is replaced with the title of the article
+    pub fn with_article_path(mut self, article_path: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.inner).article_path = article_path.into();
+        self
+    }
+
+    /// Sets the human readable name of the site
+    pub fn with_site_name(mut self, site_name: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.inner).site_name = site_name.into();
+        self
+    }
+
+    /// Returns the endpoint of the given language edition of the site. Sites with a single
+    /// language edition return themselves
+    pub fn for_language(&self, language: Language) -> Self {
+        let template = match self.inner.url_template {
+            Some(ref template) => template,
+            None => return self.clone(),
+        };
+
+        match Url::parse(&template.replace(LANGUAGE_PLACEHOLDER, language.code())) {
+            Ok(url) => EndpointInner {
+                url,
+                ..self.inner.as_ref().clone()
+            }
+            .into(),
+            Err(error) => {
+                warn!(
+                    "failed building the endpoint for '{}' from '{}': {}",
+                    language.code(),
+                    template,
+                    error
+                );
+                self.clone()
+            }
+        }
+    }
+
+    /// Returns the language edition a url of this site belongs to. This is only known for sites
+    /// with multiple language editions
+    pub fn language_of(&self, url: &Url) -> Option<Language> {
+        // the placeholder is not allowed in a host, so the template can't be parsed as a url
+        let (_, template) = self.inner.url_template.as_ref()?.split_once("://")?;
+        let template_host = template.split('/').next()?;
+        let (prefix, suffix) = template_host.split_once(LANGUAGE_PLACEHOLDER)?;
+        let code = url.host_str()?.strip_prefix(prefix)?.strip_suffix(suffix)?;
+        Language::from_str(code).ok()
+    }
+
+    /// Whether the site has multiple language editions
+    pub fn supports_languages(&self) -> bool {
+        self.inner.url_template.is_some()
+    }
+
+    /// Url of the article with the given title
+    pub fn article_url(&self, title: &str) -> Option<Url> {
+        let path = self
+            .inner
+            .article_path
+            .replace(TITLE_PLACEHOLDER, &title.replace(' ', "_"));
+        self.inner.url.join(&path).ok()
+    }
+
+    /// Human readable name of the site
+    pub fn site_name(&self) -> &str {
+        &self.inner.site_name
+    }
+
+    /// The project the site belongs to
+    pub fn project(&self) -> Project {
+        self.inner.project
+    }
+
+    /// Url of the `api.php` of the site
+    pub fn api_url(&self) -> &Url {
+        &self.inner.url
+    }
+
+    /// Template for building the api url of a language edition, if the site has multiple
+    pub fn url_template(&self) -> Option<&str> {
+        self.inner.url_template.as_deref()
+    }
+
+    /// Path of the articles on the site, `$1` is the title of the article
+    pub fn article_path(&self) -> &str {
+        &self.inner.article_path
+    }
+}
+
+impl Default for Endpoint {
+    fn default() -> Self {
+        Endpoint::wikipedia(Language::default())
+    }
+}
+
+impl From<EndpointInner> for Endpoint {
+    fn from(inner: EndpointInner) -> Self {
+        Endpoint {
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+impl From<Url> for Endpoint {
+    fn from(url: Url) -> Self {
+        Endpoint::from_url(url)
+    }
+}
+
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.inner.site_name, self.inner.url)
+    }
+}
+
+/// Endpoints used to be plain urls. To keep caches from older versions readable, the url is
+/// also accepted
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawEndpoint {
+    Url(Url),
+    Endpoint {
+        site_name: String,
+        project: Project,
+        url: Url,
+        url_template: Option<String>,
+        article_path: String,
+    },
+}
+
+impl Serialize for Endpoint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.inner.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Endpoint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(match RawEndpoint::deserialize(deserializer)? {
+            RawEndpoint::Url(url) => Endpoint::from_url(url),
+            RawEndpoint::Endpoint {
+                site_name,
+                project,
+                url,
+                url_template,
+                article_path,
+            } => EndpointInner {
+                site_name,
+                project,
+                url,
+                url_template,
+                article_path,
+            }
+            .into(),
+        })
+    }
+}
+
+/// Errors that can occur when configuring an [`Endpoint`]
+///
+/// [`Endpoint`]: Endpoint
+#[derive(Debug)]
+pub enum EndpointError {
+    /// The url template doesn't contain the `{lang}` placeholder
+    MissingPlaceholder(String),
+    /// The url template doesn't result in a valid url
+    InvalidUrl(String, url::ParseError),
+}
+
+impl Display for EndpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EndpointError::MissingPlaceholder(template) => write!(
+                f,
+                "the url template '{}' is missing the '{}' placeholder",
+                template, LANGUAGE_PLACEHOLDER
+            ),
+            EndpointError::InvalidUrl(template, error) => {
+                write!(f, "the url template '{}' is invalid: {}", template, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EndpointError {}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::{Endpoint, Project};
+    use crate::languages::Language;
+
+    #[test]
+    fn test_wikipedia_languages() {
+        let endpoint = Endpoint::parse("https://de.wikipedia.org/w/api.php").unwrap();
+        assert_eq!(endpoint.project(), Project::Wikipedia);
+        assert!(endpoint.supports_languages());
+
+        let english = endpoint.for_language(Language::English);
+        assert_eq!(english, Endpoint::wikipedia(Language::English));
+        assert_eq!(
+            english
+                .article_url("Rust (programming language)")
+                .unwrap()
+                .as_str(),
+            "https://en.wikipedia.org/wiki/Rust_(programming_language)"
+        );
+
+        let url = Url::parse("https://fr.wikipedia.org/wiki/Paris").unwrap();
+        assert_eq!(endpoint.language_of(&url), Some(Language::French));
+    }
+
+    #[test]
+    fn test_wiktionary_languages() {
+        let endpoint = Endpoint::parse("https://fr.wiktionary.org/w/api.php").unwrap();
+        assert_eq!(endpoint.project(), Project::Wiktionary);
+        assert_eq!(endpoint.site_name(), "Wiktionary");
+        assert_eq!(
+            endpoint.for_language(Language::English),
+            Endpoint::wiktionary(Language::English)
+        );
+    }
+
+    #[test]
+    fn test_single_language_site() {
+        let endpoint = Endpoint::parse("https://wiki.example.com/api.php")
+            .unwrap()
+            .with_article_path("/index.php/$1");
+        assert_eq!(endpoint.project(), Project::MediaWiki);
+        assert_eq!(endpoint.site_name(), "wiki.example.com");
+
+        // changing the language must not break sites without language editions
+        assert_eq!(endpoint.for_language(Language::German), endpoint);
+        assert_eq!(
+            endpoint.article_url("Main Page").unwrap().as_str(),
+            "https://wiki.example.com/index.php/Main_Page"
+        );
+    }
+
+    #[test]
+    fn test_url_template() {
+        let endpoint = Endpoint::parse("https://en.example.org/w/api.php")
+            .unwrap()
+            .with_url_template("https://{lang}.example.org/w/api.php")
+            .unwrap();
+        assert_eq!(
+            endpoint.for_language(Language::German).api_url().as_str(),
+            "https://de.example.org/w/api.php"
+        );
+
+        assert!(Endpoint::default()
+            .with_url_template("https://example.org/w/api.php")
+            .is_err());
+    }
+
+    #[test]
+    fn test_deserialize_legacy_url() {
+        let endpoint: Endpoint =
+            serde_json::from_str("\"https://en.wikipedia.org/w/api.php\"").unwrap();
+        assert_eq!(endpoint, Endpoint::wikipedia(Language::English));
+
+        let json = serde_json::to_string(&endpoint).unwrap();
+        let roundtrip: Endpoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtrip, endpoint);
+    }
+}
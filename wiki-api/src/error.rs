@@ -0,0 +1,148 @@
+use reqwest::StatusCode;
+use snafu::Snafu;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors returned by the requests of this crate
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum Error {
+    /// The request could not be sent or the response could not be read
+    #[snafu(display("the request failed: {source}"))]
+    Request { source: reqwest::Error },
+
+    /// The server is rate limiting the requests. `retry_after` is the amount of seconds the
+    /// server asked to wait before trying again
+    #[snafu(display("the server is rate limiting the requests"))]
+    RateLimited { retry_after: Option<u64> },
+
+    /// The server responded with an error status
+    #[snafu(display("the server returned an error ({status})"))]
+    Status { status: StatusCode },
+
+    /// The MediaWiki api returned an error. The code is one of the error codes listed in the api
+    /// documentation (e.g. `missingtitle`)
+    #[snafu(display("the server returned an error: {info} ({code})"))]
+    Api { code: String, info: String },
+
+    /// The requested page doesn't exist
+    #[snafu(display("the page '{title}' doesn't exist"))]
+    MissingPage { title: String },
+
+    /// The response is not valid json
+    #[snafu(display("failed interpreting the response as json: {source}"))]
+    Json { source: serde_json::Error },
+
+    /// The response is missing a required field
+    #[snafu(display("the response is missing the {what}"))]
+    MissingField { what: &'static str },
+
+    /// A field of the response has an unexpected format
+    #[snafu(display("failed parsing the {what}: {source}"))]
+    Deserialize {
+        what: &'static str,
+        source: serde_json::Error,
+    },
+
+    /// The http client could not be created, e.g. because of an invalid proxy
+    #[snafu(display("failed creating the http client: {source}"))]
+    Client { source: reqwest::Error },
+
+    /// The url of the request could not be built
+    #[snafu(display("failed building the request url: {source}"))]
+    InvalidUrl { source: url::ParseError },
+
+    /// The site doesn't support the request, e.g. the daily feed only exists on Wikipedia
+    #[snafu(display("the {what} is not supported by {site}"))]
+    Unsupported { what: &'static str, site: String },
+}
+
+impl Error {
+    /// Whether the requested page doesn't exist
+    pub fn is_missing_page(&self) -> bool {
+        match self {
+            Error::MissingPage { .. } => true,
+            Error::Api { code, .. } => matches!(code.as_str(), "missingtitle" | "nosuchpageid"),
+            _ => false,
+        }
+    }
+
+    /// Whether sending the same request again could succeed. This is the case for network
+    /// failures, rate limiting and errors of the server itself
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Request { .. } | Error::RateLimited { .. } => true,
+            Error::Status { status } => status.is_server_error(),
+            _ => false,
+        }
+    }
+
+    /// The error code returned by the MediaWiki api, if any
+    pub fn api_code(&self) -> Option<&str> {
+        match self {
+            Error::Api { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the error reported in the `error` object of a MediaWiki api response
+pub(crate) fn api_error(res_json: &serde_json::Value) -> Option<Error> {
+    let error = res_json.get("error")?;
+    let code = error
+        .get("code")
+        .and_then(|x| x.as_str())
+        .unwrap_or("unknown")
+        .to_string();
+    let info = error
+        .get("info")
+        .and_then(|x| x.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    // the api reports rate limiting and a lagging database in the body as well
+    if code == "ratelimited" || code == "maxlag" {
+        return Some(Error::RateLimited { retry_after: None });
+    }
+
+    Some(Error::Api { code, info })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_keeps_the_code() {
+        let res_json: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/fixtures/parse_missingtitle.json"))
+                .unwrap();
+
+        let error = api_error(&res_json).unwrap();
+        assert_eq!(error.api_code(), Some("missingtitle"));
+        assert!(error.is_missing_page());
+        assert!(!error.is_retryable());
+        assert_eq!(
+            error.to_string(),
+            "the server returned an error: The page you specified doesn't exist. (missingtitle)"
+        );
+    }
+
+    #[test]
+    fn test_api_error_detects_rate_limiting() {
+        let res_json = serde_json::json!({
+            "error": { "code": "ratelimited", "info": "You've exceeded your rate limit." }
+        });
+
+        let error = api_error(&res_json).unwrap();
+        assert!(matches!(error, Error::RateLimited { retry_after: None }));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_api_error_ignores_successful_responses() {
+        let res_json: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/fixtures/query_random.json")).unwrap();
+        assert!(api_error(&res_json).is_none());
+    }
+}
@@ -0,0 +1,329 @@
+use reqwest::Response;
+use serde::Deserialize;
+use snafu::{ensure, ResultExt};
+use url::Url;
+
+use crate::{
+    endpoint::Project,
+    error::{DeserializeSnafu, InvalidUrlSnafu, Result, UnsupportedSnafu},
+    languages::Language,
+    Client, Endpoint,
+};
+
+/// The daily feed of a Wikipedia, containing the featured article, the most read articles and
+/// selected events that happened on this day
+///
+/// Read more in the [Wikimedia REST API docs](https://api.wikimedia.org/wiki/Feed_API/Reference/Featured_content)
+#[derive(Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Feed {
+    /// Optional: Today's featured article. Not every Wikipedia has featured articles
+    pub featured: Option<FeedArticle>,
+    /// The most read articles of the previous day, sorted by their rank
+    pub most_read: Vec<FeedArticle>,
+    /// Selected events that happened on this day
+    pub on_this_day: Vec<OnThisDay>,
+
+    /// Language of the Wikipedia the feed belongs to
+    pub language: Language,
+    /// API endpoint of the MediaWiki site the feed belongs to
+    pub endpoint: Endpoint,
+}
+
+impl Feed {
+    /// Creates a [`FeedBuilder`] to configure and fetch a feed
+    ///
+    /// [`FeedBuilder`]: FeedBuilder
+    pub fn builder() -> FeedBuilder<NoDate, NoEndpoint, NoLanguage> {
+        FeedBuilder::default()
+    }
+}
+
+impl std::fmt::Debug for Feed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Feed")
+            .field("featured", &self.featured.as_ref().map(|x| &x.title))
+            .field("most_read", &self.most_read.len())
+            .field("on_this_day", &self.on_this_day.len())
+            .field("language", &self.language)
+            .finish()
+    }
+}
+
+/// An article referenced in a feed
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FeedArticle {
+    /// Title of the page
+    pub title: String,
+    /// PageId of the page
+    pub pageid: usize,
+    /// Optional: Short description of the page
+    pub description: Option<String>,
+    /// Optional: Plain text extract of the first paragraph of the page
+    pub extract: Option<String>,
+    /// Optional: How often the page was viewed (only available for the most read articles)
+    pub views: Option<usize>,
+}
+
+/// An event that happened on this day
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OnThisDay {
+    /// Description of the event
+    pub text: String,
+    /// Optional: The year the event happened in. Negative years are BC
+    pub year: Option<i32>,
+    /// Pages related to the event
+    pub pages: Vec<FeedArticle>,
+}
+
+#[doc(hidden)]
+pub struct WithDate(u16, u8, u8);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoDate;
+
+#[doc(hidden)]
+pub struct WithEndpoint(Endpoint);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoEndpoint;
+
+#[doc(hidden)]
+pub struct WithLanguage(Language);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoLanguage;
+
+/// A fully configured `FeedBuilder` that can be used to fetch the feed. This is a convenience type
+pub type FeedRequest = FeedBuilder<WithDate, WithEndpoint, WithLanguage>;
+
+/// A `FeedBuilder` can be used to configure and fetch the feed for a specific day
+#[derive(Default)]
+pub struct FeedBuilder<D, E, L> {
+    date: D,
+    endpoint: E,
+    language: L,
+    client: Client,
+}
+
+impl<E, L> FeedBuilder<NoDate, E, L> {
+    /// The day to fetch the feed for
+    pub fn date(self, year: u16, month: u8, day: u8) -> FeedBuilder<WithDate, E, L> {
+        FeedBuilder {
+            date: WithDate(year, month, day),
+            endpoint: self.endpoint,
+            language: self.language,
+            client: self.client,
+        }
+    }
+}
+
+impl<D, L> FeedBuilder<D, NoEndpoint, L> {
+    /// API endpoint for the MediaWiki site to fetch the feed from. The feed is requested from the
+    /// REST API of the same site
+    pub fn endpoint(self, endpoint: Endpoint) -> FeedBuilder<D, WithEndpoint, L> {
+        FeedBuilder {
+            date: self.date,
+            endpoint: WithEndpoint(endpoint),
+            language: self.language,
+            client: self.client,
+        }
+    }
+}
+
+impl<D, E> FeedBuilder<D, E, NoLanguage> {
+    /// Language of the Wikipedia
+    pub fn language(self, language: Language) -> FeedBuilder<D, E, WithLanguage> {
+        FeedBuilder {
+            date: self.date,
+            endpoint: self.endpoint,
+            language: WithLanguage(language),
+            client: self.client,
+        }
+    }
+}
+
+impl<D, E, L> FeedBuilder<D, E, L> {
+    /// Http client used to send the request
+    ///
+    /// Default: a shared [`Client`] with the default configuration
+    ///
+    /// [`Client`]: Client
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+impl FeedBuilder<WithDate, WithEndpoint, WithLanguage> {
+    /// Fetches the feed of the configured day
+    ///
+    /// # Error
+    ///
+    /// This function returns an error when one of the following things happens:
+    /// - The endpoint is not a Wikipedia, only the Wikimedia REST API provides the feed
+    /// - The request to the server could not be made
+    /// - The server returned an error
+    /// - The returned result could not interpreted as a `Feed`
+    pub async fn fetch(self) -> Result<Feed> {
+        async fn rest_get(client: &Client, url: Url) -> Result<Response> {
+            let request = client.get(url);
+            client.send(request).await
+        }
+
+        ensure!(
+            self.endpoint.0.project() == Project::Wikipedia,
+            UnsupportedSnafu {
+                what: "daily feed",
+                site: self.endpoint.0.site_name().to_string(),
+            }
+        );
+
+        let WithDate(year, month, day) = self.date;
+        let url = self
+            .endpoint
+            .0
+            .api_url()
+            .join(&format!(
+                "/api/rest_v1/feed/featured/{:04}/{:02}/{:02}",
+                year, month, day
+            ))
+            .context(InvalidUrlSnafu)?;
+
+        let response = rest_get(&self.client, url).await?;
+        let res_json = self.client.response_json(response).await?;
+
+        feed_from_json(res_json, self.endpoint.0, self.language.0)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawTitles {
+    normalized: String,
+}
+
+#[derive(Deserialize)]
+struct RawArticle {
+    titles: RawTitles,
+    pageid: usize,
+    description: Option<String>,
+    extract: Option<String>,
+    views: Option<usize>,
+}
+
+impl From<RawArticle> for FeedArticle {
+    fn from(raw: RawArticle) -> Self {
+        FeedArticle {
+            title: raw.titles.normalized,
+            pageid: raw.pageid,
+            description: raw.description,
+            extract: raw.extract,
+            views: raw.views,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawMostRead {
+    articles: Vec<RawArticle>,
+}
+
+#[derive(Deserialize)]
+struct RawOnThisDay {
+    text: String,
+    year: Option<i32>,
+    #[serde(default)]
+    pages: Vec<RawArticle>,
+}
+
+#[derive(Deserialize)]
+struct RawFeed {
+    tfa: Option<RawArticle>,
+    mostread: Option<RawMostRead>,
+    #[serde(default)]
+    onthisday: Vec<RawOnThisDay>,
+}
+
+fn feed_from_json(
+    res_json: serde_json::Value,
+    endpoint: Endpoint,
+    language: Language,
+) -> Result<Feed> {
+    let raw: RawFeed =
+        serde_json::from_value(res_json).context(DeserializeSnafu { what: "feed" })?;
+
+    Ok(Feed {
+        featured: raw.tfa.map(FeedArticle::from),
+        most_read: raw
+            .mostread
+            .map(|x| x.articles.into_iter().map(FeedArticle::from).collect())
+            .unwrap_or_default(),
+        on_this_day: raw
+            .onthisday
+            .into_iter()
+            .map(|x| OnThisDay {
+                text: x.text,
+                year: x.year,
+                pages: x.pages.into_iter().map(FeedArticle::from).collect(),
+            })
+            .collect(),
+        language,
+        endpoint,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{feed_from_json, Feed};
+    use crate::{languages::Language, Endpoint, Error};
+
+    #[test]
+    fn test_feed_from_fixture() {
+        let res_json = serde_json::from_str(include_str!("../tests/fixtures/feed_featured.json"))
+            .expect("the fixture should be valid json");
+        let endpoint = Endpoint::parse("https://en.wikipedia.org/w/api.php").unwrap();
+
+        let feed = feed_from_json(res_json, endpoint, Language::English).unwrap();
+
+        let featured = feed.featured.expect("the fixture has a featured article");
+        assert_eq!(featured.title, "Hurricane Iniki");
+        assert_eq!(featured.pageid, 1478520);
+        assert!(featured.views.is_none());
+
+        assert_eq!(feed.most_read.len(), 2);
+        assert_eq!(feed.most_read[0].title, "Deaths in 2024");
+        assert_eq!(feed.most_read[0].views, Some(262718));
+
+        assert_eq!(feed.on_this_day.len(), 2);
+        assert_eq!(feed.on_this_day[0].year, Some(1961));
+        assert_eq!(feed.on_this_day[0].pages[0].title, "Yuri Gagarin");
+        assert!(feed.on_this_day[1].pages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_feed_unsupported_site() {
+        let endpoint = Endpoint::parse("https://minecraft.fandom.com/api.php").unwrap();
+        let error = Feed::builder()
+            .date(2024, 4, 12)
+            .endpoint(endpoint)
+            .language(Language::English)
+            .fetch()
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Unsupported { .. }));
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_feed_without_featured_article() {
+        let res_json = serde_json::json!({ "onthisday": [] });
+        let endpoint = Endpoint::parse("https://de.wikipedia.org/w/api.php").unwrap();
+
+        let feed = feed_from_json(res_json, endpoint, Language::German).unwrap();
+        assert!(feed.featured.is_none());
+        assert!(feed.most_read.is_empty());
+        assert!(feed.on_this_day.is_empty());
+    }
+}
@@ -8099,3 +8099,133 @@ parse_languages!(
 }
 "#
 );
+
+/// A variant of a language, written in another script or following the conventions of a region.
+/// Wikis of languages with variants convert their content to the requested variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Variant {
+    /// Code of the variant, like `zh-hant`
+    pub code: &'static str,
+    /// Name of the variant in English
+    pub name: &'static str,
+}
+
+macro_rules! variants {
+    ($($code:literal => $name:literal),+ $(,)?) => {
+        &[$(Variant {
+            code: $code,
+            name: $name,
+        }),+]
+    };
+}
+
+impl Language {
+    /// Returns the variants the content in the language can be converted to. The first variant
+    /// is the language itself, which leaves the content unconverted. Languages without variants
+    /// have none
+    pub fn variants(&self) -> &'static [Variant] {
+        match self.code() {
+            "zh" => variants!(
+                "zh" => "No conversion",
+                "zh-hans" => "Simplified",
+                "zh-hant" => "Traditional",
+                "zh-cn" => "Mainland China",
+                "zh-hk" => "Hong Kong",
+                "zh-mo" => "Macau",
+                "zh-my" => "Malaysia",
+                "zh-sg" => "Singapore",
+                "zh-tw" => "Taiwan",
+            ),
+            "gan" => variants!(
+                "gan" => "No conversion",
+                "gan-hans" => "Simplified",
+                "gan-hant" => "Traditional",
+            ),
+            "wuu" => variants!(
+                "wuu" => "No conversion",
+                "wuu-hans" => "Simplified",
+                "wuu-hant" => "Traditional",
+            ),
+            "sr" => variants!(
+                "sr" => "No conversion",
+                "sr-ec" => "Cyrillic",
+                "sr-el" => "Latin",
+            ),
+            "sh" => variants!(
+                "sh" => "No conversion",
+                "sh-latn" => "Latin",
+                "sh-cyrl" => "Cyrillic",
+            ),
+            "kk" => variants!(
+                "kk" => "No conversion",
+                "kk-cyrl" => "Cyrillic",
+                "kk-latn" => "Latin",
+                "kk-arab" => "Arabic",
+                "kk-kz" => "Kazakhstan",
+                "kk-tr" => "Turkey",
+                "kk-cn" => "China",
+            ),
+            "uz" => variants!(
+                "uz" => "No conversion",
+                "uz-latn" => "Latin",
+                "uz-cyrl" => "Cyrillic",
+            ),
+            "tg" => variants!(
+                "tg" => "No conversion",
+                "tg-cyrl" => "Cyrillic",
+                "tg-latn" => "Latin",
+            ),
+            "ku" => variants!(
+                "ku" => "No conversion",
+                "ku-latn" => "Latin",
+                "ku-arab" => "Arabic",
+            ),
+            "crh" => variants!(
+                "crh" => "No conversion",
+                "crh-latn" => "Latin",
+                "crh-cyrl" => "Cyrillic",
+            ),
+            "shi" => variants!(
+                "shi" => "No conversion",
+                "shi-tfng" => "Tifinagh",
+                "shi-latn" => "Latin",
+            ),
+            "iu" => variants!(
+                "iu" => "No conversion",
+                "ike-cans" => "Syllabics",
+                "ike-latn" => "Latin",
+            ),
+            _ => &[],
+        }
+    }
+
+    /// Returns the variant of the language with this code
+    pub fn variant(&self, code: &str) -> Option<Variant> {
+        self.variants()
+            .iter()
+            .find(|variant| variant.code.eq_ignore_ascii_case(code))
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Language};
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Language::English.direction(), Direction::LeftToRight);
+        assert_eq!(Language::Arabic.direction(), Direction::RightToLeft);
+        assert_eq!(Language::Hebrew.direction(), Direction::RightToLeft);
+        assert_eq!(Language::Unknown.direction(), Direction::LeftToRight);
+    }
+
+    #[test]
+    fn test_variants() {
+        let chinese: Language = "zh".parse().unwrap();
+        assert_eq!(chinese.variants()[0].code, "zh");
+        assert_eq!(chinese.variant("zh-Hant").map(|x| x.code), Some("zh-hant"));
+        assert!(chinese.variant("sr-el").is_none());
+        assert!(Language::English.variants().is_empty());
+    }
+}
@@ -1,10 +1,21 @@
-use url::Url;
-
+pub mod backlinks;
+pub mod category;
+pub mod client;
+pub mod dictionary;
+pub mod diff;
 pub mod document;
+pub mod endpoint;
+pub mod error;
+pub mod feed;
 pub mod languages;
 pub mod page;
 pub mod parser;
+pub mod random;
+pub mod revisions;
 pub mod search;
+pub mod sitematrix;
+pub mod summary;
 
-// TODO: Make Endpoint a real struct
-pub type Endpoint = Url;
+pub use client::{Client, Progress};
+pub use endpoint::Endpoint;
+pub use error::{Error, Result};
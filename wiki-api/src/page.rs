@@ -1,12 +1,13 @@
+use crate::error::{MissingFieldSnafu, Result};
 use crate::{
     document::{Document, HeaderKind},
     parser::{Parser, WikipediaParser},
-    Endpoint,
+    Client, Endpoint,
 };
-use anyhow::{anyhow, Context, Result};
-use reqwest::{Client, Response};
+use reqwest::Response;
 use scraper::Html;
 use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
 use std::fmt::Display;
 use tracing::{debug, warn};
 use url::Url;
@@ -97,9 +98,28 @@ pub struct LanguageLink {
     pub autonym: String,
     pub title: String,
     pub url: Url,
+    #[serde(default)]
     pub endpoint: Endpoint,
 }
 
+/// A category the page belongs to
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq, Serialize)]
+pub struct Category {
+    /// Name of the category, without the namespace prefix
+    #[serde(rename = "category")]
+    pub name: String,
+    /// Hidden categories are used for maintenance and are usually not shown to readers
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl Category {
+    /// Title of the category page, including the namespace prefix
+    pub fn title(&self) -> String {
+        format!("Category:{}", self.name)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Eq, Serialize)]
 pub struct Section {
     #[serde(skip_deserializing)]
@@ -120,7 +140,19 @@ pub struct Page {
     pub language: Language,
     pub language_links: Option<Vec<LanguageLink>>,
     pub sections: Option<Vec<Section>>,
+    #[serde(default)]
+    pub categories: Option<Vec<Category>>,
     pub revision_id: Option<usize>,
+    /// Whether this is an old revision of the page and not its current version
+    #[serde(default)]
+    pub is_old_revision: bool,
+    /// Variant of the language the content was converted to
+    #[serde(default)]
+    pub variant: Option<String>,
+    /// Pages cached by older versions don't have an endpoint, they default to the English
+    /// Wikipedia and have to be moved to the Wikipedia in their language
+    #[serde(default)]
+    pub endpoint: Endpoint,
     pub uuid: Uuid,
 }
 
@@ -132,12 +164,10 @@ impl Page {
         }
 
         let content = std::fs::read_to_string(path).ok()?;
-        let nodes = WikipediaParser::parse_document(
-            &content,
-            url::Url::parse("https://en.wikipedia.org/w/api.php").ok()?,
-            Language::default(),
-        )
-        .nodes();
+        let endpoint = Endpoint::wikipedia(Language::default());
+        let nodes =
+            WikipediaParser::parse_document(&content, endpoint.clone(), Language::default())
+                .nodes();
 
         Some(Page {
             title: "DEBUG: FILE".to_string(),
@@ -146,7 +176,11 @@ impl Page {
             language: Language::default(),
             language_links: None,
             sections: None,
+            categories: None,
             revision_id: None,
+            is_old_revision: false,
+            variant: None,
+            endpoint,
             uuid: Uuid::new_v4(),
         })
     }
@@ -179,7 +213,9 @@ impl std::fmt::Debug for Page {
             .field("language", &self.language)
             .field("language_links", &self.language_links.is_some())
             .field("sections", &self.sections.is_some())
+            .field("categories", &self.categories.is_some())
             .field("revision_id", &self.revision_id)
+            .field("is_old_revision", &self.is_old_revision)
             .finish()
     }
 }
@@ -267,7 +303,7 @@ pub struct WithPage(String);
 #[derive(Default)]
 pub struct NoPage;
 
-pub struct WithEndpoint(Url);
+pub struct WithEndpoint(Endpoint);
 #[derive(Default)]
 pub struct NoEndpoint;
 
@@ -282,8 +318,11 @@ pub struct PageBuilder<I, P, E, L> {
     endpoint: E,
     language: L,
     revision: Option<usize>,
+    old_revision: Option<usize>,
     redirects: Option<bool>,
     properties: Option<Vec<Property>>,
+    variant: Option<String>,
+    client: Client,
 }
 
 pub type PageRequest = PageBuilder<NoPageID, WithPage, WithEndpoint, WithLanguage>;
@@ -297,9 +336,12 @@ impl<E, L> PageBuilder<NoPageID, NoPage, E, L> {
             page: self.page,
             endpoint: self.endpoint,
             revision: self.revision,
+            old_revision: self.old_revision,
             redirects: self.redirects,
             properties: self.properties,
+            variant: self.variant,
             language: self.language,
+            client: self.client,
         }
     }
 
@@ -310,9 +352,12 @@ impl<E, L> PageBuilder<NoPageID, NoPage, E, L> {
             page: WithPage(page.into()),
             endpoint: self.endpoint,
             revision: self.revision,
+            old_revision: self.old_revision,
             redirects: self.redirects,
             properties: self.properties,
+            variant: self.variant,
             language: self.language,
+            client: self.client,
         }
     }
 }
@@ -322,23 +367,29 @@ impl<I, P, L> PageBuilder<I, P, NoEndpoint, L> {
         PageBuilder {
             pageid: self.pageid,
             page: self.page,
-            endpoint: WithEndpoint(url.into()),
+            endpoint: WithEndpoint(Endpoint::from_url(url.into())),
             revision: self.revision,
+            old_revision: self.old_revision,
             redirects: self.redirects,
             properties: self.properties,
+            variant: self.variant,
             language: self.language,
+            client: self.client,
         }
     }
 
-    pub fn endpoint(self, endpoint: Url) -> PageBuilder<I, P, WithEndpoint, L> {
+    pub fn endpoint(self, endpoint: Endpoint) -> PageBuilder<I, P, WithEndpoint, L> {
         PageBuilder {
             pageid: self.pageid,
             page: self.page,
             endpoint: WithEndpoint(endpoint),
             revision: self.revision,
+            old_revision: self.old_revision,
             redirects: self.redirects,
             properties: self.properties,
+            variant: self.variant,
             language: self.language,
+            client: self.client,
         }
     }
 }
@@ -351,19 +402,39 @@ impl<I, P, E> PageBuilder<I, P, E, NoLanguage> {
             endpoint: self.endpoint,
             language: WithLanguage(language),
             revision: self.revision,
+            old_revision: self.old_revision,
             redirects: self.redirects,
             properties: self.properties,
+            variant: self.variant,
+            client: self.client,
         }
     }
 }
 
 impl<I, P, U, L> PageBuilder<I, P, U, L> {
+    /// Http client used to send the request
+    ///
+    /// Default: a shared [`Client`] with the default configuration
+    ///
+    /// [`Client`]: Client
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     /// Revision ID, for `{{REVISIONID}}` and similar variables
     pub fn revision(mut self, revision: usize) -> Self {
         self.revision = Some(revision);
         self
     }
 
+    /// Parse this old revision of the page instead of its current version. The title or pageid
+    /// is then ignored by the server
+    pub fn old_revision(mut self, revid: usize) -> Self {
+        self.old_revision = Some(revid);
+        self
+    }
+
     /// If page or pageid is set to a redirect, resolve it
     pub fn redirects(mut self, redirects: bool) -> Self {
         self.redirects = Some(redirects);
@@ -375,39 +446,48 @@ impl<I, P, U, L> PageBuilder<I, P, U, L> {
         self.properties = Some(properties);
         self
     }
+
+    /// Convert the content to this variant of the language, e.g. `zh-hant` or `sr-el`. The
+    /// variants of a language are listed by [`Language::variants`]
+    ///
+    /// [`Language::variants`]: Language::variants
+    pub fn variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
 }
 
 impl<I, P> PageBuilder<I, P, WithEndpoint, WithLanguage> {
     async fn fetch_with_params(self, mut params: Vec<(&str, String)>) -> Result<Page> {
-        async fn action_parse(params: Vec<(&str, String)>, endpoint: Url) -> Result<Response> {
-            Client::new()
-                .get(endpoint)
-                .header(
-                    "User-Agent",
-                    format!(
-                        "wiki-tui/{} (https://github.com/Builditluc/wiki-tui)",
-                        env!("CARGO_PKG_VERSION")
-                    ),
-                )
+        async fn action_parse(
+            client: &Client,
+            params: Vec<(&str, String)>,
+            endpoint: Endpoint,
+        ) -> Result<Response> {
+            let request = client
+                .get(endpoint.api_url().clone())
                 .query(&[
                     ("action", "parse"),
                     ("format", "json"),
                     ("formatversion", "2"),
                     ("parsoid", "true"),
                 ])
-                .query(&params)
-                .send()
-                .await
-                .inspect(|response| {
-                    debug!("response url: '{}'", response.url().as_str());
-                })
-                .context("failed sending the request")
+                .query(&params);
+            client.send(request).await.inspect(|response| {
+                debug!("response url: '{}'", response.url().as_str());
+            })
         }
 
         if let Some(revision) = self.revision {
             params.push(("revid", revision.to_string()));
         }
 
+        if let Some(old_revision) = self.old_revision {
+            // oldid can't be combined with page or pageid
+            params.retain(|(name, _)| *name != "page" && *name != "pageid");
+            params.push(("oldid", old_revision.to_string()));
+        }
+
         if let Some(redirects) = self.redirects {
             params.push(("redirects", redirects.to_string()));
         }
@@ -421,21 +501,14 @@ impl<I, P> PageBuilder<I, P, WithEndpoint, WithLanguage> {
             params.push(("prop", prop_str));
         }
 
-        let response = action_parse(params, self.endpoint.0.clone())
-            .await?
-            .error_for_status()
-            .context("the server returned an error")?;
+        if let Some(ref variant) = self.variant {
+            params.push(("variant", variant.clone()));
+        }
 
-        let res_json: serde_json::Value = serde_json::from_str(
-            &response
-                .text()
-                .await
-                .context("failed reading the response")?,
-        )
-        .context("failed interpreting the response as json")?;
+        let response = action_parse(&self.client, params, self.endpoint.0.clone()).await?;
+        let res_json = self.client.response_json(response).await?;
 
         self.serialize_result(res_json)
-            .context("failed serializing the returned response")
     }
 
     fn serialize_result(self, res_json: serde_json::Value) -> Result<Page> {
@@ -444,14 +517,14 @@ impl<I, P> PageBuilder<I, P, WithEndpoint, WithLanguage> {
             .and_then(|x| x.get("title"))
             .and_then(|x| x.as_str())
             .map(|x| x.to_string())
-            .ok_or_else(|| anyhow!("missing the title"))?;
+            .context(MissingFieldSnafu { what: "title" })?;
 
         let pageid = res_json
             .get("parse")
             .and_then(|x| x.get("pageid"))
             .and_then(|x| x.as_u64())
             .map(|x| x as usize)
-            .ok_or_else(|| anyhow!("missing the pageid"))?;
+            .context(MissingFieldSnafu { what: "pageid" })?;
 
         let endpoint = self.endpoint.0;
         let language = self.language.0;
@@ -465,8 +538,7 @@ impl<I, P> PageBuilder<I, P, WithEndpoint, WithLanguage> {
                     nodes: parser.nodes(),
                 }
             })
-            // HACK: implement correct errors
-            .ok_or(anyhow!("missing the content or failed parsing the content"))?;
+            .context(MissingFieldSnafu { what: "content" })?;
 
         let language_links = res_json
             .get("parse")
@@ -479,9 +551,15 @@ impl<I, P> PageBuilder<I, P, WithEndpoint, WithLanguage> {
                         let mut language_link: LanguageLink = serde_json::from_value(x)
                             .map_err(|err| warn!("language_link parsing error: {:?}", err))
                             .ok()?;
-                        let mut endpoint = endpoint.clone();
-                        let _ = endpoint.set_host(Some(language_link.url.host_str().unwrap()));
-                        language_link.endpoint = endpoint;
+                        language_link.endpoint = if endpoint.supports_languages() {
+                            endpoint.for_language(language_link.language)
+                        } else {
+                            // without a url template, the best guess is the same api path on the
+                            // host of the linked article
+                            let mut url = endpoint.api_url().clone();
+                            let _ = url.set_host(language_link.url.host_str());
+                            Endpoint::from_url(url)
+                        };
                         Some(language_link)
                     })
                     .collect::<Vec<LanguageLink>>()
@@ -523,6 +601,25 @@ impl<I, P> PageBuilder<I, P, WithEndpoint, WithLanguage> {
                 x
             });
 
+        let categories = res_json
+            .get("parse")
+            .and_then(|x| x.get("categories"))
+            .and_then(|x| x.as_array())
+            .map(|x| {
+                x.iter()
+                    .filter_map(|x| {
+                        serde_json::from_value(x.to_owned())
+                            .map_err(|err| warn!("category parsing error: {:?}", err))
+                            .ok()
+                            .map(|mut x: Category| {
+                                // the api returns the category names in their url form
+                                x.name = x.name.replace('_', " ");
+                                x
+                            })
+                    })
+                    .collect::<Vec<Category>>()
+            });
+
         let revision_id = res_json
             .get("parse")
             .and_then(|x| x.get("revid"))
@@ -536,7 +633,11 @@ impl<I, P> PageBuilder<I, P, WithEndpoint, WithLanguage> {
             language,
             language_links,
             sections,
+            categories,
             revision_id,
+            is_old_revision: self.old_revision.is_some(),
+            variant: self.variant,
+            endpoint,
             uuid: Uuid::new_v4(),
         })
     }
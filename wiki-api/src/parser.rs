@@ -2,7 +2,6 @@ use html5ever::{parse_document, tendril::TendrilSink};
 use markup5ever_rcdom::{Handle, NodeData, RcDom};
 use std::str::FromStr;
 use tracing::{trace, warn};
-use url::Url;
 
 use crate::{
     document::{Data, HeaderKind, Raw, UnsupportedElement},
@@ -303,7 +302,11 @@ impl WikipediaParser {
         })
     }
 
-    fn parse_link(endpoint: &Url, language: Language, attrs: &[(String, String)]) -> Option<Data> {
+    fn parse_link(
+        endpoint: &Endpoint,
+        language: Language,
+        attrs: &[(String, String)],
+    ) -> Option<Data> {
         let href = attrs
             .iter()
             .find(|(name, _)| name.as_str() == "href")
@@ -315,7 +318,7 @@ impl WikipediaParser {
             .map(|(_, value)| value.to_owned())
             .unwrap_or_default();
 
-        let link_url = endpoint.join(&href).ok()?;
+        let link_url = endpoint.api_url().join(&href).ok()?;
         let link_type: &str = match attrs
             .iter()
             .find(|(name, _)| name.as_str() == "rel")
@@ -328,15 +331,18 @@ impl WikipediaParser {
             _ => "",
         };
 
+        // the ids of the headers are not percent-encoded, the anchors have to match them
         let anchor = link_url.fragment().map(|fragment| AnchorData {
             title: title.to_string(),
-            anchor: fragment.to_string(),
+            anchor: urlencoding::decode(fragment)
+                .map(|x| x.into_owned())
+                .unwrap_or_else(|_| fragment.to_string()),
         });
 
         if link_type == "wiki" {
             let namespace = Namespace::Main;
 
-            let is_same_wiki = link_url.domain() == endpoint.domain();
+            let is_same_wiki = link_url.domain() == endpoint.api_url().domain();
             if !is_same_wiki {
                 return Some(Data::Link(Link::ExternalToInternal(
                     ExternalToInteralData {},
@@ -344,6 +350,7 @@ impl WikipediaParser {
             }
 
             let page = link_url.path_segments()?.next_back()?;
+            let page = urlencoding::decode(page).ok()?;
 
             const NAMESPACE_DELIMITER: char = ':';
             let (namespace, page) =
@@ -356,24 +363,12 @@ impl WikipediaParser {
                         page_str,
                     )
                 } else {
-                    (namespace, page)
+                    (namespace, page.as_ref())
                 };
 
-            // we get the language from the host
-            // for wikipedia, the host looks like this
-            //      [lang].wikipedia.org/
-            // where [lang] is the language code, for example
-            //      en.wikipedia.org/
-            // for the english wikipedia
-
-            let lang_str = link_url
-                .host_str()
-                .and_then(|x| x.split_once('.').map(|x| x.0));
-
-            let language = match lang_str {
-                Some(str) => Language::from_str(str).unwrap_or(language),
-                None => language,
-            };
+            // we get the language from the host, sites without multiple language editions
+            // keep the language of the page
+            let language = endpoint.language_of(&link_url).unwrap_or(language);
 
             let link_data = InternalData {
                 namespace,
@@ -420,3 +415,48 @@ impl Parser for WikipediaParser {
         self.nodes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::WikipediaParser;
+    use crate::{
+        document::Data,
+        languages::Language,
+        page::{link_data::InternalData, Link},
+        Endpoint,
+    };
+
+    fn parse_internal_link(href: &str) -> InternalData {
+        let endpoint = Endpoint::parse("https://en.wikipedia.org/w/api.php").unwrap();
+        let attrs = vec![
+            ("rel".to_string(), "mw:WikiLink".to_string()),
+            ("href".to_string(), href.to_string()),
+            ("title".to_string(), "Title".to_string()),
+        ];
+
+        match WikipediaParser::parse_link(&endpoint, Language::English, &attrs) {
+            Some(Data::Link(Link::Internal(link_data))) => link_data,
+            data => panic!("expected an internal link, got {:?}", data),
+        }
+    }
+
+    #[test]
+    fn test_link_with_anchor() {
+        let link_data = parse_internal_link("./Rust_(programming_language)#Early_history");
+        assert_eq!(link_data.page, "Rust_(programming_language)");
+        assert_eq!(
+            link_data.anchor.map(|x| x.anchor).as_deref(),
+            Some("Early_history")
+        );
+    }
+
+    #[test]
+    fn test_link_is_decoded() {
+        let link_data = parse_internal_link("./Caf%C3%A9#Caf%C3%A9s_in_Paris");
+        assert_eq!(link_data.page, "Café");
+        assert_eq!(
+            link_data.anchor.map(|x| x.anchor).as_deref(),
+            Some("Cafés_in_Paris")
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use reqwest::Response;
+use serde::Deserialize;
+use snafu::{OptionExt, ResultExt};
+
+use crate::{
+    error::{DeserializeSnafu, MissingFieldSnafu, Result},
+    languages::Language,
+    search::Namespace,
+    Client, Endpoint,
+};
+
+/// A randomly selected page of a MediaWiki site
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RandomPage {
+    /// Namespace where the page belongs to
+    pub namespace: Namespace,
+    /// Title of the page
+    pub title: String,
+    /// PageId of the page
+    pub pageid: usize,
+
+    /// Language, the page is written in
+    pub language: Language,
+    /// API endpoint of the MediaWiki site this page belongs to
+    pub endpoint: Endpoint,
+}
+
+impl RandomPage {
+    /// Creates a [`RandomBuilder`] to configure and fetch random pages
+    ///
+    /// [`RandomBuilder`]: RandomBuilder
+    pub fn builder() -> RandomBuilder<NoEndpoint, NoLanguage> {
+        RandomBuilder::default()
+    }
+}
+
+#[doc(hidden)]
+pub struct WithEndpoint(Endpoint);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoEndpoint;
+
+#[doc(hidden)]
+pub struct WithLanguage(Language);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoLanguage;
+
+/// A fully configured `RandomBuilder` that can be used to fetch random pages. This is a
+/// convenience type
+pub type RandomRequest = RandomBuilder<WithEndpoint, WithLanguage>;
+
+/// A `RandomBuilder` can be used to configure and fetch a set of random pages
+#[derive(Default)]
+pub struct RandomBuilder<E, L> {
+    endpoint: E,
+    language: L,
+    namespace: Option<Namespace>,
+    limit: Option<usize>,
+    client: Client,
+}
+
+impl<L> RandomBuilder<NoEndpoint, L> {
+    /// API endpoint for the MediaWiki site to fetch the random pages from
+    pub fn endpoint(self, endpoint: Endpoint) -> RandomBuilder<WithEndpoint, L> {
+        RandomBuilder {
+            endpoint: WithEndpoint(endpoint),
+            language: self.language,
+            namespace: self.namespace,
+            limit: self.limit,
+            client: self.client,
+        }
+    }
+}
+
+impl<E> RandomBuilder<E, NoLanguage> {
+    /// Language of the MediaWiki site
+    pub fn language(self, language: Language) -> RandomBuilder<E, WithLanguage> {
+        RandomBuilder {
+            endpoint: self.endpoint,
+            language: WithLanguage(language),
+            namespace: self.namespace,
+            limit: self.limit,
+            client: self.client,
+        }
+    }
+}
+
+impl<E, L> RandomBuilder<E, L> {
+    /// Http client used to send the request
+    ///
+    /// Default: a shared [`Client`] with the default configuration
+    ///
+    /// [`Client`]: Client
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Return pages only from this namespace
+    ///
+    /// Default: [`Namespace::Main`]
+    ///
+    /// [`Namespace::Main`]: Namespace::Main
+    pub fn namespace(mut self, namespace: Namespace) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
+    /// How many random pages to return. The value must be between 1 and 500
+    ///
+    /// Default: `1`
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl RandomBuilder<WithEndpoint, WithLanguage> {
+    /// Fetches the random pages. Redirects are never returned
+    ///
+    /// # Error
+    ///
+    /// This function returns an error when one of the following things happens:
+    /// - The request to the server could not be made
+    /// - The server returned an error
+    /// - The returned result could not interpreted as a list of random pages
+    pub async fn fetch(self) -> Result<Vec<RandomPage>> {
+        async fn action_query(
+            client: &Client,
+            params: Vec<(&str, String)>,
+            endpoint: Endpoint,
+        ) -> Result<Response> {
+            let request = client
+                .get(endpoint.api_url().clone())
+                .query(&[
+                    ("action", "query"),
+                    ("format", "json"),
+                    ("formatversion", "2"),
+                ])
+                .query(&params);
+            client.send(request).await
+        }
+
+        let namespace = self.namespace.unwrap_or(Namespace::Main);
+        let params = vec![
+            ("list", "random".to_string()),
+            ("rnnamespace", (namespace as usize).to_string()),
+            ("rnfilterredir", "nonredirects".to_string()),
+            ("rnlimit", self.limit.unwrap_or(1).to_string()),
+        ];
+
+        let response = action_query(&self.client, params, self.endpoint.0.clone()).await?;
+        let res_json = self.client.response_json(response).await?;
+
+        random_pages_from_json(res_json, self.endpoint.0, self.language.0)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawRandomPage {
+    id: usize,
+    ns: Namespace,
+    title: String,
+}
+
+fn random_pages_from_json(
+    res_json: serde_json::Value,
+    endpoint: Endpoint,
+    language: Language,
+) -> Result<Vec<RandomPage>> {
+    let random_json = res_json
+        .get("query")
+        .and_then(|x| x.get("random"))
+        .context(MissingFieldSnafu {
+            what: "random pages",
+        })?
+        .to_owned();
+
+    let raw_pages: Vec<RawRandomPage> =
+        serde_json::from_value(random_json).context(DeserializeSnafu {
+            what: "random pages",
+        })?;
+
+    Ok(raw_pages
+        .into_iter()
+        .map(|raw| RandomPage {
+            namespace: raw.ns,
+            title: raw.title,
+            pageid: raw.id,
+            language,
+            endpoint: endpoint.clone(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::random_pages_from_json;
+    use crate::{languages::Language, search::Namespace, Endpoint};
+
+    #[test]
+    fn test_random_pages_from_fixture() {
+        let res_json = serde_json::from_str(include_str!("../tests/fixtures/query_random.json"))
+            .expect("the fixture should be valid json");
+        let endpoint = Endpoint::parse("https://en.wikipedia.org/w/api.php").unwrap();
+
+        let pages = random_pages_from_json(res_json, endpoint, Language::English).unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].title, "Tōkai Nagoya Building");
+        assert_eq!(pages[0].pageid, 61342281);
+        assert_eq!(pages[0].namespace, Namespace::Main);
+        assert_eq!(pages[1].title, "Sitka deer");
+    }
+}
@@ -0,0 +1,354 @@
+use reqwest::Response;
+use serde::Deserialize;
+use snafu::{OptionExt, ResultExt};
+
+use crate::{
+    error::{DeserializeSnafu, MissingFieldSnafu, MissingPageSnafu, Result},
+    languages::Language,
+    Client, Endpoint,
+};
+
+/// A batch of revisions of a page, ordered from the newest to the oldest revision
+#[derive(Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Revisions {
+    /// Title of the page the revisions belong to
+    pub title: String,
+    /// The revisions in this batch
+    pub revisions: Vec<Revision>,
+    /// If more revisions are available, use this value to continue listing them
+    pub continue_from: Option<String>,
+
+    /// Language of the page
+    pub language: Language,
+    /// API endpoint of the MediaWiki site the page belongs to
+    pub endpoint: Endpoint,
+}
+
+impl Revisions {
+    /// Creates a [`RevisionsBuilder`] to configure and fetch the revisions of a page
+    ///
+    /// [`RevisionsBuilder`]: RevisionsBuilder
+    pub fn builder() -> RevisionsBuilder<NoTitle, NoEndpoint, NoLanguage> {
+        RevisionsBuilder::default()
+    }
+
+    /// If available, returns the data necessary for fetching the next batch of revisions
+    ///
+    /// [`RevisionsContinue`]: RevisionsContinue
+    pub fn continue_data(&self) -> Option<RevisionsContinue> {
+        self.continue_from
+            .as_ref()
+            .map(|continue_from| RevisionsContinue {
+                title: self.title.clone(),
+                endpoint: self.endpoint.clone(),
+                language: self.language,
+                continue_from: continue_from.clone(),
+            })
+    }
+}
+
+impl std::fmt::Debug for Revisions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Revisions")
+            .field("title", &self.title)
+            .field("revisions", &self.revisions.len())
+            .field("continue_from", &self.continue_from)
+            .finish()
+    }
+}
+
+/// Contains the necessary data for fetching the next batch of revisions. This data can be
+/// extracted from already fetched revisions with [`Revisions::continue_data`]
+///
+/// [`Revisions::continue_data`]: Revisions::continue_data
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RevisionsContinue {
+    /// Title of the page
+    pub title: String,
+    /// API endpoint of the MediaWiki site the page belongs to
+    pub endpoint: Endpoint,
+    /// Language of the page
+    pub language: Language,
+    /// Continuation value returned by the server
+    pub continue_from: String,
+}
+
+/// A single revision of a page
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Revision {
+    /// Id of the revision
+    pub revid: usize,
+    /// Optional: Id of the previous revision. Is `None` for the first revision of a page
+    pub parentid: Option<usize>,
+    /// Timestamp of when the revision was made
+    pub timestamp: String,
+    /// Optional: User that made the revision. Is `None` when the user was hidden
+    pub user: Option<String>,
+    /// Optional: Edit summary of the revision. Is `None` when the comment was hidden
+    pub comment: Option<String>,
+    /// Size in bytes of the page after the revision
+    pub size: usize,
+}
+
+#[doc(hidden)]
+pub struct WithTitle(String);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoTitle;
+
+#[doc(hidden)]
+pub struct WithEndpoint(Endpoint);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoEndpoint;
+
+#[doc(hidden)]
+pub struct WithLanguage(Language);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoLanguage;
+
+/// A fully configured `RevisionsBuilder` that can be used to fetch the revisions. This is a
+/// convenience type
+pub type RevisionsRequest = RevisionsBuilder<WithTitle, WithEndpoint, WithLanguage>;
+
+/// A `RevisionsBuilder` can be used to configure and fetch the revisions of a page
+#[derive(Default)]
+pub struct RevisionsBuilder<T, E, L> {
+    title: T,
+    endpoint: E,
+    language: L,
+    limit: Option<usize>,
+    continue_from: Option<String>,
+    client: Client,
+}
+
+impl<E, L> RevisionsBuilder<NoTitle, E, L> {
+    /// Title of the page to list the revisions of
+    pub fn title(self, title: impl Into<String>) -> RevisionsBuilder<WithTitle, E, L> {
+        RevisionsBuilder {
+            title: WithTitle(title.into()),
+            endpoint: self.endpoint,
+            language: self.language,
+            limit: self.limit,
+            continue_from: self.continue_from,
+            client: self.client,
+        }
+    }
+}
+
+impl<T, L> RevisionsBuilder<T, NoEndpoint, L> {
+    /// API endpoint for the MediaWiki site the page belongs to
+    pub fn endpoint(self, endpoint: Endpoint) -> RevisionsBuilder<T, WithEndpoint, L> {
+        RevisionsBuilder {
+            title: self.title,
+            endpoint: WithEndpoint(endpoint),
+            language: self.language,
+            limit: self.limit,
+            continue_from: self.continue_from,
+            client: self.client,
+        }
+    }
+}
+
+impl<T, E> RevisionsBuilder<T, E, NoLanguage> {
+    /// Language of the page
+    pub fn language(self, language: Language) -> RevisionsBuilder<T, E, WithLanguage> {
+        RevisionsBuilder {
+            title: self.title,
+            endpoint: self.endpoint,
+            language: WithLanguage(language),
+            limit: self.limit,
+            continue_from: self.continue_from,
+            client: self.client,
+        }
+    }
+}
+
+impl<T, E, L> RevisionsBuilder<T, E, L> {
+    /// Http client used to send the request
+    ///
+    /// Default: a shared [`Client`] with the default configuration
+    ///
+    /// [`Client`]: Client
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// How many revisions to return. The value must be between 1 and 500
+    ///
+    /// Default: `10`
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// When more revisions are available, use this to continue (See
+    /// [`Revisions::continue_data`])
+    ///
+    /// [`Revisions::continue_data`]: Revisions::continue_data
+    pub fn continue_from(mut self, continue_from: impl Into<String>) -> Self {
+        self.continue_from = Some(continue_from.into());
+        self
+    }
+}
+
+impl RevisionsBuilder<WithTitle, WithEndpoint, WithLanguage> {
+    /// Fetches the revisions of the page, starting with the newest one
+    ///
+    /// # Error
+    ///
+    /// This function returns an error when one of the following things happens:
+    /// - The request to the server could not be made
+    /// - The server returned an error
+    /// - The page doesn't exist
+    /// - The returned result could not interpreted as `Revisions`
+    pub async fn fetch(self) -> Result<Revisions> {
+        async fn action_query(
+            client: &Client,
+            params: Vec<(&str, String)>,
+            endpoint: Endpoint,
+        ) -> Result<Response> {
+            let request = client
+                .get(endpoint.api_url().clone())
+                .query(&[
+                    ("action", "query"),
+                    ("format", "json"),
+                    ("formatversion", "2"),
+                ])
+                .query(&params);
+            client.send(request).await
+        }
+
+        let mut params = vec![
+            ("prop", "revisions".to_string()),
+            ("titles", self.title.0.clone()),
+            ("rvprop", "ids|timestamp|user|comment|size".to_string()),
+        ];
+
+        if let Some(limit) = self.limit {
+            params.push(("rvlimit", limit.to_string()));
+        }
+
+        if let Some(continue_from) = self.continue_from {
+            params.push(("rvcontinue", continue_from));
+        }
+
+        let response = action_query(&self.client, params, self.endpoint.0.clone()).await?;
+        let res_json = self.client.response_json(response).await?;
+
+        revisions_from_json(res_json, self.endpoint.0, self.language.0)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawRevision {
+    revid: usize,
+    #[serde(default)]
+    parentid: usize,
+    timestamp: String,
+    user: Option<String>,
+    comment: Option<String>,
+    size: usize,
+}
+
+#[derive(Deserialize)]
+struct RawPage {
+    title: String,
+    #[serde(default)]
+    missing: bool,
+    #[serde(default)]
+    revisions: Vec<RawRevision>,
+}
+
+fn revisions_from_json(
+    res_json: serde_json::Value,
+    endpoint: Endpoint,
+    language: Language,
+) -> Result<Revisions> {
+    let continue_from = res_json
+        .get("continue")
+        .and_then(|x| x.get("rvcontinue"))
+        .and_then(|x| x.as_str())
+        .map(|x| x.to_string());
+
+    let page_json = res_json
+        .get("query")
+        .and_then(|x| x.get("pages"))
+        .and_then(|x| x.as_array())
+        .and_then(|x| x.first())
+        .context(MissingFieldSnafu { what: "page" })?
+        .to_owned();
+
+    let page: RawPage =
+        serde_json::from_value(page_json).context(DeserializeSnafu { what: "page" })?;
+    if page.missing {
+        return MissingPageSnafu { title: page.title }.fail();
+    }
+
+    Ok(Revisions {
+        title: page.title,
+        revisions: page
+            .revisions
+            .into_iter()
+            .map(|raw| Revision {
+                revid: raw.revid,
+                // the first revision of a page has a parentid of 0
+                parentid: Some(raw.parentid).filter(|x| *x != 0),
+                timestamp: raw.timestamp,
+                user: raw.user,
+                comment: raw.comment,
+                size: raw.size,
+            })
+            .collect(),
+        continue_from,
+        language,
+        endpoint,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::revisions_from_json;
+    use crate::{languages::Language, Endpoint};
+
+    #[test]
+    fn test_revisions_from_fixture() {
+        let res_json = serde_json::from_str(include_str!("../tests/fixtures/query_revisions.json"))
+            .expect("the fixture should be valid json");
+        let endpoint = Endpoint::parse("https://en.wikipedia.org/w/api.php").unwrap();
+
+        let revisions = revisions_from_json(res_json, endpoint, Language::English).unwrap();
+        assert_eq!(revisions.title, "Meaning");
+        assert_eq!(
+            revisions.continue_from.as_deref(),
+            Some("20230212151455|1138977839")
+        );
+        assert_eq!(revisions.revisions.len(), 3);
+
+        let newest = &revisions.revisions[0];
+        assert_eq!(newest.revid, 1214587311);
+        assert_eq!(newest.parentid, Some(1197262329));
+        assert_eq!(newest.user.as_deref(), Some("Citation bot"));
+        assert_eq!(newest.size, 2310);
+
+        let hidden = &revisions.revisions[2];
+        assert!(hidden.user.is_none());
+        assert!(hidden.comment.is_none());
+        assert!(hidden.parentid.is_none());
+    }
+
+    #[test]
+    fn test_revisions_of_missing_page() {
+        let res_json = serde_json::json!({
+            "query": { "pages": [{ "ns": 0, "title": "Does not exist", "missing": true }] }
+        });
+        let endpoint = Endpoint::parse("https://en.wikipedia.org/w/api.php").unwrap();
+
+        assert!(revisions_from_json(res_json, endpoint, Language::English).is_err());
+    }
+}
@@ -1,8 +1,9 @@
-use anyhow::{anyhow, Context, Result};
+use crate::error::{DeserializeSnafu, MissingFieldSnafu, Result};
+use snafu::{OptionExt, ResultExt};
 
 use bitflags::bitflags;
 use core::fmt;
-use reqwest::{Client, Response};
+use reqwest::Response;
 use scraper::Html;
 use serde::Deserialize;
 use serde_repr::{Deserialize_repr, Serialize_repr};
@@ -10,7 +11,7 @@ use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Write;
 
-use crate::Endpoint;
+use crate::{Client, Endpoint};
 
 use crate::languages::Language;
 
@@ -93,15 +94,21 @@ pub struct SearchInfo {
 ///
 /// # Example
 ///
-/// ```
+/// ```no_run
+/// # use wiki_api::search::Search;
+/// # async fn example(search: Search) -> wiki_api::Result<()> {
 /// // This will continue the already completed search
-/// let continue_data = search.continue_data()?;
-/// let continued_search = Search::builder()
-///     .query(continue_data.query)
-///     .endpoint(continue_data.endpoint)
-///     .langauge(continue_data.language)
-///     .offset(continue_data.offset)
-///     .search()?;
+/// if let Some(continue_data) = search.continue_data() {
+///     let continued_search = Search::builder()
+///         .query(continue_data.query)
+///         .endpoint(continue_data.endpoint)
+///         .language(continue_data.language)
+///         .offset(continue_data.offset)
+///         .search()
+///         .await?;
+/// }
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// [`Search::continue_data`]: Search::continue_data
@@ -516,6 +523,8 @@ pub struct SearchBuilder<Q, E, L> {
     interwiki: Option<bool>,
     rewrites: Option<bool>,
     sort_order: Option<SortOrder>,
+    variant: Option<String>,
+    client: Client,
 }
 
 impl<E, L> SearchBuilder<NoQuery, E, L> {
@@ -535,6 +544,8 @@ impl<E, L> SearchBuilder<NoQuery, E, L> {
             interwiki: self.interwiki,
             rewrites: self.rewrites,
             sort_order: self.sort_order,
+            variant: self.variant,
+            client: self.client,
         }
     }
 }
@@ -556,6 +567,8 @@ impl<Q, L> SearchBuilder<Q, NoEndpoint, L> {
             interwiki: self.interwiki,
             rewrites: self.rewrites,
             sort_order: self.sort_order,
+            variant: self.variant,
+            client: self.client,
         }
     }
 }
@@ -577,11 +590,23 @@ impl<Q, E> SearchBuilder<Q, E, NoLanguage> {
             interwiki: self.interwiki,
             rewrites: self.rewrites,
             sort_order: self.sort_order,
+            variant: self.variant,
+            client: self.client,
         }
     }
 }
 
 impl<Q, E, L> SearchBuilder<Q, E, L> {
+    /// Http client used to send the request
+    ///
+    /// Default: a shared [`Client`] with the default configuration
+    ///
+    /// [`Client`]: Client
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     /// Search only in this specific namespace
     pub fn namespace(mut self, namespace: Namespace) -> Self {
         self.namespace = Some(namespace);
@@ -675,6 +700,15 @@ impl<Q, E, L> SearchBuilder<Q, E, L> {
         self.sort_order = Some(sort_order);
         self
     }
+
+    /// Convert the titles and snippets of the results to this variant of the language, e.g.
+    /// `zh-hant` or `sr-el`. The variants of a language are listed by [`Language::variants`]
+    ///
+    /// [`Language::variants`]: Language::variants
+    pub fn variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
 }
 
 impl SearchBuilder<WithQuery, WithEndpoint, WithLanguage> {
@@ -683,13 +717,18 @@ impl SearchBuilder<WithQuery, WithEndpoint, WithLanguage> {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
+    /// # use wiki_api::{languages::Language, search::Search, Endpoint};
+    /// # async fn example() -> wiki_api::Result<()> {
     /// // This searches for the pages containing 'meaning' in the english wikipedia
     /// let search = Search::builder()
     ///     .query("meaning")
-    ///     .endpoint(Url::from("https://en.wikipedia.org/w/api.php")?)
+    ///     .endpoint(Endpoint::wikipedia(Language::English))
     ///     .language(Language::English)
-    ///     .search()?;
+    ///     .search()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
     /// ```
     ///
     /// # Error
@@ -699,25 +738,20 @@ impl SearchBuilder<WithQuery, WithEndpoint, WithLanguage> {
     /// - The server returned an error
     /// - The returned result could not interpreted as a `Search`
     pub async fn search(self) -> Result<Search> {
-        async fn action_query(params: Vec<(&str, String)>, endpoint: Endpoint) -> Result<Response> {
-            Client::new()
-                .get(endpoint)
-                .header(
-                    "User-Agent",
-                    format!(
-                        "wiki-tui/{} (https://github.com/Builditluc/wiki-tui)",
-                        env!("CARGO_PKG_VERSION")
-                    ),
-                )
+        async fn action_query(
+            client: &Client,
+            params: Vec<(&str, String)>,
+            endpoint: Endpoint,
+        ) -> Result<Response> {
+            let request = client
+                .get(endpoint.api_url().clone())
                 .query(&[
                     ("action", "query"),
                     ("format", "json"),
                     ("formatversion", "2"),
                 ])
-                .query(&params)
-                .send()
-                .await
-                .context("failed sending the request")
+                .query(&params);
+            client.send(request).await
         }
 
         let mut params = vec![
@@ -770,18 +804,12 @@ impl SearchBuilder<WithQuery, WithEndpoint, WithLanguage> {
             params.push(("srsort", sort_order.to_string()));
         }
 
-        let response = action_query(params, self.endpoint.0.clone())
-            .await?
-            .error_for_status()
-            .context("the server returned an error")?;
+        if let Some(variant) = self.variant {
+            params.push(("variant", variant));
+        }
 
-        let res_json: serde_json::Value = serde_json::from_str(
-            &response
-                .text()
-                .await
-                .context("failed reading the response")?,
-        )
-        .context("failed interpreting the response as json")?;
+        let response = action_query(&self.client, params, self.endpoint.0.clone()).await?;
+        let res_json = self.client.response_json(response).await?;
 
         let continue_offset = res_json
             .get("continue")
@@ -812,20 +840,20 @@ impl SearchBuilder<WithQuery, WithEndpoint, WithLanguage> {
                 .get("query")
                 .and_then(|x| x.get("search"))
                 .and_then(|x| x.as_array())
-                .ok_or_else(|| anyhow!("missing the search results"))?
+                .context(MissingFieldSnafu {
+                    what: "search results",
+                })?
                 .to_owned();
 
             macro_rules! value_from_json {
                 ($result: ident, $val: expr) => {
-                    serde_json::from_value($result.get($val).map(|x| x.to_owned()).ok_or_else(
-                        || {
-                            anyhow!(
-                                "couldn't find '{}'
- in the result",
-                                stringify!($val)
-                            )
-                        },
-                    )?)?
+                    serde_json::from_value(
+                        $result
+                            .get($val)
+                            .map(|x| x.to_owned())
+                            .context(MissingFieldSnafu { what: $val })?,
+                    )
+                    .context(DeserializeSnafu { what: $val })?
                 };
             }
 
@@ -0,0 +1,328 @@
+use std::sync::{Mutex, RwLock};
+
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+use snafu::{OptionExt, ResultExt};
+
+use crate::{
+    error::{DeserializeSnafu, MissingFieldSnafu, Result},
+    languages::{Direction, Language, LANGUAGES},
+    Client, Endpoint,
+};
+
+/// The registry used to look up languages, see [`LanguageRegistry::install`]
+static REGISTRY: RwLock<Option<&'static LanguageRegistry>> = RwLock::new(None);
+/// Codes of the languages that were deserialized without being known, see [`intern`]
+static INTERNED_CODES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+/// A language of the site matrix of the Wikimedia projects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SiteLanguage {
+    /// Language code, also the subdomain of its wikis
+    pub code: String,
+    /// Name of the language in English
+    pub name: String,
+    /// Name of the language in the language itself
+    pub local_name: String,
+    /// Whether the language is written from right to left
+    pub rtl: bool,
+    /// Whether the Wikipedia in this language is closed. Closed wikis can still be read but
+    /// aren't edited anymore
+    pub closed: bool,
+}
+
+impl SiteLanguage {
+    /// Returns the language, which is [`Language::Other`] for languages that aren't built in
+    ///
+    /// [`Language::Other`]: Language::Other
+    pub fn language(&'static self) -> Language {
+        Language::builtin(&self.code).unwrap_or(Language::Other(&self.code))
+    }
+}
+
+/// A list of languages that can be refreshed while the program is running, for example to know
+/// about wikis that were created after the program was built
+///
+/// The languages built into [`Language`] are generated from a copy of the site matrix at compile
+/// time. A registry fetched with [`LanguageRegistry::fetch`] can be merged with them and
+/// installed, after which parsing a [`Language`] also finds the languages of the registry
+///
+/// # Example
+///
+/// ```no_run
+/// # use wiki_api::{sitematrix::LanguageRegistry, Client, Endpoint};
+/// # async fn example() -> wiki_api::Result<()> {
+/// let endpoint = Endpoint::parse("https://meta.wikimedia.org/w/api.php").unwrap();
+/// let mut registry = LanguageRegistry::builtin();
+/// registry.merge(LanguageRegistry::fetch(&Client::default(), endpoint).await?);
+/// registry.install();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageRegistry {
+    languages: Vec<SiteLanguage>,
+}
+
+impl LanguageRegistry {
+    /// Creates a registry containing the built in languages. None of them are marked as closed
+    pub fn builtin() -> Self {
+        LanguageRegistry {
+            languages: LANGUAGES
+                .iter()
+                .map(|language| SiteLanguage {
+                    code: language.code().to_string(),
+                    name: language.name().to_string(),
+                    local_name: language.local_name().to_string(),
+                    rtl: language.direction() == Direction::RightToLeft,
+                    closed: false,
+                })
+                .collect(),
+        }
+    }
+
+    /// Fetches the languages of the site matrix, the endpoint can be any Wikimedia wiki
+    ///
+    /// # Error
+    ///
+    /// This function returns an error when one of the following things happens:
+    /// - The request to the server could not be made
+    /// - The server returned an error
+    /// - The returned result could not interpreted as a site matrix
+    pub async fn fetch(client: &Client, endpoint: Endpoint) -> Result<Self> {
+        async fn action_sitematrix(client: &Client, endpoint: Endpoint) -> Result<Response> {
+            let request = client.get(endpoint.api_url().clone()).query(&[
+                ("action", "sitematrix"),
+                ("format", "json"),
+                ("formatversion", "2"),
+                ("smtype", "language"),
+                ("smlangprop", "code|name|localname|dir|site"),
+                ("smsiteprop", "code"),
+                ("smlimit", "max"),
+            ]);
+            client.send(request).await
+        }
+
+        let response = action_sitematrix(client, endpoint).await?;
+        let res_json = client.response_json(response).await?;
+
+        registry_from_json(res_json)
+    }
+
+    /// Adds the languages of the other registry. Languages that are in both registries are
+    /// replaced by the ones of the other registry
+    pub fn merge(&mut self, other: LanguageRegistry) {
+        for language in other.languages {
+            match self.languages.iter_mut().find(|x| x.code == language.code) {
+                Some(existing) => *existing = language,
+                None => self.languages.push(language),
+            }
+        }
+    }
+
+    pub fn languages(&self) -> &[SiteLanguage] {
+        &self.languages
+    }
+
+    /// Returns the language with this code
+    pub fn get(&self, code: &str) -> Option<&SiteLanguage> {
+        self.languages
+            .iter()
+            .find(|language| language.code.eq_ignore_ascii_case(code))
+    }
+
+    /// Returns the language with this code, English name or local name
+    pub fn find(&self, from: &str) -> Option<&SiteLanguage> {
+        let from = from.to_lowercase();
+        self.languages.iter().find(|language| {
+            language.code.to_lowercase() == from
+                || language.name.to_lowercase() == from
+                || language.local_name.to_lowercase() == from
+        })
+    }
+
+    /// Uses this registry to look up languages. Languages borrow their names from the installed
+    /// registry, so it's kept until the program exits, even after installing another one
+    pub fn install(self) {
+        let registry: &'static LanguageRegistry = Box::leak(Box::new(self));
+        *REGISTRY.write().unwrap() = Some(registry);
+    }
+
+    /// Returns the installed registry
+    pub fn installed() -> Option<&'static LanguageRegistry> {
+        *REGISTRY.read().unwrap()
+    }
+
+    /// Goes back to only using the built in languages
+    pub fn uninstall() {
+        *REGISTRY.write().unwrap() = None;
+    }
+}
+
+/// Returns all languages, the ones of the installed registry or the built in ones
+pub fn languages() -> Vec<Language> {
+    match LanguageRegistry::installed() {
+        Some(registry) => registry
+            .languages
+            .iter()
+            .map(|language| language.language())
+            .collect(),
+        None => LANGUAGES.to_vec(),
+    }
+}
+
+/// Finds a language in the installed registry
+pub(crate) fn find_language(from: &str) -> Option<Language> {
+    LanguageRegistry::installed()?
+        .find(from)
+        .map(|language| language.language())
+}
+
+/// Returns the code with a static lifetime, to be used in [`Language::Other`]. Every code is only
+/// leaked once
+pub(crate) fn intern(code: &str) -> &'static str {
+    let mut codes = INTERNED_CODES.lock().unwrap();
+    match codes.iter().find(|interned| **interned == code) {
+        Some(interned) => interned,
+        None => {
+            let interned: &'static str = Box::leak(code.to_string().into_boxed_str());
+            codes.push(interned);
+            interned
+        }
+    }
+}
+
+/// Returns the English name, local name and code of a language that isn't built in
+pub(crate) fn language_data(code: &'static str) -> (&'static str, &'static str, &'static str) {
+    match LanguageRegistry::installed().and_then(|registry| registry.get(code)) {
+        Some(language) => (&language.name, &language.local_name, code),
+        None => (code, code, code),
+    }
+}
+
+/// Returns the direction of a language that isn't built in
+pub(crate) fn direction(code: &str) -> Direction {
+    match LanguageRegistry::installed().and_then(|registry| registry.get(code)) {
+        Some(language) if language.rtl => Direction::RightToLeft,
+        _ => Direction::LeftToRight,
+    }
+}
+
+impl Language {
+    /// Whether the Wikipedia in this language is closed. Only known when a
+    /// [`LanguageRegistry`] fetched from the site matrix is installed
+    pub fn is_closed(&self) -> bool {
+        LanguageRegistry::installed()
+            .and_then(|registry| registry.get(self.code()))
+            .map(|language| language.closed)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Deserialize)]
+struct RawSite {
+    code: String,
+    #[serde(default)]
+    closed: bool,
+}
+
+#[derive(Deserialize)]
+struct RawLanguage {
+    code: String,
+    name: String,
+    localname: String,
+    #[serde(default)]
+    dir: String,
+    #[serde(default)]
+    site: Vec<RawSite>,
+}
+
+fn registry_from_json(res_json: serde_json::Value) -> Result<LanguageRegistry> {
+    let sitematrix = res_json
+        .get("sitematrix")
+        .and_then(|x| x.as_object())
+        .context(MissingFieldSnafu { what: "sitematrix" })?;
+
+    // the languages are listed by their index, next to the count and the special wikis
+    let mut languages = Vec::new();
+    for (_, raw) in sitematrix
+        .iter()
+        .filter(|(key, _)| key.parse::<usize>().is_ok())
+    {
+        let raw: RawLanguage = serde_json::from_value(raw.to_owned())
+            .context(DeserializeSnafu { what: "language" })?;
+        languages.push(SiteLanguage {
+            closed: raw
+                .site
+                .iter()
+                .any(|site| site.code == "wiki" && site.closed),
+            rtl: raw.dir == "rtl",
+            code: raw.code,
+            name: raw.localname,
+            local_name: raw.name,
+        });
+    }
+
+    Ok(LanguageRegistry { languages })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{registry_from_json, LanguageRegistry};
+    use crate::languages::{Direction, Language};
+
+    fn registry() -> LanguageRegistry {
+        let res_json = serde_json::from_str(include_str!("../tests/fixtures/sitematrix.json"))
+            .expect("the fixture should be valid json");
+        registry_from_json(res_json).unwrap()
+    }
+
+    #[test]
+    fn test_registry_from_fixture() {
+        let registry = registry();
+        assert_eq!(registry.languages().len(), 4);
+
+        let afar = registry.get("aa").unwrap();
+        assert_eq!(afar.name, "Afar");
+        assert_eq!(afar.local_name, "Qafár af");
+        assert!(afar.closed);
+
+        let arabic = registry.get("ar").unwrap();
+        assert!(arabic.rtl);
+        assert!(!arabic.closed);
+
+        let obolo = registry.find("Obolo").unwrap();
+        assert_eq!(obolo.code, "ann");
+    }
+
+    #[test]
+    fn test_merge_and_install() {
+        let mut merged = LanguageRegistry::builtin();
+        let n_builtin = merged.languages().len();
+        merged.merge(registry());
+        assert_eq!(merged.languages().len(), n_builtin + 1);
+
+        merged.install();
+        let obolo: Language = "ann".parse().unwrap();
+        assert_eq!(obolo, Language::Other("ann"));
+        assert_eq!(obolo.name(), "Obolo");
+        assert_eq!(obolo.direction(), Direction::LeftToRight);
+        assert!("aa".parse::<Language>().unwrap().is_closed());
+        assert_eq!("english".parse::<Language>().unwrap(), Language::English);
+
+        LanguageRegistry::uninstall();
+        assert!("ann".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_unknown_code() {
+        // a page saved while a registry was installed
+        let obolo: Language = serde_json::from_str("\"ann\"").unwrap();
+        assert_eq!(obolo, Language::Other("ann"));
+        assert_eq!(obolo.code(), "ann");
+        assert_eq!(serde_json::to_string(&obolo).unwrap(), "\"ann\"");
+
+        let unknown: Language = serde_json::from_str("\"UNKNOWN\"").unwrap();
+        assert_eq!(unknown, Language::Unknown);
+    }
+}
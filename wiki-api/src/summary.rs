@@ -0,0 +1,262 @@
+use reqwest::Response;
+use serde::Deserialize;
+use snafu::{OptionExt, ResultExt};
+
+use crate::{
+    error::{DeserializeSnafu, MissingFieldSnafu, MissingPageSnafu, Result},
+    languages::Language,
+    Client, Endpoint,
+};
+
+/// A short summary of a page: its description and the introduction as plain text
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Summary {
+    /// Title of the page. When the requested title was a redirect, this is the title of the page
+    /// the redirect points to
+    pub title: String,
+    /// PageId of the page
+    pub pageid: usize,
+    /// Short description of the page (e.g. "General-purpose programming language"). Not every
+    /// page or MediaWiki site has one
+    pub description: Option<String>,
+    /// Introduction of the page, the text before the first section, as plain text
+    pub extract: String,
+
+    /// Language of the page
+    pub language: Language,
+    /// API endpoint of the MediaWiki site the page belongs to
+    pub endpoint: Endpoint,
+}
+
+impl Summary {
+    /// Creates a [`SummaryBuilder`] to configure and fetch the summary of a page
+    ///
+    /// [`SummaryBuilder`]: SummaryBuilder
+    pub fn builder() -> SummaryBuilder<NoTitle, NoEndpoint, NoLanguage> {
+        SummaryBuilder::default()
+    }
+}
+
+#[doc(hidden)]
+pub struct WithTitle(String);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoTitle;
+
+#[doc(hidden)]
+pub struct WithEndpoint(Endpoint);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoEndpoint;
+
+#[doc(hidden)]
+pub struct WithLanguage(Language);
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct NoLanguage;
+
+/// A fully configured `SummaryBuilder` that can be used to fetch the summary. This is a
+/// convenience type
+pub type SummaryRequest = SummaryBuilder<WithTitle, WithEndpoint, WithLanguage>;
+
+/// A `SummaryBuilder` can be used to configure and fetch the summary of a page
+#[derive(Default)]
+pub struct SummaryBuilder<T, E, L> {
+    title: T,
+    endpoint: E,
+    language: L,
+    sentences: Option<usize>,
+    client: Client,
+}
+
+impl<E, L> SummaryBuilder<NoTitle, E, L> {
+    /// Title of the page to summarize
+    pub fn title(self, title: impl Into<String>) -> SummaryBuilder<WithTitle, E, L> {
+        SummaryBuilder {
+            title: WithTitle(title.into()),
+            endpoint: self.endpoint,
+            language: self.language,
+            sentences: self.sentences,
+            client: self.client,
+        }
+    }
+}
+
+impl<T, L> SummaryBuilder<T, NoEndpoint, L> {
+    /// API endpoint for the MediaWiki site the page belongs to
+    pub fn endpoint(self, endpoint: Endpoint) -> SummaryBuilder<T, WithEndpoint, L> {
+        SummaryBuilder {
+            title: self.title,
+            endpoint: WithEndpoint(endpoint),
+            language: self.language,
+            sentences: self.sentences,
+            client: self.client,
+        }
+    }
+}
+
+impl<T, E> SummaryBuilder<T, E, NoLanguage> {
+    /// Language of the page
+    pub fn language(self, language: Language) -> SummaryBuilder<T, E, WithLanguage> {
+        SummaryBuilder {
+            title: self.title,
+            endpoint: self.endpoint,
+            language: WithLanguage(language),
+            sentences: self.sentences,
+            client: self.client,
+        }
+    }
+}
+
+impl<T, E, L> SummaryBuilder<T, E, L> {
+    /// Http client used to send the request
+    ///
+    /// Default: a shared [`Client`] with the default configuration
+    ///
+    /// [`Client`]: Client
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Only return this many sentences of the introduction. The value must be between 1 and 10
+    ///
+    /// Default: the whole introduction
+    pub fn sentences(mut self, sentences: usize) -> Self {
+        self.sentences = Some(sentences);
+        self
+    }
+}
+
+impl SummaryBuilder<WithTitle, WithEndpoint, WithLanguage> {
+    /// Fetches the summary of the page. Redirects are followed
+    ///
+    /// This uses the TextExtracts extension of MediaWiki, which is installed on all Wikimedia
+    /// sites but might be missing on other MediaWiki sites
+    ///
+    /// # Error
+    ///
+    /// This function returns an error when one of the following things happens:
+    /// - The request to the server could not be made
+    /// - The server returned an error
+    /// - The page doesn't exist
+    /// - The returned result could not interpreted as a `Summary`
+    pub async fn fetch(self) -> Result<Summary> {
+        async fn action_query(
+            client: &Client,
+            params: Vec<(&str, String)>,
+            endpoint: Endpoint,
+        ) -> Result<Response> {
+            let request = client
+                .get(endpoint.api_url().clone())
+                .query(&[
+                    ("action", "query"),
+                    ("format", "json"),
+                    ("formatversion", "2"),
+                ])
+                .query(&params);
+            client.send(request).await
+        }
+
+        let mut params = vec![
+            ("prop", "extracts|description".to_string()),
+            ("titles", self.title.0),
+            ("redirects", "1".to_string()),
+            ("exintro", "1".to_string()),
+            ("explaintext", "1".to_string()),
+        ];
+
+        if let Some(sentences) = self.sentences {
+            params.push(("exsentences", sentences.to_string()));
+        }
+
+        let response = action_query(&self.client, params, self.endpoint.0.clone()).await?;
+        let res_json = self.client.response_json(response).await?;
+
+        summary_from_json(res_json, self.endpoint.0, self.language.0)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawSummary {
+    title: String,
+    #[serde(default)]
+    pageid: usize,
+    #[serde(default)]
+    missing: bool,
+    #[serde(default)]
+    invalid: bool,
+    description: Option<String>,
+    #[serde(default)]
+    extract: String,
+}
+
+fn summary_from_json(
+    res_json: serde_json::Value,
+    endpoint: Endpoint,
+    language: Language,
+) -> Result<Summary> {
+    let page_json = res_json
+        .get("query")
+        .and_then(|x| x.get("pages"))
+        .and_then(|x| x.as_array())
+        .and_then(|x| x.first())
+        .context(MissingFieldSnafu { what: "page" })?
+        .to_owned();
+
+    let raw: RawSummary =
+        serde_json::from_value(page_json).context(DeserializeSnafu { what: "summary" })?;
+    if raw.missing || raw.invalid {
+        return MissingPageSnafu { title: raw.title }.fail();
+    }
+
+    Ok(Summary {
+        title: raw.title,
+        pageid: raw.pageid,
+        description: raw.description.filter(|x| !x.is_empty()),
+        extract: raw.extract.trim().to_string(),
+        language,
+        endpoint,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::summary_from_json;
+    use crate::{languages::Language, Endpoint};
+
+    #[test]
+    fn test_summary_from_fixture() {
+        let res_json = serde_json::from_str(include_str!("../tests/fixtures/query_extracts.json"))
+            .expect("the fixture should be valid json");
+        let endpoint = Endpoint::parse("https://en.wikipedia.org/w/api.php").unwrap();
+
+        let summary = summary_from_json(res_json, endpoint, Language::English).unwrap();
+        // the requested title was a redirect
+        assert_eq!(summary.title, "Rust (programming language)");
+        assert_eq!(summary.pageid, 29414838);
+        assert_eq!(
+            summary.description.as_deref(),
+            Some("General-purpose programming language")
+        );
+        assert!(summary
+            .extract
+            .starts_with("Rust is a general-purpose programming language"));
+        assert!(!summary.extract.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_summary_of_missing_page() {
+        let res_json = serde_json::json!({
+            "batchcomplete": true,
+            "query": { "pages": [{ "ns": 0, "title": "Does not exist", "missing": true }] }
+        });
+        let endpoint = Endpoint::parse("https://en.wikipedia.org/w/api.php").unwrap();
+
+        let error = summary_from_json(res_json, endpoint, Language::English).unwrap_err();
+        assert!(error.is_missing_page());
+    }
+}